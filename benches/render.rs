@@ -0,0 +1,72 @@
+// =============================================================================
+// Benchmarks — how long a frame takes to draw, by how much is in Output
+//
+//   cargo bench                       everything
+//   cargo bench -- short_lines/1000000
+//   cargo bench -- --save-baseline before    then, after a change:
+//   cargo bench -- --baseline before
+//
+// Each one fills Output with a scrollback of some size and draws the whole
+// screen into a 120 × 40 TestBackend, following the end as a live session
+// would. short_lines is the shape of ordinary output; long_lines is a
+// minified log or a JSON dump — more text per row than fits. Work done per
+// frame that grows with the scrollback shows up as the 1M case running away
+// from the others. Config and history come from a scratch directory, so
+// the user's own layout doesn't change the numbers.
+// =============================================================================
+
+use std::{env, hint::black_box, sync::Once};
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use ratatui::{Terminal, backend::TestBackend};
+use tui_template::{App, render};
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 40;
+const LONG: usize = 2000; // characters in a long line
+
+static SCRATCH: Once = Once::new();
+
+fn app(lines: usize, width: usize) -> App {
+    SCRATCH.call_once(|| {
+        let dir = env::temp_dir().join("tui-template-bench");
+        for var in ["XDG_CONFIG_HOME", "XDG_DATA_HOME", "XDG_STATE_HOME", "HOME"] {
+            // SAFETY: the first benchmark, before any App has started a thread
+            unsafe { env::set_var(var, &dir) };
+        }
+    });
+    let mut app = App::new();
+    let text = "the quick brown fox jumps over the lazy dog ".repeat(width / 44 + 1);
+    for i in 0..lines {
+        app.print(format!("{i:>7}  {}", &text[..width]));
+    }
+    app
+}
+
+fn bench(c: &mut Criterion, name: &str, sizes: &[usize], width: usize) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+    for &lines in sizes {
+        let app = app(lines, width);
+        let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).expect("a test terminal");
+        group.bench_with_input(BenchmarkId::from_parameter(lines), &app, |b, app| {
+            b.iter(|| {
+                terminal
+                    .draw(|frame| render(frame, black_box(app)))
+                    .expect("drawing into memory");
+            });
+        });
+    }
+    group.finish();
+}
+
+fn short_lines(c: &mut Criterion) {
+    bench(c, "short_lines", &[100, 10_000, 1_000_000], 40);
+}
+
+fn long_lines(c: &mut Criterion) {
+    bench(c, "long_lines", &[100, 10_000], LONG);
+}
+
+criterion_group!(benches, short_lines, long_lines);
+criterion_main!(benches);
@@ -0,0 +1,99 @@
+// =============================================================================
+// Fuzz target — arbitrary key sequences into App, looking for panics
+//
+//   cargo install cargo-fuzz
+//   cargo +nightly fuzz run input
+//
+// Each byte of the input picks a key from KEYS — typing (a few non-ASCII
+// characters among them, for UTF-8 slips), editing, history, scrolling,
+// focus, popups — or a click (at the next two bytes), a paste (of as many
+// of KEYS' characters as the next byte says, picked by the bytes after) or
+// a focus change. Enter runs the line only if its first word is in SAFE —
+// anything else could start shell commands or write files wherever — and
+// clears it otherwise. Config, history and sessions go to a scratch
+// directory, never the real ones.
+// =============================================================================
+
+#![no_main]
+
+use std::{env, sync::Once};
+
+use libfuzzer_sys::fuzz_target;
+use tui_template::{App, Input, KeyBinding};
+
+const KEYS: &[&str] = &[
+    "a",
+    "z",
+    "1",
+    "-",
+    " ",
+    "$",
+    "=",
+    "/",
+    "\"",
+    "é",
+    "字",
+    "🦀",
+    "enter",
+    "backspace",
+    "delete",
+    "ctrl-u",
+    "up",
+    "down",
+    "left",
+    "right",
+    "home",
+    "end",
+    "tab",
+    "pageup",
+    "pagedown",
+    "f12",
+    "ctrl-y",
+    "esc",
+];
+
+const SAFE: &[&str] = &[
+    "hello", "help", "set", "unset", "vars", "metric", "toast", "crumbs", "filter", "clips",
+    "keys", "clear",
+];
+
+static SCRATCH: Once = Once::new();
+
+fuzz_target!(|data: &[u8]| {
+    SCRATCH.call_once(|| {
+        let dir = env::temp_dir().join("tui-template-fuzz");
+        for var in ["XDG_CONFIG_HOME", "XDG_DATA_HOME", "XDG_STATE_HOME", "HOME"] {
+            // SAFETY: the first run, before any App has started a thread
+            unsafe { env::set_var(var, &dir) };
+        }
+    });
+    let keys: Vec<KeyBinding> = KEYS.iter().map(|k| k.parse().expect("a key")).collect();
+    let chars: Vec<char> = KEYS.iter().filter_map(|k| k.parse().ok()).collect();
+    let enter: KeyBinding = "enter".parse().expect("a key");
+    let clear: KeyBinding = "ctrl-u".parse().expect("a key");
+
+    let mut app = App::new();
+    let mut bytes = data.iter().copied();
+    while let Some(byte) = bytes.next() {
+        let input = match byte as usize % (keys.len() + 3) {
+            n if n < keys.len() => Input::Key(keys[n]),
+            n if n == keys.len() => Input::Click {
+                column: bytes.next().unwrap_or_default().into(),
+                row: bytes.next().unwrap_or_default().into(),
+            },
+            n if n == keys.len() + 1 => {
+                let len = bytes.next().unwrap_or_default().into();
+                let picks = bytes.by_ref().take(len);
+                Input::Paste(picks.map(|b| chars[b as usize % chars.len()]).collect())
+            }
+            _ => Input::Focus(byte % 2 == 0),
+        };
+        let line = app.input();
+        let first = line.split_whitespace().next().unwrap_or_default();
+        if input == Input::Key(enter) && !SAFE.contains(&first) {
+            app.handle(Input::Key(clear));
+            continue;
+        }
+        app.handle(input);
+    }
+});
@@ -0,0 +1,101 @@
+// =============================================================================
+// Alerts — the bell, or a flash of Output's border, so things get noticed
+// without watching the screen
+//
+//   [alerts]                     # in config.toml
+//   on_error = "flash"           # a command failed, or an error toast
+//   on_long_job = "bell"         # a command's background job finished…
+//   long_job_seconds = 10        # …after running at least this long
+//
+// Each is "bell", "flash", "both" or "off". The bell is the terminal's own,
+// so it beeps or blinks the window as the terminal is set up to (its
+// "visual bell" setting); the flash turns Output's border red for FLASH.
+// =============================================================================
+
+use std::{
+    io::{self, Write},
+    mem,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock;
+
+const FLASH: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Alert {
+    Bell,
+    Flash,
+    Both,
+    Off,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertConfig {
+    pub on_error: Alert,
+    pub on_long_job: Alert,
+    pub long_job_seconds: u64,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            on_error: Alert::Flash,
+            on_long_job: Alert::Bell,
+            long_job_seconds: 10,
+        }
+    }
+}
+
+impl AlertConfig {
+    pub fn long_job(&self) -> Duration {
+        Duration::from_secs(self.long_job_seconds)
+    }
+}
+
+// Raised alerts the main loop hasn't acted on yet. Only the TUI loop rings
+// and draws them, so batch mode's stdout stays clean.
+#[derive(Default)]
+pub struct Alerts {
+    bell: bool,                   // ring on the next ring()
+    flash_until: Option<Instant>, // Output's border is red until then
+}
+
+impl Alerts {
+    pub fn raise(&mut self, alert: Alert) {
+        if matches!(alert, Alert::Bell | Alert::Both) {
+            self.bell = true;
+        }
+        if matches!(alert, Alert::Flash | Alert::Both) {
+            self.flash_until = Some(clock::now() + FLASH);
+        }
+    }
+
+    // Ring the bell if it was raised since the last call
+    pub fn ring(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        if mem::take(&mut self.bell) {
+            out.write_all(b"\x07")?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn flashing(&self) -> bool {
+        self.flash_until.is_some_and(|until| clock::now() < until)
+    }
+
+    // True once, when a flash has just ended and the border needs redrawing
+    pub fn expire(&mut self) -> bool {
+        match self.flash_until {
+            Some(until) if clock::now() >= until => {
+                self.flash_until = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
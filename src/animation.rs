@@ -0,0 +1,90 @@
+// =============================================================================
+// Animation — popups slide open and toasts slide in, over DURATION
+//
+//   let before = frame.buffer_mut().clone();
+//   draw_the_popup(frame);
+//   animation::reveal(frame, &before, animation::progress(opened), Reveal::Down);
+//
+// Nothing here knows how a popup is drawn: reveal() compares the buffer
+// with a copy from before the drawing, and puts back what the popup hasn't
+// "reached" yet. So any overlay or toast animates without extra code.
+//
+// While something is animating the main loop redraws every FRAME instead of
+// every tick. `reduced_motion = true` in the config turns all of it off.
+// =============================================================================
+
+use std::time::{Duration, Instant};
+
+use ratatui::{Frame, buffer::Buffer, layout::Rect};
+
+use crate::clock;
+
+pub const DURATION: Duration = Duration::from_millis(150);
+pub const FRAME: Duration = Duration::from_millis(16); // about 60 fps
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reveal {
+    Down, // top row first, like a dropdown
+    Left, // right edge first, sliding in from the side
+}
+
+// 0.0 when `since` is now, 1.0 from DURATION on
+pub fn progress(since: Instant) -> f64 {
+    (clock::since(since).as_secs_f64() / DURATION.as_secs_f64()).min(1.0)
+}
+
+pub fn running(since: Instant) -> bool {
+    clock::since(since) < DURATION
+}
+
+// Undo the part of what was drawn since `before` that isn't revealed yet
+pub fn reveal(frame: &mut Frame, before: &Buffer, progress: f64, from: Reveal) {
+    if progress >= 1.0 {
+        return;
+    }
+    let buffer = frame.buffer_mut();
+    let Some(drawn) = changed_area(before, buffer) else {
+        return;
+    };
+    let shown = |len: u16| (len as f64 * progress).ceil() as u16;
+    let visible = match from {
+        Reveal::Down => Rect {
+            height: shown(drawn.height),
+            ..drawn
+        },
+        Reveal::Left => {
+            let width = shown(drawn.width);
+            Rect {
+                x: drawn.right() - width,
+                width,
+                ..drawn
+            }
+        }
+    };
+    for y in drawn.top()..drawn.bottom() {
+        for x in drawn.left()..drawn.right() {
+            if !visible.contains((x, y).into())
+                && let (Some(old), Some(new)) = (before.cell((x, y)), buffer.cell_mut((x, y)))
+            {
+                *new = old.clone();
+            }
+        }
+    }
+}
+
+// The smallest rectangle holding every cell that differs
+fn changed_area(before: &Buffer, after: &Buffer) -> Option<Rect> {
+    let area = after.area;
+    let (mut left, mut top, mut right, mut bottom) = (u16::MAX, u16::MAX, 0, 0);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if before.cell((x, y)) != after.cell((x, y)) {
+                left = left.min(x);
+                right = right.max(x + 1);
+                top = top.min(y);
+                bottom = bottom.max(y + 1);
+            }
+        }
+    }
+    (left < right).then(|| Rect::new(left, top, right - left, bottom - top))
+}
@@ -0,0 +1,516 @@
+// =============================================================================
+// Backend — the library that drives the terminal: crossterm, or termion or
+// termwiz for where crossterm misbehaves
+//
+//   let (terminal, keyboard) = backend::init(inline)?;   raw mode, the screen
+//   keyboard.next(timeout)?                               an event, if one comes
+//   backend::restore();                                   the shell's terminal back
+//
+//   cargo build --features termion       # unix only; elsewhere it's crossterm
+//   cargo build --features termwiz       # wins if both are on
+//
+// Whichever it is, the rest of the app speaks crossterm: the keymap,
+// recordings and e2e scripts all take its Event, so termion's and termwiz's
+// are translated on the way in, and mode changes (mouse capture, bracketed
+// paste, the title) go out as the same escape sequences. What each lacks:
+//
+//   termion   resize events, so SIGWINCH makes them; it can parse neither
+//             focus reports (FOCUS is false, see notifications.rs) nor
+//             bracketed paste, so a paste arrives as typing
+//   termwiz   focus reports too, and --inline (cli.rs turns it down)
+//
+// Nor can either read the kitty keyboard protocol's reports, so only
+// crossterm asks the terminal whether it has that (see keymap.rs).
+//
+// A panic restores the terminal first, as ratatui::init arranges for
+// crossterm (see recovery.rs for what runs before that).
+// =============================================================================
+
+#[cfg(not(any(feature = "termwiz", all(feature = "termion", unix))))]
+pub use self::crossterm::{FOCUS, Keyboard, Screen, init, restore};
+#[cfg(all(feature = "termion", not(feature = "termwiz"), unix))]
+pub use self::termion::{FOCUS, Keyboard, Screen, init, restore};
+#[cfg(feature = "termwiz")]
+pub use self::termwiz::{FOCUS, Keyboard, Screen, init, restore};
+
+// The default
+#[cfg(not(any(feature = "termwiz", all(feature = "termion", unix))))]
+mod crossterm {
+    use std::time::Duration;
+
+    use color_eyre::Result;
+    use crossterm::{
+        event::{self, Event},
+        terminal,
+    };
+    use ratatui::{DefaultTerminal, TerminalOptions, Viewport};
+
+    pub type Screen = DefaultTerminal;
+
+    pub const FOCUS: bool = true;
+
+    pub struct Keyboard;
+
+    impl Keyboard {
+        pub fn next(&mut self, timeout: Duration) -> Result<Option<Event>> {
+            if !event::poll(timeout)? {
+                return Ok(None);
+            }
+            Ok(Some(event::read()?))
+        }
+
+        // Whether the terminal has the kitty keyboard protocol. It's asked,
+        // so this comes before any events are read
+        pub fn kitty(&mut self) -> bool {
+            terminal::supports_keyboard_enhancement().unwrap_or(false)
+        }
+    }
+
+    // With the viewport in the bottom `inline` rows, there's no alternate screen
+    pub fn init(inline: Option<u16>) -> Result<(Screen, Keyboard)> {
+        let terminal = match inline {
+            Some(rows) => ratatui::init_with_options(TerminalOptions {
+                viewport: Viewport::Inline(rows),
+            }),
+            None => ratatui::init(),
+        };
+        Ok((terminal, Keyboard))
+    }
+
+    pub fn restore() {
+        ratatui::restore();
+    }
+}
+
+#[cfg(all(feature = "termion", not(feature = "termwiz"), unix))]
+mod termion {
+    use std::{
+        io::{self, ErrorKind, Stdout, Write},
+        sync::{
+            Mutex,
+            atomic::{AtomicBool, Ordering},
+            mpsc::{self, Receiver, RecvTimeoutError},
+        },
+        thread,
+        time::Duration,
+    };
+
+    use color_eyre::{Result, eyre::eyre};
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::{
+        Terminal, TerminalOptions, Viewport,
+        backend::TermionBackend,
+        termion::{
+            self,
+            event::{self as termion_event, Key},
+            input::TermRead,
+            raw::{IntoRawMode, RawTerminal},
+            screen::{ToAlternateScreen, ToMainScreen},
+        },
+    };
+    use signal_hook::{consts::SIGWINCH, iterator::Signals};
+
+    pub type Screen = Terminal<TermionBackend<Stdout>>;
+
+    pub const FOCUS: bool = false;
+
+    // Dropping it is what turns raw mode off, so it's kept here for restore()
+    static RAW: Mutex<Option<RawTerminal<Stdout>>> = Mutex::new(None);
+    static ALTERNATE: AtomicBool = AtomicBool::new(false);
+
+    // Events from two threads: one reading the terminal, one waiting for SIGWINCH
+    pub struct Keyboard {
+        events: Receiver<Event>,
+    }
+
+    impl Keyboard {
+        fn start() -> Result<Self> {
+            let (tx, events) = mpsc::channel();
+            let tty = termion::get_tty()?; // not stdin, which --ingest has
+            let keys = tx.clone();
+            thread::Builder::new()
+                .name("keyboard".into())
+                .spawn(move || {
+                    for event in tty.events() {
+                        let event = match event {
+                            Ok(event) => event,
+                            Err(err) if err.kind() == ErrorKind::Other => continue, // unparsed
+                            Err(_) => break,
+                        };
+                        if let Some(event) = translate(event)
+                            && keys.send(event).is_err()
+                        {
+                            break;
+                        }
+                    }
+                })?;
+            let mut signals = Signals::new([SIGWINCH])?;
+            thread::Builder::new()
+                .name("resize".into())
+                .spawn(move || {
+                    for _ in signals.forever() {
+                        if let Ok((width, height)) = termion::terminal_size()
+                            && tx.send(Event::Resize(width, height)).is_err()
+                        {
+                            break;
+                        }
+                    }
+                })?;
+            Ok(Self { events })
+        }
+
+        pub fn next(&mut self, timeout: Duration) -> Result<Option<Event>> {
+            match self.events.recv_timeout(timeout) {
+                Ok(event) => Ok(Some(event)),
+                Err(RecvTimeoutError::Timeout) => Ok(None),
+                Err(RecvTimeoutError::Disconnected) => {
+                    Err(eyre!("the terminal stopped sending input"))
+                }
+            }
+        }
+
+        pub fn kitty(&mut self) -> bool {
+            false
+        }
+    }
+
+    pub fn init(inline: Option<u16>) -> Result<(Screen, Keyboard)> {
+        let raw = io::stdout().into_raw_mode()?;
+        let mut out = io::stdout();
+        let viewport = match inline {
+            Some(rows) => Viewport::Inline(rows),
+            None => {
+                write!(out, "{ToAlternateScreen}")?;
+                ALTERNATE.store(true, Ordering::Relaxed);
+                Viewport::Fullscreen
+            }
+        };
+        *RAW.lock().unwrap() = Some(raw);
+        super::restore_on_panic();
+        // An inline viewport asks the terminal where the cursor is, so this
+        // comes before anything else reads from it
+        let terminal =
+            Terminal::with_options(TermionBackend::new(out), TerminalOptions { viewport })?;
+        Ok((terminal, Keyboard::start()?))
+    }
+
+    pub fn restore() {
+        if ALTERNATE.swap(false, Ordering::Relaxed) {
+            _ = write!(io::stdout(), "{ToMainScreen}");
+        }
+        _ = write!(io::stdout(), "{}", termion::cursor::Show);
+        _ = io::stdout().flush();
+        if let Ok(mut raw) = RAW.try_lock() {
+            raw.take(); // raw mode off
+        }
+    }
+
+    fn translate(event: termion_event::Event) -> Option<Event> {
+        match event {
+            termion_event::Event::Key(key) => {
+                let (code, modifiers) = key_code(key)?;
+                Some(Event::Key(KeyEvent::new(code, modifiers)))
+            }
+            termion_event::Event::Mouse(mouse) => {
+                use termion_event::{MouseButton as Button, MouseEvent as Mouse};
+                let (kind, column, row) = match mouse {
+                    Mouse::Press(Button::Left, x, y) => {
+                        (MouseEventKind::Down(MouseButton::Left), x, y)
+                    }
+                    Mouse::Press(Button::Right, x, y) => {
+                        (MouseEventKind::Down(MouseButton::Right), x, y)
+                    }
+                    Mouse::Press(Button::Middle, x, y) => {
+                        (MouseEventKind::Down(MouseButton::Middle), x, y)
+                    }
+                    Mouse::Press(Button::WheelUp, x, y) => (MouseEventKind::ScrollUp, x, y),
+                    Mouse::Press(Button::WheelDown, x, y) => (MouseEventKind::ScrollDown, x, y),
+                    Mouse::Press(Button::WheelLeft, x, y) => (MouseEventKind::ScrollLeft, x, y),
+                    Mouse::Press(Button::WheelRight, x, y) => (MouseEventKind::ScrollRight, x, y),
+                    // termion doesn't say which button these are
+                    Mouse::Hold(x, y) => (MouseEventKind::Drag(MouseButton::Left), x, y),
+                    Mouse::Release(x, y) => (MouseEventKind::Up(MouseButton::Left), x, y),
+                };
+                Some(Event::Mouse(MouseEvent {
+                    kind,
+                    column: column.saturating_sub(1), // termion's count from 1
+                    row: row.saturating_sub(1),
+                    modifiers: KeyModifiers::NONE,
+                }))
+            }
+            termion_event::Event::Unsupported(_) => None,
+        }
+    }
+
+    // As crossterm would report the same key
+    fn key_code(key: Key) -> Option<(KeyCode, KeyModifiers)> {
+        let (shift, alt, ctrl) = (
+            KeyModifiers::SHIFT,
+            KeyModifiers::ALT,
+            KeyModifiers::CONTROL,
+        );
+        let none = KeyModifiers::NONE;
+        Some(match key {
+            Key::Char('\n') => (KeyCode::Enter, none),
+            Key::Char('\t') => (KeyCode::Tab, none),
+            Key::Char(c) if c.is_uppercase() => (KeyCode::Char(c), shift),
+            Key::Char(c) => (KeyCode::Char(c), none),
+            Key::Alt(c) => (KeyCode::Char(c), alt),
+            Key::Ctrl(c) => (KeyCode::Char(c), ctrl),
+            Key::Null => (KeyCode::Char(' '), ctrl),
+            Key::Esc => (KeyCode::Esc, none),
+            Key::Backspace => (KeyCode::Backspace, none),
+            Key::Delete => (KeyCode::Delete, none),
+            Key::Insert => (KeyCode::Insert, none),
+            Key::BackTab => (KeyCode::BackTab, shift),
+            Key::Left => (KeyCode::Left, none),
+            Key::ShiftLeft => (KeyCode::Left, shift),
+            Key::AltLeft => (KeyCode::Left, alt),
+            Key::CtrlLeft => (KeyCode::Left, ctrl),
+            Key::Right => (KeyCode::Right, none),
+            Key::ShiftRight => (KeyCode::Right, shift),
+            Key::AltRight => (KeyCode::Right, alt),
+            Key::CtrlRight => (KeyCode::Right, ctrl),
+            Key::Up => (KeyCode::Up, none),
+            Key::ShiftUp => (KeyCode::Up, shift),
+            Key::AltUp => (KeyCode::Up, alt),
+            Key::CtrlUp => (KeyCode::Up, ctrl),
+            Key::Down => (KeyCode::Down, none),
+            Key::ShiftDown => (KeyCode::Down, shift),
+            Key::AltDown => (KeyCode::Down, alt),
+            Key::CtrlDown => (KeyCode::Down, ctrl),
+            Key::Home => (KeyCode::Home, none),
+            Key::CtrlHome => (KeyCode::Home, ctrl),
+            Key::End => (KeyCode::End, none),
+            Key::CtrlEnd => (KeyCode::End, ctrl),
+            Key::PageUp => (KeyCode::PageUp, none),
+            Key::PageDown => (KeyCode::PageDown, none),
+            Key::F(n) => (KeyCode::F(n), none),
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(feature = "termwiz")]
+mod termwiz {
+    use std::{cell::RefCell, io, mem, sync::Mutex, time::Duration};
+
+    use color_eyre::{Result, eyre::eyre};
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::{
+        Terminal,
+        backend::{Backend, ClearType, TermwizBackend, WindowSize},
+        buffer::Cell,
+        layout::{Position, Size},
+        termwiz::{
+            self,
+            caps::{Capabilities, ProbeHints},
+            input::{InputEvent, KeyCode as Key, Modifiers, MouseButtons},
+            terminal::{SystemTerminal, Terminal as _, buffered::BufferedTerminal},
+        },
+    };
+
+    pub type Screen = Terminal<Resizable>;
+
+    pub const FOCUS: bool = false;
+
+    // The terminal input is read from. Its settings are the shell's, so
+    // dropping it (on restore()) puts them back and leaves the alternate screen
+    static INPUT: Mutex<Option<SystemTerminal>> = Mutex::new(None);
+
+    #[derive(Default)]
+    pub struct Keyboard {
+        pressed: bool, // the left button, so a move with it down is a drag
+    }
+
+    impl Keyboard {
+        pub fn next(&mut self, timeout: Duration) -> Result<Option<Event>> {
+            let mut input = INPUT.lock().unwrap();
+            let Some(terminal) = input.as_mut() else {
+                return Ok(None);
+            };
+            let event = terminal
+                .poll_input(Some(timeout))
+                .map_err(|err| eyre!("reading the terminal: {err:#}"))?;
+            Ok(event.and_then(|event| self.translate(event)))
+        }
+
+        pub fn kitty(&mut self) -> bool {
+            false
+        }
+
+        fn translate(&mut self, event: InputEvent) -> Option<Event> {
+            match event {
+                InputEvent::Key(key) => Some(Event::Key(KeyEvent::new(
+                    key_code(key.key)?,
+                    modifiers(key.modifiers),
+                ))),
+                InputEvent::Mouse(mouse) => {
+                    let buttons = mouse.mouse_buttons;
+                    let kind = if buttons.contains(MouseButtons::VERT_WHEEL) {
+                        match buttons.contains(MouseButtons::WHEEL_POSITIVE) {
+                            true => MouseEventKind::ScrollUp,
+                            false => MouseEventKind::ScrollDown,
+                        }
+                    } else {
+                        let left = buttons.contains(MouseButtons::LEFT);
+                        match (mem::replace(&mut self.pressed, left), left) {
+                            (false, true) => MouseEventKind::Down(MouseButton::Left),
+                            (true, true) => MouseEventKind::Drag(MouseButton::Left),
+                            (true, false) => MouseEventKind::Up(MouseButton::Left),
+                            (false, false) => MouseEventKind::Moved,
+                        }
+                    };
+                    Some(Event::Mouse(MouseEvent {
+                        kind,
+                        column: mouse.x.saturating_sub(1), // termwiz's count from 1
+                        row: mouse.y.saturating_sub(1),
+                        modifiers: modifiers(mouse.modifiers),
+                    }))
+                }
+                InputEvent::Resized { cols, rows } => Some(Event::Resize(
+                    cols.try_into().unwrap_or(u16::MAX),
+                    rows.try_into().unwrap_or(u16::MAX),
+                )),
+                InputEvent::Paste(text) => Some(Event::Paste(text)),
+                InputEvent::PixelMouse(_) | InputEvent::Wake => None,
+            }
+        }
+    }
+
+    pub fn init(_inline: Option<u16>) -> Result<(Screen, Keyboard)> {
+        // Both before raw mode, so both remember the shell's settings
+        let mut input = SystemTerminal::new(capabilities()?)?;
+        let output = SystemTerminal::new(capabilities()?)?;
+        input.set_raw_mode()?;
+        input.enter_alternate_screen()?;
+        input.flush()?;
+        let backend = TermwizBackend::with_buffered_terminal(BufferedTerminal::new(output)?);
+        *INPUT.lock().unwrap() = Some(input);
+        super::restore_on_panic();
+        Ok((
+            Terminal::new(Resizable(RefCell::new(backend)))?,
+            Keyboard::default(),
+        ))
+    }
+
+    pub fn restore() {
+        if let Ok(mut input) = INPUT.try_lock() {
+            input.take();
+        }
+    }
+
+    // Mouse capture is the app's to turn on and off (see minimap.rs)
+    fn capabilities() -> termwiz::Result<Capabilities> {
+        Capabilities::new_with_hints(ProbeHints::new_from_env().mouse_reporting(Some(false)))
+    }
+
+    fn key_code(key: Key) -> Option<KeyCode> {
+        Some(match key {
+            Key::Char(c) => KeyCode::Char(c),
+            Key::Enter => KeyCode::Enter,
+            Key::Tab => KeyCode::Tab,
+            Key::Backspace => KeyCode::Backspace,
+            Key::Escape => KeyCode::Esc,
+            Key::Delete => KeyCode::Delete,
+            Key::Insert => KeyCode::Insert,
+            Key::Home | Key::KeyPadHome => KeyCode::Home,
+            Key::End | Key::KeyPadEnd => KeyCode::End,
+            Key::PageUp | Key::KeyPadPageUp => KeyCode::PageUp,
+            Key::PageDown | Key::KeyPadPageDown => KeyCode::PageDown,
+            Key::LeftArrow | Key::ApplicationLeftArrow => KeyCode::Left,
+            Key::RightArrow | Key::ApplicationRightArrow => KeyCode::Right,
+            Key::UpArrow | Key::ApplicationUpArrow => KeyCode::Up,
+            Key::DownArrow | Key::ApplicationDownArrow => KeyCode::Down,
+            Key::Function(n) => KeyCode::F(n),
+            _ => return None,
+        })
+    }
+
+    fn modifiers(modifiers: Modifiers) -> KeyModifiers {
+        let mut out = KeyModifiers::NONE;
+        for (theirs, ours) in [
+            (Modifiers::SHIFT, KeyModifiers::SHIFT),
+            (Modifiers::ALT, KeyModifiers::ALT),
+            (Modifiers::CTRL, KeyModifiers::CONTROL),
+        ] {
+            if modifiers.contains(theirs) {
+                out |= ours;
+            }
+        }
+        out
+    }
+
+    // TermwizBackend's size is its surface's, which changes only when it's
+    // told to, and Terminal::autoresize asks through &self: so this tells it
+    pub struct Resizable(RefCell<TermwizBackend>);
+
+    impl Backend for Resizable {
+        type Error = io::Error;
+
+        fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a Cell)>,
+        {
+            self.0.get_mut().draw(content)
+        }
+
+        fn append_lines(&mut self, n: u16) -> io::Result<()> {
+            self.0.get_mut().append_lines(n)
+        }
+
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.0.get_mut().hide_cursor()
+        }
+
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.0.get_mut().show_cursor()
+        }
+
+        fn get_cursor_position(&mut self) -> io::Result<Position> {
+            self.0.get_mut().get_cursor_position()
+        }
+
+        fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+            self.0.get_mut().set_cursor_position(position)
+        }
+
+        fn clear(&mut self) -> io::Result<()> {
+            self.0.get_mut().clear()
+        }
+
+        fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+            self.0.get_mut().clear_region(clear_type)
+        }
+
+        fn size(&self) -> io::Result<Size> {
+            let mut backend = self.0.borrow_mut();
+            backend
+                .buffered_terminal_mut()
+                .check_for_resize()
+                .map_err(io::Error::other)?;
+            backend.size()
+        }
+
+        fn window_size(&mut self) -> io::Result<WindowSize> {
+            self.0.get_mut().window_size()
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.get_mut().flush()
+        }
+    }
+}
+
+#[cfg(any(feature = "termwiz", all(feature = "termion", unix)))]
+fn restore_on_panic() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore();
+        previous(info);
+    }));
+}
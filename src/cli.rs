@@ -0,0 +1,146 @@
+// =============================================================================
+// Command-line flags
+//
+// Parsed by hand to keep the template dependency-light. Add a field to Args
+// and a match arm below for each new flag. Flags that take a value accept
+// both `--flag value` and `--flag=value`.
+// =============================================================================
+
+use std::{
+    env,
+    io::{self, IsTerminal},
+    path::PathBuf,
+    process,
+};
+
+use crate::paths;
+
+const USAGE: &str = "\
+Usage: tui-template [OPTIONS]
+
+Options:
+      --no-tui             Run commands from stdin/stdout instead of the full-screen UI
+      --ingest             Show piped stdin in Output (e.g. `journalctl -f | tui-template
+                           --ingest`) instead of running it as commands
+      --profile <NAME>     Use a separate config, history and sessions for NAME
+      --log-level <LEVEL>  Log filter: error, warn, info, debug, trace, or a
+                           tracing directive like `tui_template=debug` [default: info]
+      --record <FILE>      Write every key, mouse and resize event to FILE (JSON lines)
+      --replay <FILE>      Feed the events a --record wrote back in, as they happened
+      --inline <ROWS>      Draw in the bottom ROWS rows of the terminal (at least 10), below
+                           what's already there, instead of taking over the whole screen
+      --no-color           No colors, just bold and reverse video (as does setting NO_COLOR)
+      --screen-reader      Plain text for screen readers: no borders or decoration, and the
+                           prompt right after the latest output (see screen_reader = true)
+      --smoke-test         Start up, draw a frame off-screen and run `help`, then exit
+                           with 0 if that all worked (no terminal needed)
+  -h, --help               Print this help
+
+When stdin is not a terminal (and not --ingest), commands are read from it line
+by line and the exit status is that of the last command.";
+
+#[derive(Debug, Default)]
+pub struct Args {
+    pub no_tui: bool,
+    pub ingest: bool,
+    pub log_level: Option<String>,
+    pub profile: Option<String>,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub smoke_test: bool,
+    pub no_color: bool,
+    pub screen_reader: bool,
+    pub inline: Option<u16>, // rows, instead of the alternate screen
+}
+
+impl Args {
+    // Exits the process for --help, and with status 2 on anything unrecognized
+    pub fn parse() -> Self {
+        let mut args = Self::default();
+        let mut argv = env::args().skip(1);
+        while let Some(arg) = argv.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            // The flag's value, from `--flag=value` or the next argument
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| argv.next())
+                    .unwrap_or_else(|| usage_error(&format!("{flag} needs a value")))
+            };
+
+            match flag {
+                "--no-tui" => args.no_tui = true,
+                "--ingest" => args.ingest = true,
+                "--profile" => {
+                    let name = value();
+                    if !paths::is_valid_name(&name) {
+                        usage_error("profile names may only contain letters, digits, '-' and '_'");
+                    }
+                    args.profile = Some(name);
+                }
+                "--log-level" => {
+                    let level = value();
+                    if let Err(err) = tracing_subscriber::EnvFilter::try_new(&level) {
+                        usage_error(&format!("invalid --log-level '{level}': {err}"));
+                    }
+                    args.log_level = Some(level);
+                }
+                "--record" => args.record = Some(value().into()),
+                "--replay" => args.replay = Some(value().into()),
+                "--smoke-test" => args.smoke_test = true,
+                "--no-color" => args.no_color = true,
+                "--screen-reader" => args.screen_reader = true,
+                "--inline" => {
+                    let rows = value();
+                    match rows.parse() {
+                        Ok(rows) if rows >= crate::MIN_HEIGHT => args.inline = Some(rows),
+                        _ => usage_error(&format!(
+                            "--inline wants a number of rows, at least {}, not '{rows}'",
+                            crate::MIN_HEIGHT
+                        )),
+                    }
+                }
+                "-h" | "--help" => {
+                    println!("{USAGE}");
+                    process::exit(0);
+                }
+                other => usage_error(&format!("unknown argument '{other}'")),
+            }
+        }
+        if args.smoke_test
+            && (args.no_tui || args.ingest || args.record.is_some() || args.replay.is_some())
+        {
+            usage_error(
+                "--smoke-test runs on its own, without --no-tui, --ingest or --record/--replay",
+            );
+        }
+        if args.ingest && (args.no_tui || io::stdin().is_terminal()) {
+            usage_error("--ingest needs the full-screen UI and something piped in");
+        }
+        let tui = !args.no_tui && (args.ingest || io::stdin().is_terminal());
+        if (args.record.is_some() || args.replay.is_some()) && !tui {
+            usage_error("--record and --replay need the full-screen UI");
+        }
+        if args.inline.is_some() && (args.smoke_test || !tui) {
+            usage_error("--inline needs the UI, so not --smoke-test, --no-tui or piped commands");
+        }
+        #[cfg(feature = "termwiz")]
+        if args.inline.is_some() {
+            usage_error("--inline isn't supported with the termwiz backend");
+        }
+        // …and a terminal to read keys from, as stdin is taken
+        #[cfg(unix)]
+        if args.ingest && std::fs::File::open("/dev/tty").is_err() {
+            usage_error("--ingest needs a terminal to read the keyboard from");
+        }
+        args
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {message}\n\n{USAGE}");
+    process::exit(2);
+}
@@ -0,0 +1,145 @@
+// =============================================================================
+// Clipboard — copies text to the *terminal's* clipboard via OSC 52, and
+// remembers what was copied
+//
+// OSC 52 is an escape sequence most modern terminals understand. Because it
+// travels through the terminal itself it also works over SSH, where there is
+// no local clipboard daemon to talk to — and through tmux or screen, wrapped
+// for them to pass on (see multiplexer.rs).
+//
+//   > clips                    pick an earlier copy to copy again
+//   > clips insert             …or to type into the input bar (ctrl-y)
+//   > clips clear
+//
+// The newest HISTORY copies are kept, newest first, for this session. OSC 52
+// can't be read back, so copies made in other apps are only seen with
+// `watch_clipboard = true` in the config and the `system-clipboard` feature,
+// which polls the desktop's clipboard (X11 — Wayland through XWayland) every
+// POLL from launch.
+// =============================================================================
+
+use std::{
+    collections::VecDeque,
+    fmt, io,
+    sync::{Arc, Mutex},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::{
+    ctx::{AppEvent, Ctx, Status},
+    multiplexer,
+    widgets::Select,
+};
+
+const HISTORY: usize = 50;
+const PREVIEW: usize = 60; // characters of an entry shown in the picker
+#[cfg(feature = "system-clipboard")]
+const POLL: std::time::Duration = std::time::Duration::from_secs(1);
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let insert = match args.trim() {
+        "" => false,
+        "insert" => true,
+        "clear" => {
+            ctx.clipboard.clear();
+            ctx.print("  Clipboard history cleared");
+            return Status::Success;
+        }
+        _ => return ctx.fail("  Usage: clips [insert | clear]"),
+    };
+    let clips: Vec<Clip> = ctx.clipboard.history().into_iter().map(Clip).collect();
+    if clips.is_empty() {
+        return ctx.fail("  Nothing copied yet");
+    }
+    ctx.open(Select::new("Clipboard history", clips), move |ctx, clip| {
+        if insert {
+            ctx.send(AppEvent::Insert(clip.0));
+            return;
+        }
+        match ctx.clipboard.copy(&clip.0) {
+            Ok(()) => ctx.print(format!("  Copied {} characters", clip.0.chars().count())),
+            Err(err) => ctx.print(format!("  Copy failed: {err}")),
+        }
+    });
+    Status::Success
+}
+
+// An entry as the picker lists it: on one line, cut short
+struct Clip(String);
+
+impl fmt::Display for Clip {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line: String = self.0.split_whitespace().collect::<Vec<_>>().join(" ");
+        match line.char_indices().nth(PREVIEW) {
+            Some((end, _)) => write!(f, "{}…", &line[..end]),
+            None => f.write_str(&line),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Clipboard {
+    history: Arc<Mutex<VecDeque<String>>>, // newest first; shared with watch()'s thread
+}
+
+impl Clipboard {
+    pub fn copy(&mut self, text: &str) -> io::Result<()> {
+        // ESC ] 52 ; c ; <base64> BEL   ("c" = the system clipboard selection)
+        let escape = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+        multiplexer::to_terminal(&mut io::stdout(), &escape)?;
+        remember(&self.history, text);
+        Ok(())
+    }
+
+    // The most recent copy, for commands that want to paste it back
+    pub fn last(&self) -> Option<String> {
+        self.history.lock().ok()?.front().cloned()
+    }
+
+    pub fn history(&self) -> Vec<String> {
+        self.history
+            .lock()
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        if let Ok(mut history) = self.history.lock() {
+            history.clear();
+        }
+    }
+
+    // Add what other apps copy too, until the app (holding the other Arc)
+    // is gone
+    #[cfg(feature = "system-clipboard")]
+    pub fn watch(&self) {
+        let history = self.history.clone();
+        std::thread::spawn(move || {
+            let Ok(mut system) = arboard::Clipboard::new() else {
+                return; // no desktop session, e.g. over SSH
+            };
+            let mut seen = None;
+            while Arc::strong_count(&history) > 1 {
+                if let Ok(text) = system.get_text()
+                    && !text.is_empty()
+                    && seen.as_ref() != Some(&text)
+                {
+                    remember(&history, &text);
+                    seen = Some(text);
+                }
+                std::thread::sleep(POLL);
+            }
+        });
+    }
+}
+
+// Put `text` first, moving it up if it was copied before
+fn remember(history: &Mutex<VecDeque<String>>, text: &str) {
+    let Ok(mut history) = history.lock() else {
+        return;
+    };
+    history.retain(|old| old != text);
+    history.push_front(text.to_string());
+    history.truncate(HISTORY);
+}
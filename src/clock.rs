@@ -0,0 +1,74 @@
+// =============================================================================
+// Clock — what time the UI thinks it is, so tests can choose
+//
+//   clock::now()               instead of Instant::now()
+//   clock::since(started)      instead of started.elapsed()
+//
+// Everything the main loop times — ticks, resize debouncing, toasts and
+// flashes expiring, popups sliding open, autosave, how long a job ran —
+// asks here. Normally that's the system's clock; a test can put a
+// ManualClock in its place and move it forward by hand, then call on_tick(),
+// rather than sleeping (see testing.rs).
+//
+// The clock is per thread: tests run side by side, each on its own, and
+// background jobs keep real time since they really do wait.
+// =============================================================================
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+thread_local! {
+    static CLOCK: RefCell<Rc<dyn Clock>> = RefCell::new(Rc::new(SystemClock));
+}
+
+pub fn now() -> Instant {
+    CLOCK.with(|clock| clock.borrow().now())
+}
+
+pub fn since(earlier: Instant) -> Duration {
+    now().saturating_duration_since(earlier)
+}
+
+// Use `clock` on this thread from now on
+#[cfg(test)]
+pub fn set(clock: Rc<dyn Clock>) {
+    CLOCK.with(|current| *current.borrow_mut() = clock);
+}
+
+// Stands still until advanced
+#[cfg(test)]
+#[derive(Clone)]
+pub struct ManualClock(Rc<std::cell::Cell<Instant>>);
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new() -> Self {
+        Self(Rc::new(std::cell::Cell::new(Instant::now())))
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.0.set(self.0.get() + by);
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.0.get()
+    }
+}
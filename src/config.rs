@@ -0,0 +1,239 @@
+// =============================================================================
+// Configuration — everything a cloned app might want to tweak without
+// touching the rendering or input code
+//
+// The config file is TOML, loaded from $XDG_CONFIG_HOME/<app>/config.toml
+// (usually ~/.config/<app>/config.toml). Every key is optional; anything left
+// out falls back to the defaults, so an empty — or missing — file is fine.
+// Mistakes are reported with their line number and only the broken setting
+// is ignored (see validate.rs):
+//
+//   history_size = 1000          # commands remembered for Up/Down
+//   tick_rate_ms = 250           # how often background work is checked
+//   slow_when_unfocused = true   # …and less often while the window isn't focused
+//   startup = ["hello"]          # commands run at launch
+//   reduced_motion = false       # true: popups and toasts appear without animating
+//   open_command = "edit +{line} {path}"   # what Enter on a grep match runs
+//   watch_clipboard = false      # true: other apps' copies join `clips` (see clipboard.rs)
+//   kitty_keyboard = true        # ctrl-enter, shift-enter, releases… where
+//                                # the terminal can (see keymap.rs)
+//   screen_reader = false        # true: a plain transcript, no borders or
+//                                # glyphs (see screen_reader.rs)
+//
+//   [session]                    # see session.rs
+//   autoload = true              # restore the last saved session at launch
+//   autosave_minutes = 5         # snapshot to the "autosave" session (0 = off)
+//
+//   [theme]                      # see theme.rs (or: theme = "<file name>")
+//   input_border = "#ff8800"
+//
+//   [keymap]                     # see keymap.rs
+//   "ctrl-l" = "clear"
+//
+//   [layout]                     # see layout.rs
+//   panels = [
+//       { kind = "output", size = "fill" },
+//       { kind = "status", size = { length = 1 } },
+//       { kind = "input",  size = { length = 3 } },
+//   ]
+//   side_panel = { position = "right", width = { percent = 30 } }
+//   breakpoints = { stack_side_panel = 80, hide_bars = 60 }   # for narrow terminals
+//
+//   [[menu]]                     # see menu.rs
+//   title = "File"
+//   items = [{ label = "Quit", command = "quit" }]
+//
+//   [notifications]              # see notifications.rs
+//   method = "osc9"              # when a job ends while you're elsewhere…
+//   after_seconds = 10           # …after running at least this long
+//
+//   [alerts]                     # see alerts.rs
+//   on_error = "flash"           # the bell or a red border, on errors and long jobs
+//
+//   [cursor]                     # see cursor.rs
+//   input = "bar"                # its shape, in the input bar, a pane or a popup
+// =============================================================================
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, bail},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alerts::AlertConfig,
+    cursor::CursorConfig,
+    keymap::Keymap,
+    layout::LayoutConfig,
+    menu::{self, Menu},
+    notifications::NotificationConfig,
+    paths,
+    theme::{self, Theme},
+    validate::{self, Problem},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub history_size: usize,
+    pub tick_rate_ms: u64,
+    pub slow_when_unfocused: bool,
+    pub startup: Vec<String>,
+    pub reduced_motion: bool,
+    pub open_command: String,
+    pub watch_clipboard: bool,
+    pub kitty_keyboard: bool,
+    pub screen_reader: bool,
+    pub session: SessionConfig,
+    #[serde(deserialize_with = "theme::deserialize")]
+    pub theme: Theme,
+    pub keymap: Keymap,
+    pub layout: LayoutConfig,
+    pub menu: Vec<Menu>,
+    pub notifications: NotificationConfig,
+    pub alerts: AlertConfig,
+    pub cursor: CursorConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            history_size: 1000,
+            tick_rate_ms: 250,
+            slow_when_unfocused: true,
+            startup: Vec::new(),
+            reduced_motion: false,
+            open_command: "edit +{line} {path}".into(),
+            watch_clipboard: false,
+            kitty_keyboard: true,
+            screen_reader: false,
+            session: SessionConfig::default(),
+            theme: Theme::default(),
+            keymap: Keymap::default(),
+            layout: LayoutConfig::default(),
+            menu: menu::defaults(),
+            notifications: NotificationConfig::default(),
+            alerts: AlertConfig::default(),
+            cursor: CursorConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionConfig {
+    pub autoload: bool,
+    pub autosave_minutes: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            autoload: false,
+            autosave_minutes: 5,
+        }
+    }
+}
+
+impl SessionConfig {
+    pub fn autosave_interval(&self) -> Option<Duration> {
+        (self.autosave_minutes > 0).then(|| Duration::from_secs(self.autosave_minutes * 60))
+    }
+}
+
+impl Config {
+    // Settings that don't check out are reported and left at their
+    // defaults (see validate.rs); only an unreadable or unparseable file
+    // is an error
+    pub fn load(path: &Path) -> Result<(Self, Vec<Problem>)> {
+        let text =
+            fs::read_to_string(path).wrap_err_with(|| format!("reading {}", path.display()))?;
+        validate::check(&text).wrap_err_with(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms)
+    }
+}
+
+// Merge `entries` into the [keymap] table of the config file, creating the
+// file if needed. Other settings are kept, but comments are not.
+pub fn save_keymap(path: &Path, entries: &[(String, String)]) -> Result<()> {
+    let mut doc: toml::Table = match fs::read_to_string(path) {
+        Ok(text) => {
+            toml::from_str(&text).wrap_err_with(|| format!("parsing {}", path.display()))?
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+        Err(err) => return Err(err).wrap_err_with(|| format!("reading {}", path.display())),
+    };
+
+    let keymap = doc
+        .entry("keymap")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    let Some(keymap) = keymap.as_table_mut() else {
+        bail!("{}: `keymap` is not a table", path.display());
+    };
+    for (key, action) in entries {
+        keymap.insert(key.clone(), toml::Value::String(action.clone()));
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+    }
+    fs::write(path, toml::to_string_pretty(&doc)?)
+        .wrap_err_with(|| format!("writing {}", path.display()))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    Some(paths::config_dir()?.join("config.toml"))
+}
+
+// =============================================================================
+// Hot reload — polls the config file's modification time
+//
+// Polling on the tick is plenty for a file humans edit by hand, and it avoids
+// pulling in a platform file-watching dependency just for this.
+// =============================================================================
+pub struct ConfigWatcher {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        let last_modified = modified(&path);
+        Self {
+            path,
+            last_modified,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Returns Some(result) when the file changed since the last call.
+    // A deleted file reloads the defaults.
+    pub fn poll(&mut self) -> Option<Result<(Config, Vec<Problem>)>> {
+        let modified = modified(&self.path);
+        if modified == self.last_modified {
+            return None;
+        }
+        self.last_modified = modified;
+
+        Some(match modified {
+            Some(_) => Config::load(&self.path),
+            None => Ok((Config::default(), Vec::new())),
+        })
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
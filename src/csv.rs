@@ -0,0 +1,157 @@
+// =============================================================================
+// CSV — `csv <path>`: a CSV (or TSV) file in a table pane
+//
+//   > csv data/orders.csv
+//
+// The first row is the header. Commas, semicolons or tabs — whichever the
+// header has most of — separate the fields; quoting follows RFC 4180. It's
+// the table pane (widgets/table.rs) with each column's inferred kind under
+// its name, so it sorts with 1–9 and filters with / like any other table:
+//
+//   / total>100     / status=shipped     / acme
+//
+// Big files aren't read all at once: the first PAGE rows are shown, and
+// more are read as the selection gets near the bottom. Sorting, filtering
+// and End need every row, so they read the rest first.
+// =============================================================================
+
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::Path,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use csv::{Reader, ReaderBuilder, StringRecord};
+use ratatui::{Frame, layout::Rect};
+
+use crate::{
+    ctx::{Ctx, Status},
+    effects::Files,
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+    widgets::TableView,
+};
+
+const PAGE: usize = 1000; // rows read at a time
+const PREFETCH: usize = 100; // read the next page when this few are below the selection
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    if args.is_empty() {
+        return ctx.fail("  Usage: csv <path>");
+    }
+    let path = args;
+    let files = &*ctx.effects.files;
+    let opened = sniff_delimiter(files, Path::new(path))
+        .and_then(|delimiter| Ok((delimiter, files.open(Path::new(path))?)));
+    let (delimiter, file) = match opened {
+        Ok(opened) => opened,
+        Err(err) => return ctx.fail(format!("  Can't read {path}: {err}")),
+    };
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true) // short or long rows are shown, not refused
+        .from_reader(file);
+    let headers: Vec<String> = match reader.headers() {
+        Ok(headers) => headers
+            .iter()
+            .enumerate()
+            .map(|(i, name)| match name.trim() {
+                "" => format!("column {}", i + 1),
+                name => name.to_string(),
+            })
+            .collect(),
+        Err(err) => return ctx.fail(format!("  {path} isn't valid CSV: {err}")),
+    };
+    if headers.is_empty() {
+        return ctx.fail(format!("  {path} is empty"));
+    }
+
+    let name = Path::new(path)
+        .file_name()
+        .map_or(path.into(), |name| name.to_string_lossy());
+    let mut view = CsvView {
+        table: TableView::new(format!("CSV: {name}"), headers).show_kinds(),
+        reader: Some(reader),
+        error: None,
+    };
+    view.read(PAGE);
+    ctx.show(view);
+    Status::Success
+}
+
+// Whichever of , ; or tab the first line has most of
+fn sniff_delimiter(files: &dyn Files, path: &Path) -> std::io::Result<u8> {
+    let mut first = String::new();
+    BufReader::new(files.open(path)?).read_line(&mut first)?;
+    let count = |d: u8| first.bytes().filter(|&b| b == d).count();
+    Ok([b',', b';', b'\t']
+        .into_iter()
+        .max_by_key(|&d| count(d))
+        .filter(|&d| count(d) > 0)
+        .unwrap_or(b','))
+}
+
+pub struct CsvView {
+    table: TableView,
+    reader: Option<Reader<Box<dyn Read + Send>>>, // None once the file has been read to the end
+    error: Option<String>,                        // why reading stopped early
+}
+
+impl CsvView {
+    // Up to `count` more rows into the table
+    fn read(&mut self, count: usize) {
+        let Some(reader) = &mut self.reader else {
+            return;
+        };
+        let mut rows = Vec::new();
+        let mut record = StringRecord::new();
+        while rows.len() < count {
+            match reader.read_record(&mut record) {
+                Ok(true) => rows.push(record.iter().map(String::from).collect()),
+                Ok(false) => {
+                    self.reader = None;
+                    break;
+                }
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    self.reader = None;
+                    break;
+                }
+            }
+        }
+        self.table.push_rows(rows);
+    }
+}
+
+impl Pane for CsvView {
+    fn title(&self) -> String {
+        let title = self.table.title();
+        match (&self.reader, &self.error) {
+            (Some(_), _) => format!("{title} — more as you scroll"),
+            (None, Some(error)) => format!("{title} — stopped early: {error}"),
+            (None, None) => title,
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let needs_everything = self.table.typing()
+            || matches!(key.code, KeyCode::End | KeyCode::Char('1'..='9' | '/'));
+        if needs_everything {
+            self.read(usize::MAX);
+        }
+        let handled = self.table.handle_key(key);
+        if self.table.rows_below() < PREFETCH {
+            self.read(PAGE);
+        }
+        handled
+    }
+
+    fn hints(&self) -> Hints {
+        self.table.hints()
+    }
+}
@@ -0,0 +1,405 @@
+// =============================================================================
+// Command context — what a command is allowed to touch
+//
+// Commands don't get &mut App. They get a Ctx with handles to the services
+// they need, and they report back by sending AppEvents. The main loop owns
+// the receiving end and applies events to App state (push a message, quit…).
+//
+// This keeps command logic decoupled from UI state: the same command runs in
+// the TUI, in a plain stdin/stdout REPL, or in a unit test that just creates
+// a channel and inspects what came out of it. What a command does outside
+// the app — run programs, touch files, make requests — goes through
+// ctx.effects, which a test can swap for a fake (see effects.rs).
+// =============================================================================
+
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc::Sender,
+    },
+    thread,
+    time::Instant,
+};
+
+use regex::Regex;
+
+use crate::{
+    clipboard::Clipboard,
+    clock,
+    config::Config,
+    effects::Effects,
+    keymap::{Action, KeyBinding},
+    overlay::{Modal, Overlay},
+    pane::Pane,
+    theme::Theme,
+    toast::Level,
+};
+
+// Everything a command (or a background job) can ask the app to do
+#[derive(Debug)]
+pub enum AppEvent {
+    Print(String),               // append a line to the Output panel
+    Toast(Level, String),        // a passing notice that stays out of the scrollback
+    Clear,                       // wipe the Output panel
+    Quit,                        // stop the main loop
+    FilterOutput(Option<Regex>), // show only matching lines in Output; None shows all
+
+    // Session state lives in App, so commands ask for it to be saved/restored
+    SaveSession(String),
+    LoadSession(Option<String>), // None = the most recently saved
+    ExportState(PathBuf),
+    ImportState(PathBuf),
+
+    SwitchProfile(Option<String>), // None = the default profile
+    Recover {
+        discard: bool,
+    }, // restore (or drop) what recovery.rs stashed
+    SetTheme(Box<Theme>),          // switch colors until the config is next reloaded
+    SetBreadcrumbs(Vec<String>),   // the title bar's path; empty clears it
+    SetMouse(Option<bool>),        // capture the mouse for the minimap or not; None toggles
+
+    // Session keymap changes; None unbinds. They survive config reloads.
+    Bind(KeyBinding, Option<Action>),
+    SaveBindings, // write the session's binds/unbinds to the config file
+
+    Run(String),             // run a command as if typed, e.g. from a pane's Enter
+    Insert(String),          // type text into the input bar (see clipboard.rs)
+    Open(Modal),             // push a popup onto the overlay stack (see overlay.rs)
+    ShowPane(Box<dyn Pane>), // replace the pane (see pane.rs)
+    ClosePane,
+
+    // A job's ProgressHandle (see Spawner) reporting in, or going away
+    Progress {
+        id: u64,
+        label: String,
+        fraction: f64,
+    },
+    ProgressDone(u64),
+    // Any spawned job returning, named after the command that started it
+    JobDone {
+        label: String,
+        started: Instant,
+    },
+
+    // A sample for the side panel's metrics (see metrics.rs)
+    Metric {
+        name: String,
+        value: f64,
+        max: Option<f64>, // Some = draw as a gauge
+    },
+    ClearMetrics,
+}
+
+// How a command finished. Batch mode turns the last one into the exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+}
+
+// What the optional integrations keep between commands — an open
+// database, a broker connection, which cluster, what's playing — owned by
+// App. Empty when they're built without.
+#[derive(Default)]
+pub struct Connections {
+    #[cfg(feature = "sqlite")]
+    pub database: Option<crate::sql::Database>, // `sql open`
+    #[cfg(feature = "mqtt")]
+    pub mqtt: Option<crate::mqtt::Session>, // `mqtt connect`
+    #[cfg(feature = "k8s")]
+    pub kube: crate::k8s::Target, // `k8s context` / `k8s ns`
+    #[cfg(feature = "media")]
+    pub media: crate::media::NowPlaying, // for the status bar
+}
+
+pub struct Ctx<'a> {
+    pub config: &'a Config,
+    pub vars: &'a mut BTreeMap<String, String>, // `set name value`, expanded as $name
+    pub clipboard: &'a mut Clipboard,
+    pub spawner: &'a Spawner,
+    #[cfg_attr(
+        not(any(feature = "sqlite", feature = "mqtt", feature = "k8s")),
+        allow(dead_code)
+    )]
+    pub connections: &'a mut Connections,
+    pub effects: &'a Effects, // processes, files, HTTP (see effects.rs)
+    tx: &'a Sender<AppEvent>,
+}
+
+impl<'a> Ctx<'a> {
+    pub fn new(
+        config: &'a Config,
+        vars: &'a mut BTreeMap<String, String>,
+        clipboard: &'a mut Clipboard,
+        spawner: &'a Spawner,
+        connections: &'a mut Connections,
+        effects: &'a Effects,
+        tx: &'a Sender<AppEvent>,
+    ) -> Self {
+        Self {
+            config,
+            vars,
+            clipboard,
+            spawner,
+            connections,
+            effects,
+            tx,
+        }
+    }
+
+    pub fn send(&self, event: AppEvent) {
+        // The receiver only goes away when the app is shutting down,
+        // at which point nobody is reading output anyway
+        let _ = self.tx.send(event);
+    }
+
+    // For a pane that sends events of its own later (AppEvent::Run…)
+    pub fn sender(&self) -> Sender<AppEvent> {
+        self.tx.clone()
+    }
+
+    pub fn print(&self, line: impl Into<String>) {
+        self.send(AppEvent::Print(line.into()));
+    }
+
+    pub fn clear(&self) {
+        self.send(AppEvent::Clear);
+    }
+
+    pub fn quit(&self) {
+        self.send(AppEvent::Quit);
+    }
+
+    // Show a popup; `on_done` gets its answer unless it's cancelled
+    pub fn open<O, F>(&self, overlay: O, on_done: F)
+    where
+        O: Overlay + 'static,
+        F: FnOnce(&mut Ctx, O::Answer) + Send + 'static,
+    {
+        self.send(AppEvent::Open(Modal::new(overlay, on_done)));
+    }
+
+    pub fn show(&self, pane: impl Pane + 'static) {
+        self.send(AppEvent::ShowPane(Box::new(pane)));
+    }
+
+    // A notice that goes away by itself (see toast.rs)
+    pub fn toast(&self, level: Level, text: impl Into<String>) {
+        self.send(AppEvent::Toast(level, text.into()));
+    }
+
+    // Print an error line and report failure, for `return ctx.fail(...)`
+    pub fn fail(&self, line: impl Into<String>) -> Status {
+        self.print(line);
+        Status::Failure
+    }
+}
+
+// =============================================================================
+// Spawn handle — runs work off the UI thread
+//
+// The job gets its own Sender, so it can stream output back while it runs.
+// Never block the main loop in a command; spawn instead.
+//
+// spawn_with_progress() also hands the job a ProgressHandle; what it reports
+// is drawn as a gauge in the status bar until the job finishes:
+//
+//   ctx.spawner.spawn_with_progress("Indexing", |tx, progress| {
+//       for (i, file) in files.iter().enumerate() {
+//           if progress.cancelled() {
+//               return;
+//           }
+//           progress.set(i as f64 / files.len() as f64);
+//           …
+//       }
+//   });
+//
+// Such a job is also listed by `jobs` and can be stopped with `cancel`;
+// cancelled() is how it finds out, so check it between steps. A job that
+// can't say how far along it is (following a file) calls progress.unknown().
+//
+// A job is timed from spawn to return, and AppEvent::JobDone says how long
+// it took (see notifications.rs). It's named after its progress label, else
+// the command that spawned it ("hex big.bin"). What feeds a pane for as
+// long as it's open (a chart, the git panel, a socket) uses spawn_watcher()
+// instead: it ends when the pane closes, which is nothing to announce.
+// =============================================================================
+pub struct Spawner {
+    tx: Sender<AppEvent>,
+    active: Arc<AtomicUsize>, // jobs still running
+    next_id: AtomicU64,       // for ProgressHandles
+    jobs: Jobs,               // the ones with a ProgressHandle, for `jobs` / `cancel`
+    command: Mutex<String>,   // being run, to name the jobs it spawns
+}
+
+// id → (label, cancelled)
+type Jobs = Arc<Mutex<BTreeMap<u64, (String, Arc<AtomicBool>)>>>;
+
+impl Spawner {
+    pub fn new(tx: Sender<AppEvent>) -> Self {
+        Self {
+            tx,
+            active: Arc::default(),
+            next_id: AtomicU64::new(1),
+            jobs: Jobs::default(),
+            command: Mutex::default(),
+        }
+    }
+
+    // Name the jobs spawned from now on after `command`; returns the name
+    // it replaces, to put back once the command returns
+    pub fn name_jobs(&self, command: &str) -> String {
+        self.command.lock().map_or_else(
+            |_| String::new(),
+            |mut current| std::mem::replace(&mut *current, command.to_string()),
+        )
+    }
+
+    pub fn spawn_with_progress<F>(&self, label: impl Into<String>, job: F)
+    where
+        F: FnOnce(Sender<AppEvent>, ProgressHandle) + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let label = label.into();
+        let cancelled = Arc::<AtomicBool>::default();
+        if let Ok(mut jobs) = self.jobs.lock() {
+            jobs.insert(id, (label.clone(), cancelled.clone()));
+        }
+        let progress = ProgressHandle {
+            id,
+            label,
+            tx: self.tx.clone(),
+            cancelled,
+            jobs: self.jobs.clone(),
+        };
+        progress.set(0.0);
+        let label = progress.label.clone();
+        self.spawn_as(Some(label), move |tx| job(tx, progress));
+    }
+
+    pub fn spawn<F>(&self, job: F)
+    where
+        F: FnOnce(Sender<AppEvent>) + Send + 'static,
+    {
+        let label = self
+            .command
+            .lock()
+            .map_or_else(|_| String::new(), |c| c.clone());
+        self.spawn_as(Some(label), job);
+    }
+
+    // A pane's feed, running until the pane closes: not timed
+    pub fn spawn_watcher<F>(&self, job: F)
+    where
+        F: FnOnce(Sender<AppEvent>) + Send + 'static,
+    {
+        self.spawn_as(None, job);
+    }
+
+    // `label` None: don't send JobDone
+    fn spawn_as<F>(&self, label: Option<String>, job: F)
+    where
+        F: FnOnce(Sender<AppEvent>) + Send + 'static,
+    {
+        let tx = self.tx.clone();
+        let guard = ActiveGuard::new(&self.active);
+        let started = clock::now();
+        tracing::debug!(active = self.active(), "job spawned");
+        thread::spawn(move || {
+            let _guard = guard; // count the job as finished even if it panics
+            job(tx.clone());
+            tracing::debug!("job finished");
+            if let Some(label) = label {
+                let _ = tx.send(AppEvent::JobDone { label, started });
+            }
+        });
+    }
+
+    // Number of spawned jobs that haven't finished yet
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    // The running jobs that have a ProgressHandle, as (id, label)
+    pub fn jobs(&self) -> Vec<(u64, String)> {
+        self.jobs.lock().map_or(Vec::new(), |jobs| {
+            jobs.iter()
+                .map(|(id, (label, _))| (*id, label.clone()))
+                .collect()
+        })
+    }
+
+    // Ask a job to stop; false if there's no such job
+    pub fn cancel(&self, id: u64) -> bool {
+        let jobs = self.jobs.lock();
+        match jobs.as_ref().ok().and_then(|jobs| jobs.get(&id)) {
+            Some((_, cancelled)) => {
+                cancelled.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+struct ActiveGuard(Arc<AtomicUsize>);
+
+impl ActiveGuard {
+    fn new(active: &Arc<AtomicUsize>) -> Self {
+        active.fetch_add(1, Ordering::SeqCst);
+        Self(Arc::clone(active))
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// A running job's gauge. Dropping it (normally when the job returns)
+// removes the gauge, and the job from `jobs`.
+pub struct ProgressHandle {
+    id: u64,
+    label: String,
+    tx: Sender<AppEvent>,
+    cancelled: Arc<AtomicBool>,
+    jobs: Jobs,
+}
+
+impl ProgressHandle {
+    // How far along the job is, from 0.0 to 1.0
+    pub fn set(&self, fraction: f64) {
+        let _ = self.tx.send(AppEvent::Progress {
+            id: self.id,
+            label: self.label.clone(),
+            fraction: fraction.clamp(0.0, 1.0),
+        });
+    }
+
+    // No way to tell how far along: the gauge just shows it's running
+    pub fn unknown(&self) {
+        let _ = self.tx.send(AppEvent::Progress {
+            id: self.id,
+            label: self.label.clone(),
+            fraction: f64::NAN,
+        });
+    }
+
+    // `cancel` was used on this job; it should wrap up and return
+    pub fn cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for ProgressHandle {
+    fn drop(&mut self) {
+        if let Ok(mut jobs) = self.jobs.lock() {
+            jobs.remove(&self.id);
+        }
+        let _ = self.tx.send(AppEvent::ProgressDone(self.id));
+    }
+}
@@ -0,0 +1,74 @@
+// =============================================================================
+// Cursor — its shape, and whether it blinks, by what has the keyboard
+//
+//   [cursor]                     # in config.toml
+//   input = "bar"                # in the input bar
+//   pane = "block"               # in a focused pane (the editor)
+//   popup = "underline"          # in a popup's text field
+//   blink = false
+//
+// Each is "bar", "block", "underline" or "default", the terminal's own
+// shape, which is what all three are unless set. `blink` goes for the
+// other shapes. The main loop sets the shape whenever the keyboard moves
+// (see run_loop) and hands the terminal's own back on the way out.
+// =============================================================================
+
+use crossterm::cursor::SetCursorStyle;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Shape {
+    #[default]
+    Default,
+    Bar,
+    Block,
+    Underline,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CursorConfig {
+    pub input: Shape,
+    pub pane: Shape,
+    pub popup: Shape,
+    pub blink: bool,
+}
+
+impl Default for CursorConfig {
+    fn default() -> Self {
+        Self {
+            input: Shape::Default,
+            pane: Shape::Default,
+            popup: Shape::Default,
+            blink: true,
+        }
+    }
+}
+
+// What has the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Input,
+    Pane,
+    Popup,
+}
+
+impl CursorConfig {
+    pub fn style(&self, mode: Mode) -> SetCursorStyle {
+        let shape = match mode {
+            Mode::Input => self.input,
+            Mode::Pane => self.pane,
+            Mode::Popup => self.popup,
+        };
+        match (shape, self.blink) {
+            (Shape::Default, _) => SetCursorStyle::DefaultUserShape,
+            (Shape::Bar, true) => SetCursorStyle::BlinkingBar,
+            (Shape::Bar, false) => SetCursorStyle::SteadyBar,
+            (Shape::Block, true) => SetCursorStyle::BlinkingBlock,
+            (Shape::Block, false) => SetCursorStyle::SteadyBlock,
+            (Shape::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+            (Shape::Underline, false) => SetCursorStyle::SteadyUnderScore,
+        }
+    }
+}
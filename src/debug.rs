@@ -0,0 +1,108 @@
+// =============================================================================
+// Debug overlay — F12 shows how the app is doing, over everything else
+//
+//   ┌ Debug ──────────────────────────────┐
+//   │fps          12                      │
+//   │frame        1.8 ms                  │
+//   │mode         input                   │
+//   │scroll       following (of 1204)     │
+//   │scrollback   96.3 KiB                │
+//   │events       ctrl-l                  │
+//   │             resize 120×40           │
+//   └─────────────────────────────────────┘
+//
+// fps counts the frames drawn in the last second — the loop only draws
+// when something changed, so an idle app shows a low number, not a slow
+// one. frame is how long the last draw took; the last EVENTS input events
+// are listed newest first. Bound to the `debug` action (see keymap.rs).
+// =============================================================================
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::Event;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{clock, keymap::KeyBinding, theme::Theme};
+
+const EVENTS: usize = 6;
+const WIDTH: u16 = 40;
+const LABEL: usize = 12;
+
+#[derive(Default)]
+pub struct DebugOverlay {
+    pub shown: bool,
+    frames: VecDeque<Instant>, // when each frame of the last second was drawn
+    frame_time: Duration,      // the last one's
+    events: VecDeque<String>,  // newest first
+}
+
+impl DebugOverlay {
+    pub fn frame(&mut self, took: Duration) {
+        let now = clock::now();
+        self.frames.push_back(now);
+        while self
+            .frames
+            .front()
+            .is_some_and(|&at| now.saturating_duration_since(at) > Duration::from_secs(1))
+        {
+            self.frames.pop_front();
+        }
+        self.frame_time = took;
+    }
+
+    pub fn event(&mut self, event: &Event) {
+        let text = match event {
+            Event::Key(key) => KeyBinding::from(*key).to_string(),
+            Event::Mouse(mouse) => {
+                format!("mouse {:?} at {},{}", mouse.kind, mouse.column, mouse.row)
+            }
+            Event::Resize(width, height) => format!("resize {width}×{height}"),
+            Event::Paste(text) => format!("paste of {} characters", text.chars().count()),
+            Event::FocusGained => "focus gained".into(),
+            Event::FocusLost => "focus lost".into(),
+        };
+        self.events.push_front(text);
+        self.events.truncate(EVENTS);
+    }
+
+    // `rows` are what the app knows and this doesn't: mode, scroll…
+    pub fn render(&self, frame: &mut Frame, rows: Vec<(&str, String)>, theme: &Theme) {
+        let mut lines = vec![
+            ("fps", self.frames.len().to_string()),
+            (
+                "frame",
+                format!("{:.1} ms", self.frame_time.as_secs_f64() * 1000.0),
+            ),
+        ];
+        lines.extend(rows);
+        for (i, event) in self.events.iter().enumerate() {
+            lines.push((if i == 0 { "events" } else { "" }, event.clone()));
+        }
+        let text: Vec<String> = lines
+            .iter()
+            .map(|(label, value)| format!("{label:LABEL$} {value}"))
+            .collect();
+
+        let full = frame.area();
+        let area = Rect {
+            x: full.x,
+            y: full.y,
+            width: WIDTH.min(full.width),
+            height: (text.len() as u16 + 2).min(full.height),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Debug ")
+            .border_style(Style::default().fg(theme.input_border));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(text.join("\n")).block(block), area);
+    }
+}
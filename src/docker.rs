@@ -0,0 +1,377 @@
+// =============================================================================
+// Docker — `docker`: containers in a table, with start / stop / logs (the
+// `docker` feature)
+//
+//   cargo run --features docker
+//   > docker
+//   > docker logs web
+//   > docker stop web
+//
+// Talks to the daemon's API (bollard) over the usual socket, or DOCKER_HOST.
+// The pane lists every container, running or not, with its CPU and memory
+// use, re-read every REFRESH by a background job; sort and filter as in any
+// table (widgets/table.rs).
+//
+//   s            start the selected container, or stop it if it's running
+//   l            follow its logs into Output
+//
+// Those keys run the commands below, which work without the pane too. Each
+// is a job: `docker logs` prints the last LOG_TAIL lines and then follows,
+// tagged with the container's name like `tail`, until `cancel`led.
+//
+//   docker start|stop|restart <name>
+//   docker logs <name>
+// =============================================================================
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex, mpsc::Sender},
+    time::Duration,
+};
+
+use bollard::{
+    Docker,
+    container::LogOutput,
+    models::{ContainerStatsResponse, ContainerSummary, ContainerSummaryStateEnum},
+    query_parameters::{ListContainersOptionsBuilder, LogsOptionsBuilder, StatsOptionsBuilder},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use futures_util::{StreamExt, future};
+use ratatui::{Frame, layout::Rect};
+
+use crate::{
+    ctx::{AppEvent, Ctx, ProgressHandle, Status},
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+    widgets::TableView,
+};
+
+const REFRESH: Duration = Duration::from_secs(3);
+const POLL: Duration = Duration::from_millis(250); // how often jobs check for closing / `cancel`
+const LOG_TAIL: &str = "50";
+const HEADERS: [&str; 6] = ["Name", "Image", "State", "Status", "CPU", "Mem MiB"];
+const STATE: usize = 2; // column s looks at
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: docker | docker start|stop|restart|logs <name>";
+    let (op, name) = args.split_once(' ').unwrap_or((args, ""));
+    let name = name.trim().to_string();
+    match op {
+        "" => show(ctx),
+        _ if name.is_empty() || name.contains(' ') => ctx.fail(usage),
+        "logs" => {
+            ctx.spawner
+                .spawn_with_progress(format!("docker logs {name}"), move |tx, progress| {
+                    progress.unknown();
+                    block_on(&tx, follow_logs(&tx, &progress, &name));
+                });
+            Status::Success
+        }
+        "start" | "stop" | "restart" => {
+            let op = op.to_string();
+            ctx.spawner.spawn(move |tx| {
+                block_on(&tx, change(&tx, &op, &name));
+            });
+            Status::Success
+        }
+        _ => ctx.fail(usage),
+    }
+}
+
+fn print(tx: &Sender<AppEvent>, line: String) {
+    let _ = tx.send(AppEvent::Print(line));
+}
+
+// bollard is async; each job runs its own small runtime
+fn block_on(tx: &Sender<AppEvent>, job: impl Future<Output = ()>) {
+    match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime.block_on(job),
+        Err(err) => print(tx, format!("  Can't start the Docker client: {err}")),
+    }
+}
+
+fn connect() -> Result<Docker, String> {
+    Docker::connect_with_defaults().map_err(|err| format!("can't reach Docker: {err}"))
+}
+
+async fn change(tx: &Sender<AppEvent>, op: &str, name: &str) {
+    let docker = match connect() {
+        Ok(docker) => docker,
+        Err(why) => return print(tx, format!("  {name}: {why}")),
+    };
+    let (result, done) = match op {
+        "start" => (docker.start_container(name, None).await, "Started"),
+        "stop" => (docker.stop_container(name, None).await, "Stopped"),
+        _ => (docker.restart_container(name, None).await, "Restarted"),
+    };
+    match result {
+        Ok(()) => print(tx, format!("  {done} {name}")),
+        Err(err) => print(tx, format!("  Can't {op} {name}: {err}")),
+    }
+}
+
+async fn follow_logs(tx: &Sender<AppEvent>, progress: &ProgressHandle, name: &str) {
+    let docker = match connect() {
+        Ok(docker) => docker,
+        Err(why) => return print(tx, format!("  [{name}] {why}")),
+    };
+    let options = LogsOptionsBuilder::new()
+        .follow(true)
+        .stdout(true)
+        .stderr(true)
+        .tail(LOG_TAIL)
+        .build();
+    let mut logs = docker.logs(name, Some(options));
+    let mut partial = String::new(); // a line that hasn't ended yet
+    while !progress.cancelled() {
+        let Ok(next) = tokio::time::timeout(POLL, logs.next()).await else {
+            continue;
+        };
+        let message = match next {
+            Some(Ok(
+                LogOutput::StdOut { message }
+                | LogOutput::StdErr { message }
+                | LogOutput::Console { message },
+            )) => message,
+            Some(Ok(LogOutput::StdIn { .. })) => continue,
+            Some(Err(err)) => return print(tx, format!("  [{name}] Stopped following: {err}")),
+            None => break, // the container stopped
+        };
+        partial.push_str(&String::from_utf8_lossy(&message));
+        while let Some(end) = partial.find('\n') {
+            let line: String = partial.drain(..=end).collect();
+            print(
+                tx,
+                format!("  [{name}] {}", line.trim_end_matches(['\r', '\n'])),
+            );
+        }
+    }
+    print(tx, format!("  [{name}] Stopped following"));
+}
+
+#[derive(Default)]
+struct State {
+    containers: Option<Result<Vec<Vec<String>>, String>>, // None until the first read
+    changed: bool,                                        // since the pane took them
+    closed: bool,                                         // the pane is gone
+}
+
+type Shared = Arc<Mutex<State>>;
+
+fn show(ctx: &mut Ctx) -> Status {
+    let state = Shared::default();
+    let job_state = state.clone();
+    ctx.show(DockerView {
+        state,
+        table: TableView::new("Docker", HEADERS),
+        error: None,
+        tx: ctx.sender(),
+    });
+    ctx.spawner.spawn_watcher(move |tx| {
+        block_on(&tx, watch(&job_state));
+    });
+    Status::Success
+}
+
+// Re-list the containers every REFRESH, until the pane closes
+async fn watch(state: &Shared) {
+    let docker = connect();
+    loop {
+        let containers = match &docker {
+            Ok(docker) => list(docker).await,
+            Err(why) => Err(why.clone()),
+        };
+        match state.lock() {
+            Ok(mut state) if !state.closed => {
+                state.containers = Some(containers);
+                state.changed = true;
+            }
+            _ => return,
+        }
+        for _ in 0..REFRESH.as_millis() / POLL.as_millis() {
+            tokio::time::sleep(POLL).await;
+            if state.lock().map_or(true, |s| s.closed) {
+                return;
+            }
+        }
+    }
+}
+
+async fn list(docker: &Docker) -> Result<Vec<Vec<String>>, String> {
+    let options = ListContainersOptionsBuilder::new().all(true).build();
+    let containers = docker
+        .list_containers(Some(options))
+        .await
+        .map_err(|err| format!("can't list containers: {err}"))?;
+    // Stats take a moment each (the daemon samples CPU twice), so all at once
+    let stats = future::join_all(containers.iter().map(|c| stats(docker, c))).await;
+    let mut stats: HashMap<String, ContainerStatsResponse> = stats.into_iter().flatten().collect();
+    Ok(containers
+        .into_iter()
+        .map(|container| {
+            let id = container.id.clone().unwrap_or_default();
+            let (cpu, memory) = stats
+                .remove(&id)
+                .map_or((String::new(), String::new()), usage);
+            let name = container.names.as_ref().and_then(|names| names.first());
+            vec![
+                name.map_or(id.chars().take(12).collect(), |n| {
+                    n.trim_start_matches('/').to_string()
+                }),
+                container.image.unwrap_or_default(),
+                container.state.map_or(String::new(), |s| s.to_string()),
+                container.status.unwrap_or_default(),
+                cpu,
+                memory,
+            ]
+        })
+        .collect())
+}
+
+// Running containers only: the others have nothing to measure
+async fn stats(
+    docker: &Docker,
+    container: &ContainerSummary,
+) -> Option<(String, ContainerStatsResponse)> {
+    if container.state != Some(ContainerSummaryStateEnum::RUNNING) {
+        return None;
+    }
+    let id = container.id.clone()?;
+    let options = StatsOptionsBuilder::new().stream(false).build();
+    let stats = docker.stats(&id, Some(options)).next().await?.ok()?;
+    Some((id, stats))
+}
+
+// (CPU %, memory MiB) the way `docker stats` works them out
+fn usage(stats: ContainerStatsResponse) -> (String, String) {
+    let total = |cpu: &Option<bollard::models::ContainerCpuStats>| {
+        let cpu = cpu.as_ref();
+        let used = cpu
+            .and_then(|c| c.cpu_usage.as_ref())
+            .and_then(|u| u.total_usage);
+        (
+            used.unwrap_or(0),
+            cpu.and_then(|c| c.system_cpu_usage).unwrap_or(0),
+        )
+    };
+    let (used, system) = total(&stats.cpu_stats);
+    let (used_before, system_before) = total(&stats.precpu_stats);
+    let cpus = stats
+        .cpu_stats
+        .as_ref()
+        .and_then(|c| c.online_cpus)
+        .unwrap_or(1);
+    let (used, system) = (
+        used.saturating_sub(used_before),
+        system.saturating_sub(system_before),
+    );
+    let cpu = match system {
+        0 => 0.0,
+        _ => used as f64 / system as f64 * f64::from(cpus) * 100.0,
+    };
+
+    // Page cache the kernel can drop anyway doesn't count
+    let memory = stats.memory_stats.as_ref();
+    let cache = memory.and_then(|m| m.stats.as_ref()).and_then(|s| {
+        s.get("inactive_file")
+            .or(s.get("total_inactive_file"))
+            .copied()
+    });
+    let bytes = memory
+        .and_then(|m| m.usage)
+        .unwrap_or(0)
+        .saturating_sub(cache.unwrap_or(0));
+    (
+        format!("{cpu:.1}"),
+        format!("{:.1}", bytes as f64 / (1024.0 * 1024.0)),
+    )
+}
+
+pub struct DockerView {
+    state: Shared,
+    table: TableView,
+    error: Option<String>, // the last read failed
+    tx: Sender<AppEvent>,
+}
+
+impl DockerView {
+    // Ask for it as a command, so it's a job like a typed one
+    fn run(&self, op: &str) {
+        if let Some(row) = self.table.selected_row() {
+            let op = match op {
+                "toggle" if row[STATE] == "running" => "stop",
+                "toggle" => "start",
+                op => op,
+            };
+            let _ = self
+                .tx
+                .send(AppEvent::Run(format!("docker {op} {}", row[0])));
+        }
+    }
+}
+
+impl Drop for DockerView {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+    }
+}
+
+impl Pane for DockerView {
+    fn title(&self) -> String {
+        match &self.error {
+            Some(why) => format!("Docker: {why}"),
+            None => self.table.title(),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if !self.table.typing() {
+            match key.code {
+                KeyCode::Char('s') => self.run("toggle"),
+                KeyCode::Char('l') => self.run("logs"),
+                _ => return self.table.handle_key(key),
+            }
+            return true;
+        }
+        self.table.handle_key(key)
+    }
+
+    fn tick(&mut self) -> bool {
+        let containers = match self.state.lock() {
+            Ok(mut state) if state.changed => {
+                state.changed = false;
+                state.containers.take()
+            }
+            _ => return false,
+        };
+        match containers {
+            Some(Ok(rows)) => {
+                self.error = None;
+                self.table.set_rows(rows);
+            }
+            Some(Err(why)) => self.error = Some(why),
+            None => {}
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("↑↓", "select"),
+            ("s", "start/stop"),
+            ("l", "logs"),
+            ("/", "filter"),
+        ]
+    }
+}
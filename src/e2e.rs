@@ -0,0 +1,283 @@
+// =============================================================================
+// End-to-end tests — a script through the real main loop, checked against
+// golden screens at each of SIZES
+//
+//   #[test]
+//   fn greets_and_runs_a_command() {
+//       golden("greets_and_runs_a_command", "
+//           type hello ada
+//           press enter
+//       ");
+//   }
+//
+// A script is one step per line:
+//
+//   type <text>            each character as a key, spaces and all
+//   paste <text>           one paste of it, with \n for each line break
+//   press <key> [<key>…]   keys as the keymap writes them ("ctrl-l", "f10")
+//   click <col> <row>      the left button, at a cell
+//   focus in|out           the terminal window gaining or losing focus
+//
+// Blank lines and `#` comments are skipped. The steps become events for a
+// Script host (see host.rs) and run_loop() runs until they're out: keymap,
+// debouncing, ticks, draws and all, into a TestBackend. It's the app as
+// testing::app() starts it, so neither the user's config nor their history
+// is involved.
+//
+// The screen the loop last drew is the golden file: src/snapshots/
+// tui_template__e2e__<name>@<width>x<height>.snap. Review and accept
+// changes as for testing.rs (`cargo insta review`).
+// =============================================================================
+
+use std::{collections::VecDeque, io::Write, time::Duration};
+
+use color_eyre::Result;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::{Terminal, backend::TestBackend};
+
+use crate::{host::Host, keymap::KeyBinding, run_loop, testing};
+
+const SIZES: [(u16, u16); 2] = [(80, 24), (40, 12)];
+
+struct Script {
+    events: VecDeque<Event>,
+    out: Vec<u8>, // what the loop wrote to the terminal besides frames
+    waits: usize, // times the loop came round and waited, so drew at most once
+    kitty: bool,  // whether to claim the kitty keyboard protocol
+}
+
+impl Script {
+    fn parse(script: &str) -> Self {
+        let mut events = VecDeque::new();
+        for line in script.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (step, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match step {
+                "type" => events.extend(
+                    rest.chars()
+                        .map(|c| Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))),
+                ),
+                "paste" => events.push_back(Event::Paste(rest.replace("\\n", "\n"))),
+                "press" => events.extend(rest.split_whitespace().map(|key| {
+                    let key: KeyBinding = key.parse().expect("a key name");
+                    Event::Key(key.into())
+                })),
+                "click" => {
+                    let mut at = rest.split_whitespace().map(|n| n.parse().expect("a cell"));
+                    let (Some(column), Some(row)) = (at.next(), at.next()) else {
+                        panic!("click wants a column and a row: {line}");
+                    };
+                    events.push_back(Event::Mouse(MouseEvent {
+                        kind: MouseEventKind::Down(MouseButton::Left),
+                        column,
+                        row,
+                        modifiers: KeyModifiers::NONE,
+                    }));
+                }
+                "focus" => events.push_back(match rest {
+                    "in" => Event::FocusGained,
+                    "out" => Event::FocusLost,
+                    _ => panic!("focus in or out: {line}"),
+                }),
+                _ => panic!("unknown step: {line}"),
+            }
+        }
+        Self {
+            events,
+            out: Vec::new(),
+            waits: 0,
+            kitty: false,
+        }
+    }
+}
+
+impl Host for Script {
+    fn event(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        self.waits += usize::from(!timeout.is_zero());
+        Ok(self.events.pop_front())
+    }
+
+    fn out(&mut self) -> &mut dyn Write {
+        &mut self.out
+    }
+
+    fn kitty_keyboard(&mut self) -> bool {
+        self.kitty
+    }
+
+    fn done(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+fn golden(name: &str, script: &str) {
+    for (width, height) in SIZES {
+        let mut app = testing::app();
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("a test terminal");
+        run_loop(&mut terminal, &mut app, &mut Script::parse(script)).expect("the loop");
+        insta::assert_snapshot!(
+            format!("{name}@{width}x{height}"),
+            testing::text(terminal.backend().buffer())
+        );
+    }
+}
+
+#[test]
+fn greets_and_runs_a_command() {
+    golden(
+        "greets_and_runs_a_command",
+        "
+        type hello ada
+        press enter
+        ",
+    );
+}
+
+#[test]
+fn scrolls_back_through_help() {
+    golden(
+        "scrolls_back_through_help",
+        "
+        type help
+        press enter pageup
+        ",
+    );
+}
+
+#[test]
+fn edits_and_recalls_history() {
+    golden(
+        "edits_and_recalls_history",
+        "
+        type hello bob
+        press backspace backspace backspace
+        type ada
+        press enter
+        type set x 1
+        press enter ctrl-u up up
+        ",
+    );
+}
+
+#[test]
+fn pastes_lines_as_commands() {
+    golden(
+        "pastes_lines_as_commands",
+        r"
+        type hello
+        paste  ada\nhello bob\nset x
+        ",
+    );
+}
+
+#[test]
+fn opens_the_menu() {
+    golden(
+        "opens_the_menu",
+        "
+        # unfocused and back, which changes nothing on screen
+        focus out
+        focus in
+        press f10 right
+        ",
+    );
+}
+
+#[test]
+fn a_burst_of_keys_is_one_redraw() {
+    let mut app = testing::app();
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("a test terminal");
+    let mut script = Script::parse(&format!("type {}", "x".repeat(200)));
+    run_loop(&mut terminal, &mut app, &mut script).expect("the loop");
+    assert_eq!(app.input().len(), 200);
+    assert_eq!(script.waits, 1);
+}
+
+#[test]
+fn titles_the_window_and_gives_the_old_title_back() {
+    let mut app = testing::app();
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("a test terminal");
+    let mut script = Script::parse("type crumbs docs/api\npress enter");
+    run_loop(&mut terminal, &mut app, &mut script).expect("the loop");
+    let out = String::from_utf8_lossy(&script.out);
+    let pushed = out.find("\x1b[22;0t").expect("the old title pushed");
+    let titled = out
+        .find("\x1b]0;tui-template — docs › api\x07")
+        .expect("the crumbs in the title");
+    assert!(pushed < titled);
+    assert!(out.ends_with("\x1b[23;0t")); // popped
+}
+
+#[test]
+fn draws_nothing_while_unfocused() {
+    for (script, drawn) in [
+        ("focus out\ntype hello", false),
+        ("focus out\ntype hello\nfocus in", true),
+    ] {
+        let mut app = testing::app();
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("a test terminal");
+        run_loop(&mut terminal, &mut app, &mut Script::parse(script)).expect("the loop");
+        assert_eq!(app.input(), "hello");
+        let screen = testing::text(terminal.backend().buffer());
+        assert_eq!(screen.contains("hello"), drawn, "{script}");
+    }
+}
+
+#[test]
+fn f9_gives_the_mouse_back_to_the_terminal() {
+    let mut app = testing::app();
+    app.config.layout.minimap = true;
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("a test terminal");
+    let mut script = Script::parse("press f9");
+    run_loop(&mut terminal, &mut app, &mut script).expect("the loop");
+    let out = String::from_utf8_lossy(&script.out);
+    let captured = out.find("\x1b[?1000h").expect("captured for the minimap");
+    let released = out.find("\x1b[?1000l").expect("released on f9");
+    let ended = out.find("\x1b[?1004l").expect("the loop's end");
+    assert!(captured < released && released < ended);
+    assert!(!app.mouse);
+}
+
+#[test]
+fn shapes_the_cursor_by_what_has_the_keyboard() {
+    for (script, shape) in [("type x", "\x1b[6 q"), ("press f10", "\x1b[4 q")] {
+        let mut app = testing::app();
+        app.config.cursor = toml::from_str(
+            r#"
+            input = "bar"
+            popup = "underline"
+            blink = false
+            "#,
+        )
+        .expect("a cursor config");
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("a test terminal");
+        let mut script = Script::parse(script);
+        run_loop(&mut terminal, &mut app, &mut script).expect("the loop");
+        let out = String::from_utf8_lossy(&script.out);
+        assert!(out.contains(shape), "{out:?}");
+        assert!(out.ends_with("\x1b[0 q")); // the terminal's own again
+    }
+}
+
+#[test]
+fn turns_the_kitty_keyboard_protocol_on_where_there_is_one() {
+    for (kitty, configured, pushed) in [
+        (true, true, true),
+        (false, true, false),
+        (true, false, false),
+    ] {
+        let mut app = testing::app();
+        app.config.kitty_keyboard = configured;
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("a test terminal");
+        let mut script = Script::parse("type x");
+        script.kitty = kitty;
+        run_loop(&mut terminal, &mut app, &mut script).expect("the loop");
+        let out = String::from_utf8_lossy(&script.out);
+        assert_eq!(out.contains("\x1b[>3u"), pushed, "{out:?}"); // disambiguate, event types
+        assert_eq!(out.contains("\x1b[<1u"), pushed); // and popped
+    }
+}
@@ -0,0 +1,501 @@
+// =============================================================================
+// Effects — what commands do to the world outside the app, behind traits
+//
+//   ctx.effects.processes      run a program (shell.rs, ssh, watch, git)
+//   ctx.effects.files          read, write and list files (json, diff, edit,
+//                              tree, hex, grep, csv, tail, preview, pick,
+//                              show image, theme, session, export/import,
+//                              feeds, history)
+//   ctx.effects.http           send a request (http.rs; the `http` feature)
+//
+// App holds System, which really does these things. A test can give it a
+// Fake instead, which does them in memory and remembers what was asked:
+//
+//   let fake = Arc::new(Fake::default().with_file("a.json", r#"{"x": 1}"#));
+//   app.effects = Effects::fake(&fake);
+//   … run `json a.json .x` …
+//   assert_eq!(fake.calls(), ["read a.json"]);
+//
+// Jobs run on their own threads, so they take a clone of the Arc they need
+// along with them (`let files = ctx.effects.files.clone();`).
+// =============================================================================
+
+#[cfg(test)]
+use std::{collections::BTreeMap, path::Component, sync::Mutex};
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Output, Stdio},
+    sync::{Arc, mpsc::Sender},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::ctx::AppEvent;
+
+const POLL: Duration = Duration::from_millis(100); // how often run() checks `cancelled`
+
+#[derive(Clone)]
+pub struct Effects {
+    pub processes: Arc<dyn Processes>,
+    pub files: Arc<dyn Files>,
+    #[cfg(feature = "http")]
+    pub http: Arc<dyn Http>,
+}
+
+impl Default for Effects {
+    fn default() -> Self {
+        Self {
+            processes: Arc::new(System),
+            files: Arc::new(System),
+            #[cfg(feature = "http")]
+            http: Arc::new(System),
+        }
+    }
+}
+
+pub trait Processes: Send + Sync {
+    // Run `command` to the end with nothing on its stdin, printing each line
+    // of its stdout and stderr as it comes, and killing it once `cancelled`
+    fn run(
+        &self,
+        tx: &Sender<AppEvent>,
+        command: Command,
+        cancelled: &dyn Fn() -> bool,
+    ) -> io::Result<ExitStatus>;
+
+    // Run `command` to the end and collect what it printed
+    #[cfg_attr(not(feature = "git"), allow(dead_code))]
+    fn output(&self, command: Command) -> io::Result<Output>;
+}
+
+pub trait Files: Send + Sync {
+    fn read(&self, path: &Path) -> io::Result<String>;
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>>;
+    // For reading as it goes: a big file a page at a time, or one that grows
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>>;
+    fn write(&self, path: &Path, text: &str) -> io::Result<()>;
+    // Replacing `to` if it's there, as one step
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    // And any parents it needs
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+    // A directory's entries, as paths under it, in no particular order
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+    fn metadata(&self, path: &Path) -> io::Result<Metadata>;
+    // Absolute, with no `.`, `..` or symlinks; an error if it isn't there
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+}
+
+// What Files::metadata says about a path (following symlinks)
+pub struct Metadata {
+    pub is_dir: bool,
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+    pub identity: u64, // the inode where there are any, else 0
+}
+
+#[cfg(feature = "http")]
+pub trait Http: Send + Sync {
+    // A failure is the reason, causes and all ("… : connection refused")
+    fn send(&self, request: Request) -> Result<Response, String>;
+}
+
+#[cfg(feature = "http")]
+pub struct Request {
+    pub url: String,
+    pub body: Option<(String, &'static str)>, // a POST's body and content type; None GETs
+}
+
+#[cfg(feature = "http")]
+pub struct Response {
+    pub version: String,                // "HTTP/1.1"
+    pub status: String,                 // "200 OK"
+    pub headers: Vec<(String, String)>, // names lowercase
+    pub length: Option<u64>,            // if the server said
+    pub body: Box<dyn Read + Send>,     // as it arrives
+}
+
+// The real thing
+pub struct System;
+
+impl Processes for System {
+    fn run(
+        &self,
+        tx: &Sender<AppEvent>,
+        mut command: Command,
+        cancelled: &dyn Fn() -> bool,
+    ) -> io::Result<ExitStatus> {
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Each pipe on its own thread, so neither fills up while we read the
+        // other, and this one is free to notice `cancelled`
+        let readers: Vec<_> = [
+            child
+                .stdout
+                .take()
+                .map(|out| Box::new(out) as Box<dyn Read + Send>),
+            child
+                .stderr
+                .take()
+                .map(|err| Box::new(err) as Box<dyn Read + Send>),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|output| {
+            let tx = tx.clone();
+            thread::spawn(move || print_lines(&tx, output))
+        })
+        .collect();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if cancelled() {
+                let _ = child.kill();
+            }
+            thread::sleep(POLL);
+        };
+        for reader in readers {
+            let _ = reader.join();
+        }
+        Ok(status)
+    }
+
+    fn output(&self, mut command: Command) -> io::Result<Output> {
+        command.output()
+    }
+}
+
+impl Files for System {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(File::open(path)?))
+    }
+
+    fn write(&self, path: &Path, text: &str) -> io::Result<()> {
+        fs::write(path, text)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        Ok(fs::read_dir(path)?.flatten().map(|e| e.path()).collect())
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::canonicalize(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        let meta = fs::metadata(path)?;
+        #[cfg(unix)]
+        let identity = std::os::unix::fs::MetadataExt::ino(&meta);
+        #[cfg(not(unix))]
+        let identity = 0;
+        Ok(Metadata {
+            is_dir: meta.is_dir(),
+            len: meta.len(),
+            modified: meta.modified().ok(),
+            identity,
+        })
+    }
+}
+
+#[cfg(feature = "http")]
+impl Http for System {
+    fn send(&self, request: Request) -> Result<Response, String> {
+        use reqwest::{blocking::Client, header::CONTENT_TYPE};
+
+        const TIMEOUT: Duration = Duration::from_secs(30);
+        let client = Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .map_err(|err| with_causes(&err))?;
+        let request = match request.body {
+            None => client.get(&request.url),
+            Some((body, content_type)) => client
+                .post(&request.url)
+                .header(CONTENT_TYPE, content_type)
+                .body(body),
+        };
+        let response = request.send().map_err(|err| with_causes(&err))?;
+        Ok(Response {
+            version: format!("{:?}", response.version()),
+            status: response.status().to_string(),
+            headers: response
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                    (name.to_string(), value)
+                })
+                .collect(),
+            length: response.content_length(),
+            body: Box::new(response),
+        })
+    }
+}
+
+// reqwest's errors put the useful part ("connection refused") in source()
+#[cfg(feature = "http")]
+fn with_causes(err: &dyn std::error::Error) -> String {
+    let mut text = err.to_string();
+    let mut cause = err.source();
+    while let Some(err) = cause {
+        text += &format!(": {err}");
+        cause = err.source();
+    }
+    text
+}
+
+fn print_lines(tx: &Sender<AppEvent>, output: impl Read) {
+    let mut reader = BufReader::new(output);
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line).is_ok_and(|n| n > 0) {
+        let text = String::from_utf8_lossy(&line);
+        let _ = tx.send(AppEvent::Print(format!(
+            "  {}",
+            text.trim_end_matches(['\r', '\n'])
+        )));
+        line.clear();
+    }
+}
+
+// Stands in for System in tests: every program prints the same lines and
+// exits 0 (or as told), files live in a map (a directory is any prefix of
+// their paths, so creating one does nothing), and every request gets the
+// same body back
+#[cfg(test)]
+#[derive(Default)]
+pub struct Fake {
+    prints: Vec<String>,
+    exit: i32,
+    #[cfg(feature = "http")]
+    body: String,
+    files: Mutex<BTreeMap<PathBuf, String>>,
+    calls: Mutex<Vec<String>>, // "run sh -c ls", "write notes.md", "GET https://…"
+}
+
+#[cfg(test)]
+impl Effects {
+    pub fn fake(fake: &Arc<Fake>) -> Self {
+        Self {
+            processes: fake.clone(),
+            files: fake.clone(),
+            #[cfg(feature = "http")]
+            http: fake.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Fake {
+    pub fn printing(mut self, lines: &[&str]) -> Self {
+        self.prints = lines.iter().map(|line| line.to_string()).collect();
+        self
+    }
+
+    pub fn exiting(mut self, code: i32) -> Self {
+        self.exit = code;
+        self
+    }
+
+    #[cfg(feature = "http")]
+    pub fn responding(mut self, body: &str) -> Self {
+        self.body = body.to_string();
+        self
+    }
+
+    pub fn with_file(self, path: impl AsRef<Path>, text: &str) -> Self {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(normal(path.as_ref()), text.to_string());
+        self
+    }
+
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<String> {
+        self.files
+            .lock()
+            .unwrap()
+            .get(&normal(path.as_ref()))
+            .cloned()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        let dir = normal(path);
+        let files = self.files.lock().unwrap();
+        files
+            .keys()
+            .any(|file| file != &dir && file.starts_with(&dir))
+    }
+
+    // What was asked of it, in order
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn call(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn ran(&self, command: &Command) -> ExitStatus {
+        let mut call = format!("run {}", command.get_program().to_string_lossy());
+        for arg in command.get_args() {
+            call += &format!(" {}", arg.to_string_lossy());
+        }
+        self.call(call);
+        #[cfg(unix)]
+        return std::os::unix::process::ExitStatusExt::from_raw(self.exit << 8);
+        #[cfg(windows)]
+        return std::os::windows::process::ExitStatusExt::from_raw(self.exit as u32);
+    }
+}
+
+#[cfg(test)]
+impl Processes for Fake {
+    fn run(
+        &self,
+        tx: &Sender<AppEvent>,
+        command: Command,
+        _cancelled: &dyn Fn() -> bool,
+    ) -> io::Result<ExitStatus> {
+        for line in &self.prints {
+            let _ = tx.send(AppEvent::Print(format!("  {line}")));
+        }
+        Ok(self.ran(&command))
+    }
+
+    fn output(&self, command: Command) -> io::Result<Output> {
+        Ok(Output {
+            status: self.ran(&command),
+            stdout: self.prints.join("\n").into_bytes(),
+            stderr: Vec::new(),
+        })
+    }
+}
+
+// "./src/../a.json" and "a.json" are the same key; "." is the empty path
+#[cfg(test)]
+fn normal(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for part in path.components() {
+        match part {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            part => out.push(part),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+impl Files for Fake {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        self.call(format!("read {}", path.display()));
+        self.file(path)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.read(path).map(String::into_bytes)
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read + Send>> {
+        let bytes = self.read_bytes(path)?;
+        Ok(Box::new(io::Cursor::new(bytes)))
+    }
+
+    fn write(&self, path: &Path, text: &str) -> io::Result<()> {
+        self.call(format!("write {}", path.display()));
+        self.files
+            .lock()
+            .unwrap()
+            .insert(normal(path), text.to_string());
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.call(format!("rename {} {}", from.display(), to.display()));
+        let mut files = self.files.lock().unwrap();
+        let text = (files.remove(&normal(from))).ok_or(io::ErrorKind::NotFound)?;
+        files.insert(normal(to), text);
+        Ok(())
+    }
+
+    fn create_dir_all(&self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.call(format!("list {}", path.display()));
+        if !self.is_dir(path) {
+            return Err(io::ErrorKind::NotFound.into());
+        }
+        let dir = normal(path);
+        let mut names: Vec<_> = (self.files.lock().unwrap().keys())
+            .filter_map(|file| file.strip_prefix(&dir).ok()?.components().next())
+            .map(|name| name.as_os_str().to_os_string())
+            .collect();
+        names.dedup(); // sorted already, as the map's keys are
+        Ok(names.into_iter().map(|name| path.join(name)).collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        let (is_dir, len) = match self.file(path) {
+            Some(text) => (false, text.len() as u64),
+            None if self.is_dir(path) => (true, 0),
+            None => return Err(io::ErrorKind::NotFound.into()),
+        };
+        Ok(Metadata {
+            is_dir,
+            len,
+            modified: None,
+            identity: 0,
+        })
+    }
+
+    // Paths are relative to the map: "./notes/../a.json" is "a.json"
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.metadata(path).map(|_| normal(path))
+    }
+}
+
+#[cfg(all(test, feature = "http"))]
+impl Http for Fake {
+    fn send(&self, request: Request) -> Result<Response, String> {
+        let method = if request.body.is_some() {
+            "POST"
+        } else {
+            "GET"
+        };
+        self.call(format!("{method} {}", request.url));
+        Ok(Response {
+            version: "HTTP/1.1".into(),
+            status: "200 OK".into(),
+            headers: vec![("content-type".into(), "text/plain".into())],
+            length: Some(self.body.len() as u64),
+            body: Box::new(io::Cursor::new(self.body.clone().into_bytes())),
+        })
+    }
+}
@@ -0,0 +1,81 @@
+// =============================================================================
+// Environment — `env`: the variables commands run from here get, in a table
+//
+//   > env                      sort and filter as in any table
+//   > env set RUST_LOG debug
+//   > env unset http_proxy
+//   > !cargo test              sees RUST_LOG=debug, and no http_proxy
+//
+// Changes apply to every process started through shell.rs — `!` commands,
+// `watch` rules, `ssh` — from then on, and last for this session. The app's
+// own environment is left alone (changing it isn't safe once threads are
+// running), so they're kept here and applied to each child as it starts.
+// =============================================================================
+
+use std::{collections::BTreeMap, env, process::Command, sync::RwLock};
+
+use crate::{
+    ctx::{Ctx, Status},
+    widgets::TableView,
+};
+
+// name → Some(value) set, None unset
+static CHANGES: RwLock<BTreeMap<String, Option<String>>> = RwLock::new(BTreeMap::new());
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: env [set <name> <value> | unset <name>]";
+    let (op, rest) = args.split_once(' ').unwrap_or((args, ""));
+    let (name, value) = rest.trim().split_once(' ').unwrap_or((rest.trim(), ""));
+    let valid = !name.is_empty() && !name.contains(['=', '\0']);
+    match op {
+        "" => {
+            let rows = vars().into_iter().map(|(name, value)| vec![name, value]);
+            ctx.show(TableView::new("Environment", ["Name", "Value"]).rows(rows));
+        }
+        "set" if valid && !value.is_empty() => {
+            let value = value.trim_start();
+            change(name, Some(value));
+            ctx.print(format!("  {name}={value} for commands run from here"));
+        }
+        "unset" if valid && value.is_empty() => {
+            change(name, None);
+            ctx.print(format!("  {name} unset for commands run from here"));
+        }
+        _ => return ctx.fail(usage),
+    }
+    Status::Success
+}
+
+fn change(name: &str, value: Option<&str>) {
+    let mut changes = CHANGES.write().unwrap_or_else(|e| e.into_inner());
+    changes.insert(name.to_string(), value.map(str::to_string));
+}
+
+// The environment a child started now would get, sorted
+pub fn vars() -> Vec<(String, String)> {
+    let mut vars: BTreeMap<String, String> = env::vars_os()
+        .map(|(name, value)| {
+            (
+                name.to_string_lossy().into_owned(),
+                value.to_string_lossy().into_owned(),
+            )
+        })
+        .collect();
+    for (name, value) in CHANGES.read().unwrap_or_else(|e| e.into_inner()).iter() {
+        match value {
+            Some(value) => vars.insert(name.clone(), value.clone()),
+            None => vars.remove(name),
+        };
+    }
+    vars.into_iter().collect()
+}
+
+// Give `command` this session's changes
+pub fn apply(command: &mut Command) {
+    for (name, value) in CHANGES.read().unwrap_or_else(|e| e.into_inner()).iter() {
+        match value {
+            Some(value) => command.env(name, value),
+            None => command.env_remove(name),
+        };
+    }
+}
@@ -0,0 +1,419 @@
+// =============================================================================
+// Feeds — `feed`: RSS and Atom entries in a table, and a reading view (the
+// `feeds` feature)
+//
+//   cargo run --features feeds
+//   > feed add https://blog.rust-lang.org/feed.xml
+//   > feed
+//
+// Subscriptions are kept in <data dir>/feeds, one URL per line. The pane
+// lists every feed's entries, newest first, fetched by a background job
+// when it opens and every REFRESH after that; sort and filter as in any
+// table (widgets/table.rs).
+//
+//   Enter        read the selected entry (Backspace goes back to the list)
+//   r            fetch the feeds again now
+//
+//   feed add <url>       checks it's a feed before keeping it
+//   feed remove [url]    no url: pick one from the list
+//   feed list
+//
+// Entries are shown as text: their HTML is reduced to paragraphs, and
+// links, images and styling are dropped. The entry's own link is at the top.
+// =============================================================================
+
+use std::{
+    io::{self, ErrorKind},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use feed_rs::model::Feed;
+use ratatui::{Frame, layout::Rect};
+use reqwest::blocking::Client;
+
+use crate::{
+    ctx::{AppEvent, Ctx, Status},
+    effects::Files,
+    hints::Hints,
+    pane::Pane,
+    paths,
+    theme::Theme,
+    widgets::{MarkdownView, Select, TableView},
+};
+
+const REFRESH: Duration = Duration::from_secs(15 * 60);
+const POLL: Duration = Duration::from_millis(250); // how often the job checks for closing / `r`
+const TIMEOUT: Duration = Duration::from_secs(30);
+const HEADERS: [&str; 3] = ["Title", "Feed", "Published"];
+const PUBLISHED: usize = 2; // newest first, to start with
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let (op, url) = args.split_once(' ').unwrap_or((args, ""));
+    let url = url.trim().to_string();
+    match (op, url.is_empty()) {
+        ("", _) => show(ctx),
+        ("list", true) => {
+            let urls = load(&*ctx.effects.files);
+            if urls.is_empty() {
+                ctx.print("  No feeds yet — `feed add <url>`");
+            }
+            for url in urls {
+                ctx.print(format!("  {url}"));
+            }
+            Status::Success
+        }
+        ("add", false) => {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return ctx.fail(format!("  Not an http(s) URL: {url}"));
+            }
+            if load(&*ctx.effects.files).contains(&url) {
+                return ctx.fail(format!("  Already following {url}"));
+            }
+            // Fetched first, so a typo'd URL isn't kept
+            let files = ctx.effects.files.clone();
+            ctx.spawner.spawn(move |tx| {
+                let line = match fetch(&url) {
+                    Ok(feed) => match edit(&*files, |urls| urls.push(url.clone())) {
+                        Ok(()) => {
+                            let entries = match feed.entries.len() {
+                                1 => "1 entry".to_string(),
+                                n => format!("{n} entries"),
+                            };
+                            format!("  Following {} ({entries})", title(&feed, &url))
+                        }
+                        Err(err) => format!("  Can't save the feed list: {err}"),
+                    },
+                    Err(why) => format!("  {url}: {why}"),
+                };
+                let _ = tx.send(AppEvent::Print(line));
+            });
+            Status::Success
+        }
+        ("remove", true) => {
+            let urls = load(&*ctx.effects.files);
+            if urls.is_empty() {
+                return ctx.fail("  No feeds to remove");
+            }
+            ctx.open(Select::new("Remove feed", urls), |ctx, url| {
+                remove(ctx, &url);
+            });
+            Status::Success
+        }
+        ("remove", false) => remove(ctx, &url),
+        _ => ctx.fail("  Usage: feed [add <url> | remove [url] | list]"),
+    }
+}
+
+fn remove(ctx: &mut Ctx, url: &str) -> Status {
+    let files = &*ctx.effects.files;
+    if !load(files).iter().any(|u| u == url) {
+        return ctx.fail(format!("  Not following {url}"));
+    }
+    match edit(files, |urls| urls.retain(|u| u != url)) {
+        Ok(()) => {
+            ctx.print(format!("  Stopped following {url}"));
+            Status::Success
+        }
+        Err(err) => ctx.fail(format!("  Can't save the feed list: {err}")),
+    }
+}
+
+fn list_path() -> Option<PathBuf> {
+    Some(paths::data_dir()?.join("feeds"))
+}
+
+fn load(files: &dyn Files) -> Vec<String> {
+    list_path()
+        .and_then(|path| files.read(&path).ok())
+        .map_or(Vec::new(), |text| {
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
+        })
+}
+
+// Change the list of feeds and write it back
+fn edit(files: &dyn Files, change: impl FnOnce(&mut Vec<String>)) -> io::Result<()> {
+    let path = list_path().ok_or(io::Error::new(ErrorKind::NotFound, "no data directory"))?;
+    let mut urls = load(files);
+    change(&mut urls);
+    if let Some(dir) = path.parent() {
+        files.create_dir_all(dir)?;
+    }
+    let text: String = urls.iter().map(|url| format!("{url}\n")).collect();
+    files.write(&path, &text)
+}
+
+fn fetch(url: &str) -> Result<Feed, String> {
+    let response = Client::builder()
+        .timeout(TIMEOUT)
+        .build()
+        .and_then(|client| client.get(url).send())
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| err.to_string())?;
+    let bytes = response.bytes().map_err(|err| err.to_string())?;
+    feed_rs::parser::parse(bytes.as_ref()).map_err(|err| format!("not a feed: {err}"))
+}
+
+fn title(feed: &Feed, url: &str) -> String {
+    feed.title
+        .as_ref()
+        .map_or(url.to_string(), |t| t.content.trim().to_string())
+}
+
+struct Entry {
+    row: Vec<String>, // as in the table, to find it again
+    link: Option<String>,
+    text: String, // the content, else the summary, as plain text
+}
+
+#[derive(Default)]
+struct State {
+    entries: Option<Vec<Entry>>, // None until the first read, and once taken
+    errors: Vec<String>,         // feeds that couldn't be read last time
+    changed: bool,               // since the pane took them
+    refresh: bool,               // `r`: don't wait for REFRESH
+    closed: bool,                // the pane is gone
+}
+
+type Shared = Arc<Mutex<State>>;
+
+fn show(ctx: &mut Ctx) -> Status {
+    let urls = load(&*ctx.effects.files);
+    if urls.is_empty() {
+        return ctx.fail("  No feeds yet — `feed add <url>`");
+    }
+    let state = Shared::default();
+    let job_state = state.clone();
+    let mut table = TableView::new("Feeds", HEADERS);
+    table.sort_by(PUBLISHED, true);
+    ctx.show(FeedView {
+        state,
+        table,
+        entries: Vec::new(),
+        errors: Vec::new(),
+        reading: None,
+    });
+    ctx.spawner.spawn_watcher(move |_| watch(&job_state, &urls));
+    Status::Success
+}
+
+// Re-read the feeds every REFRESH (or on `r`), until the pane closes
+fn watch(state: &Shared, urls: &[String]) {
+    loop {
+        let mut entries = Vec::new();
+        let mut errors = Vec::new();
+        for url in urls {
+            match fetch(url) {
+                Ok(feed) => entries.extend(entries_of(&feed, url)),
+                Err(why) => errors.push(format!("{url}: {why}")),
+            }
+        }
+        match state.lock() {
+            Ok(mut state) if !state.closed => {
+                state.entries = Some(entries);
+                state.errors = errors;
+                state.changed = true;
+                state.refresh = false;
+            }
+            _ => return,
+        }
+        for _ in 0..REFRESH.as_millis() / POLL.as_millis() {
+            thread::sleep(POLL);
+            match state.lock() {
+                Ok(state) if state.closed => return,
+                Ok(state) if state.refresh => break,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn entries_of(feed: &Feed, url: &str) -> Vec<Entry> {
+    let name = title(feed, url);
+    feed.entries
+        .iter()
+        .map(|entry| {
+            let published = entry.published.or(entry.updated);
+            let html = entry
+                .content
+                .as_ref()
+                .and_then(|c| c.body.clone())
+                .or_else(|| entry.summary.as_ref().map(|s| s.content.clone()))
+                .unwrap_or_default();
+            Entry {
+                row: vec![
+                    entry
+                        .title
+                        .as_ref()
+                        .map_or(String::new(), |t| t.content.trim().to_string()),
+                    name.clone(),
+                    published.map_or(String::new(), |at| at.format("%Y-%m-%d %H:%M").to_string()),
+                ],
+                link: entry.links.first().map(|l| l.href.clone()),
+                text: plain_text(&html),
+            }
+        })
+        .collect()
+}
+
+// Just enough of HTML to read: block tags become paragraph breaks, the
+// other tags go, and the common entities are decoded
+fn plain_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim_start_matches('/');
+        let name = tag.split([' ', '/']).next().unwrap_or_default();
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "p" | "div" | "br" | "li" | "h1" | "h2" | "h3" | "h4" | "blockquote" | "pre" => {
+                text.push_str("\n\n")
+            }
+            _ => {}
+        }
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+    // One blank line between paragraphs, however many tags made them
+    let paragraphs: Vec<String> = text
+        .split("\n\n")
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|p| !p.is_empty())
+        .collect();
+    paragraphs.join("\n\n")
+}
+
+pub struct FeedView {
+    state: Shared,
+    table: TableView,
+    entries: Vec<Entry>,
+    errors: Vec<String>,           // as of the last read
+    reading: Option<MarkdownView>, // an entry, over the list
+}
+
+impl FeedView {
+    fn read_selected(&mut self) {
+        let Some(row) = self.table.selected_row() else {
+            return;
+        };
+        let Some(entry) = self.entries.iter().find(|e| e.row == row) else {
+            return;
+        };
+        let [title, feed, published] = [&entry.row[0], &entry.row[1], &entry.row[2]];
+        let mut markdown = format!("# {title}\n\n*{feed} — {published}*\n\n");
+        if let Some(link) = &entry.link {
+            markdown += &format!("{}\n\n", escape(link));
+        }
+        // The entry's text isn't markdown; keep it from being read as such
+        for paragraph in entry.text.split("\n\n") {
+            markdown += &format!("{}\n\n", escape(paragraph));
+        }
+        self.reading = Some(MarkdownView::text(title.clone(), markdown));
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .flat_map(|c| match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '#' | '<' | '>' | '|' => vec!['\\', c],
+            c => vec![c],
+        })
+        .collect()
+}
+
+impl Drop for FeedView {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+    }
+}
+
+impl Pane for FeedView {
+    fn title(&self) -> String {
+        if let Some(reading) = &self.reading {
+            return reading.title();
+        }
+        match self.errors.len() {
+            0 => self.table.title(),
+            1 => format!("{} — {}", self.table.title(), self.errors[0]),
+            n => format!("{} — {n} feeds failed", self.table.title()),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        match &self.reading {
+            Some(reading) => reading.render(frame, area, theme),
+            None => self.table.render(frame, area, theme),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if let Some(reading) = &mut self.reading {
+            if key.code == KeyCode::Backspace {
+                self.reading = None;
+                return true;
+            }
+            return reading.handle_key(key);
+        }
+        if self.table.typing() {
+            return self.table.handle_key(key);
+        }
+        match key.code {
+            KeyCode::Enter => self.read_selected(),
+            KeyCode::Char('r') => {
+                if let Ok(mut state) = self.state.lock() {
+                    state.refresh = true;
+                }
+            }
+            _ => return self.table.handle_key(key),
+        }
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+        if !state.changed {
+            return false;
+        }
+        state.changed = false;
+        if let Some(entries) = state.entries.take() {
+            self.table.set_rows(entries.iter().map(|e| e.row.clone()));
+            self.entries = entries;
+        }
+        self.errors = std::mem::take(&mut state.errors);
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        match self.reading {
+            Some(_) => &[("↑↓", "scroll"), ("Backspace", "back")],
+            None => &[
+                ("↑↓", "select"),
+                ("Enter", "read"),
+                ("r", "refresh"),
+                ("/", "filter"),
+            ],
+        }
+    }
+}
@@ -0,0 +1,388 @@
+// =============================================================================
+// Git — `git`: the working directory's repository at a glance (the `git`
+// feature)
+//
+//   cargo run --features git
+//   > git
+//
+// A pane (in the side panel, if the layout has one) with the branch and how
+// far it is ahead of / behind its upstream, the changed files — staged part
+// green, unstaged red, like `git status -s` — and the last few commits.
+//
+// It shells out to the `git` on PATH, so there's nothing to link. A
+// background job keeps it current: it re-reads the status DEBOUNCE after
+// files in the work tree (or the branch, or the index) change, and every
+// REFRESH regardless. Closing the pane stops the job.
+//
+//   ↑/↓ PgUp/PgDn Home/End   scroll
+//   r                        refresh now
+// =============================================================================
+
+use std::{
+    cell::Cell,
+    path::{Component, Path, PathBuf},
+    process::Command,
+    sync::{Arc, Mutex, mpsc},
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    ctx::{Ctx, Status},
+    effects::Processes,
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+};
+
+const REFRESH: Duration = Duration::from_secs(10);
+const DEBOUNCE: Duration = Duration::from_millis(200);
+const POLL: Duration = Duration::from_millis(100); // how often the job checks for `r` and closing
+const COMMITS: usize = 10;
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    if !args.is_empty() {
+        return ctx.fail("  Usage: git (shows this repository's status in a pane)");
+    }
+    let processes = ctx.effects.processes.clone();
+    let root = match git(
+        &*processes,
+        Path::new("."),
+        &["rev-parse", "--show-toplevel"],
+    ) {
+        Ok(root) => PathBuf::from(root.trim()),
+        Err(why) => return ctx.fail(format!("  Not in a git repository: {why}")),
+    };
+    let name = root.file_name().map_or(root.display().to_string(), |n| {
+        n.to_string_lossy().into_owned()
+    });
+    let state = Shared::default();
+    ctx.show(GitView {
+        name,
+        state: state.clone(),
+        top: 0,
+        count: Cell::new(0),
+        height: Cell::new(1),
+    });
+    ctx.spawner
+        .spawn_watcher(move |_| run(&*processes, &root, &state));
+    Status::Success
+}
+
+#[derive(Default)]
+struct Snapshot {
+    branch: String,           // git says "(detached)" when there isn't one
+    upstream: Option<String>, // e.g. "origin/main"
+    ahead: u32,
+    behind: u32,
+    files: Vec<(String, String)>, // ("XY", path), as in `git status -s`
+    commits: Vec<(String, String, String)>, // (short hash, age, subject)
+}
+
+#[derive(Default)]
+struct State {
+    snapshot: Option<Result<Snapshot, String>>, // None until the first read
+    refresh: bool,                              // `r` was pressed
+    changed: bool,                              // since the last render
+    closed: bool,                               // the pane is gone
+}
+
+type Shared = Arc<Mutex<State>>;
+
+// Re-read the repository whenever it's due, until the pane closes
+fn run(processes: &dyn Processes, root: &Path, state: &Shared) {
+    let (events, changes) = mpsc::channel();
+    // Without a watcher (too many files, say) the timer still refreshes
+    let _watcher = notify::recommended_watcher(events)
+        .and_then(|mut w| w.watch(root, RecursiveMode::Recursive).map(|()| w))
+        .ok();
+    let mut due = Instant::now();
+    loop {
+        let now = Instant::now();
+        let refresh = {
+            let Ok(mut state) = state.lock() else {
+                return;
+            };
+            if state.closed {
+                return;
+            }
+            std::mem::take(&mut state.refresh)
+        };
+        if refresh || now >= due {
+            let snapshot = read(processes, root);
+            let Ok(mut state) = state.lock() else {
+                return;
+            };
+            state.snapshot = Some(snapshot);
+            state.changed = true;
+            due = Instant::now() + REFRESH;
+        }
+        if let Ok(Ok(event)) = changes.recv_timeout(POLL)
+            && relevant(&event, root)
+        {
+            due = due.min(Instant::now() + DEBOUNCE);
+        }
+    }
+}
+
+// Work tree changes, and the parts of .git that change what's shown
+fn relevant(event: &Event, root: &Path) -> bool {
+    if matches!(event.kind, EventKind::Access(_) | EventKind::Other) {
+        return false;
+    }
+    event.paths.iter().any(|path| {
+        let mut parts = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .map(Component::as_os_str);
+        match parts.next().and_then(|p| p.to_str()) {
+            Some("target") => false,
+            Some(".git") => matches!(
+                parts.next().and_then(|p| p.to_str()),
+                Some("HEAD" | "index" | "refs")
+            ),
+            _ => true,
+        }
+    })
+}
+
+fn git(processes: &dyn Processes, dir: &Path, args: &[&str]) -> Result<String, String> {
+    let mut git = Command::new("git");
+    git.arg("-C").arg(dir).args(args);
+    let output = processes
+        .output(git)
+        .map_err(|err| format!("can't run git: {err}"))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let why = stderr.lines().next().unwrap_or("git failed");
+        Err(why.trim_start_matches("fatal: ").to_string())
+    }
+}
+
+fn read(processes: &dyn Processes, root: &Path) -> Result<Snapshot, String> {
+    // --no-optional-locks: don't rewrite the index, which would wake the
+    // watcher and refresh again
+    let status = git(
+        processes,
+        root,
+        &[
+            "--no-optional-locks",
+            "status",
+            "--porcelain=v2",
+            "--branch",
+        ],
+    )?;
+    let mut snapshot = Snapshot::default();
+    for line in status.lines() {
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match kind {
+            "#" => match rest.split_once(' ') {
+                Some(("branch.head", head)) => snapshot.branch = head.to_string(),
+                Some(("branch.upstream", upstream)) => {
+                    snapshot.upstream = Some(upstream.to_string());
+                }
+                Some(("branch.ab", counts)) => {
+                    for count in counts.split(' ') {
+                        if let Some(n) = count.strip_prefix('+') {
+                            snapshot.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = count.strip_prefix('-') {
+                            snapshot.behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            // Changed, renamed and unmerged entries: the path is the last
+            // field (a rename's is "new\told")
+            "1" | "2" | "u" => {
+                let fields = match kind {
+                    "1" => 8,
+                    "2" => 9,
+                    _ => 10,
+                };
+                let mut parts = rest.splitn(fields, ' ');
+                let xy = parts.next().unwrap_or("..").replace('.', " ");
+                let path = parts.last().unwrap_or_default();
+                let path = path.split('\t').next().unwrap_or(path);
+                snapshot.files.push((xy, path.to_string()));
+            }
+            "?" => snapshot.files.push(("??".to_string(), rest.to_string())),
+            _ => {}
+        }
+    }
+
+    // A repository with no commits yet has no log
+    let count = format!("-{COMMITS}");
+    if let Ok(log) = git(
+        processes,
+        root,
+        &["log", &count, "--format=%h%x09%cr%x09%s"],
+    ) {
+        snapshot.commits = log
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                Some((
+                    parts.next()?.to_string(),
+                    parts.next()?.to_string(),
+                    parts.next().unwrap_or_default().to_string(),
+                ))
+            })
+            .collect();
+    }
+    Ok(snapshot)
+}
+
+pub struct GitView {
+    name: String, // the repository's directory
+    state: Shared,
+    top: usize,          // first line on screen
+    count: Cell<usize>,  // lines, as of the last render
+    height: Cell<usize>, // rows that fit, as of the last render
+}
+
+impl GitView {
+    fn scroll_by(&mut self, step: isize) {
+        let last = self.count.get().saturating_sub(self.height.get());
+        self.top = self.top.saturating_add_signed(step).min(last);
+    }
+}
+
+impl Drop for GitView {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+    }
+}
+
+fn lines(snapshot: &Snapshot, theme: &Theme) -> Vec<Line<'static>> {
+    let dim = Style::default().add_modifier(Modifier::DIM);
+    let heading = Style::default()
+        .fg(theme.input_border)
+        .add_modifier(Modifier::BOLD);
+    let staged = Style::default().fg(Color::Green);
+    let unstaged = Style::default().fg(Color::Red);
+
+    let mut branch = vec![Span::styled(snapshot.branch.clone(), heading)];
+    if let Some(upstream) = &snapshot.upstream {
+        branch.push(Span::styled(format!(" → {upstream}"), dim));
+        if snapshot.ahead > 0 {
+            branch.push(Span::raw(format!(" ↑{}", snapshot.ahead)));
+        }
+        if snapshot.behind > 0 {
+            branch.push(Span::raw(format!(" ↓{}", snapshot.behind)));
+        }
+    }
+    let mut lines = vec![Line::from(branch), Line::default()];
+
+    if snapshot.files.is_empty() {
+        lines.push(Line::styled("Nothing to commit, working tree clean", dim));
+    } else {
+        lines.push(Line::styled(
+            format!("Changes ({})", snapshot.files.len()),
+            heading,
+        ));
+        for (xy, path) in &snapshot.files {
+            let (x, y) = xy.split_at(1);
+            let (x_style, y_style) = match xy.as_str() {
+                "??" => (dim, dim),
+                _ if xy.contains('U') => (unstaged, unstaged), // conflicted
+                _ => (staged, unstaged),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(x.to_string(), x_style),
+                Span::styled(y.to_string(), y_style),
+                Span::raw(format!(" {path}")),
+            ]));
+        }
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Recent commits", heading));
+    if snapshot.commits.is_empty() {
+        lines.push(Line::styled("No commits yet", dim));
+    }
+    for (hash, age, subject) in &snapshot.commits {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{hash} "), Style::default().fg(Color::Yellow)),
+            Span::raw(subject.clone()),
+            Span::styled(format!(" ({age})"), dim),
+        ]));
+    }
+    lines
+}
+
+impl Pane for GitView {
+    fn title(&self) -> String {
+        let Ok(state) = self.state.lock() else {
+            return format!("Git: {}", self.name);
+        };
+        match &state.snapshot {
+            Some(Ok(snapshot)) => format!(
+                "Git: {} on {} ({} changed)",
+                self.name,
+                snapshot.branch,
+                snapshot.files.len()
+            ),
+            Some(Err(_)) => format!("Git: {} (status failed)", self.name),
+            None => format!("Git: {} (reading…)", self.name),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.changed = false;
+        self.height.set(area.height as usize);
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let lines = match &state.snapshot {
+            Some(Ok(snapshot)) => lines(snapshot, theme),
+            Some(Err(why)) => vec![Line::styled(format!("git status failed: {why}"), dim)],
+            None => vec![Line::styled("Reading the repository…", dim)],
+        };
+        self.count.set(lines.len());
+        let text = Paragraph::new(lines).scroll((self.top as u16, 0));
+        frame.render_widget(text, area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get().max(1) as isize;
+        match key.code {
+            KeyCode::Up => self.scroll_by(-1),
+            KeyCode::Down => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home => self.top = 0,
+            KeyCode::End => self.scroll_by(isize::MAX),
+            KeyCode::Char('r') => {
+                if let Ok(mut state) = self.state.lock() {
+                    state.refresh = true;
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.state.lock().is_ok_and(|s| s.changed)
+    }
+
+    fn hints(&self) -> Hints {
+        &[("↑↓", "scroll"), ("r", "refresh")]
+    }
+}
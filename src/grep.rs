@@ -0,0 +1,282 @@
+// =============================================================================
+// Grep — `grep [-i] <pattern> <path>…`: search files for a regex
+//
+//   > grep -i "todo|fixme" src/
+//
+// Directories are searched recursively, skipping hidden ones and target/;
+// files that look binary (a NUL byte early on) are skipped too. The search
+// runs in the background, then the matches are listed in a pane as
+// file:line with the matching text highlighted:
+//
+//   ↑/↓ PgUp/PgDn Home/End   move
+//   Enter                    open the match — runs `open_command` from the
+//                            config ("edit +{line} {path}" unless changed)
+//
+// The pattern is the regex crate's syntax. At most MAX_MATCHES are listed.
+// =============================================================================
+
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    sync::mpsc::Sender,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState},
+};
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    ctx::{AppEvent, Ctx, Status},
+    effects::Files,
+    hints::Hints,
+    pane::{Loading, Pane},
+    theme::Theme,
+};
+
+const MAX_MATCHES: usize = 10_000;
+const SKIPPED_DIRS: [&str; 1] = ["target"]; // besides hidden ones
+const BINARY_SNIFF: usize = 8 * 1024; // bytes checked for a NUL
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: grep [-i] <pattern> <path>…";
+    let (ignore_case, args) = match args.strip_prefix("-i ") {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, args),
+    };
+    let (pattern, paths) = match split_pattern(args) {
+        Some((pattern, paths)) if !paths.is_empty() => (pattern, paths),
+        _ => return ctx.fail(usage),
+    };
+    let regex = match RegexBuilder::new(&pattern)
+        .case_insensitive(ignore_case)
+        .build()
+    {
+        Ok(regex) => regex,
+        Err(err) => {
+            // The last line of regex's multi-line message says what's wrong
+            let text = err.to_string();
+            let why = text.lines().last().unwrap_or_default();
+            return ctx.fail(format!(
+                "  Bad pattern: {}",
+                why.trim_start_matches("error: ")
+            ));
+        }
+    };
+    let files = ctx.effects.files.clone();
+    if let Some(missing) = paths.iter().find(|p| files.metadata(Path::new(p)).is_err()) {
+        return ctx.fail(format!(
+            "  Can't search {missing}: no such file or directory"
+        ));
+    }
+
+    let title = format!("Grep: {pattern}");
+    let (loading, slot) = Loading::new(title.clone());
+    let open_command = ctx.config.open_command.clone();
+    let tx = ctx.sender();
+    ctx.show(loading);
+    ctx.spawner.spawn(move |_| {
+        let mut matches = Vec::new();
+        let mut searched = 0;
+        for path in &paths {
+            search(
+                &*files,
+                &regex,
+                Path::new(path),
+                &mut matches,
+                &mut searched,
+            );
+        }
+        let view = GrepView {
+            title,
+            matches,
+            files: searched,
+            selected: 0,
+            offset: Cell::new(0),
+            height: Cell::new(1),
+            open_command,
+            tx,
+        };
+        slot.ready(view);
+    });
+    Status::Success
+}
+
+// The pattern may be quoted to hold spaces: grep "fn main" src/
+fn split_pattern(args: &str) -> Option<(String, Vec<String>)> {
+    let (pattern, rest) = match args.strip_prefix('"') {
+        Some(quoted) => quoted.split_once('"')?,
+        None => args.split_once(' ').unwrap_or((args, "")),
+    };
+    let paths = rest.split_whitespace().map(String::from).collect();
+    Some((pattern.to_string(), paths))
+}
+
+struct Match {
+    path: PathBuf,
+    line: usize,                // 1-based
+    text: String,               // the whole line
+    spans: Vec<(usize, usize)>, // byte ranges in `text` that matched
+}
+
+fn search(fs: &dyn Files, regex: &Regex, path: &Path, matches: &mut Vec<Match>, files: &mut usize) {
+    if matches.len() >= MAX_MATCHES {
+        return;
+    }
+    let is_dir = |path: &Path| fs.metadata(path).is_ok_and(|meta| meta.is_dir);
+    if is_dir(path) {
+        let Ok(mut entries) = fs.read_dir(path) else {
+            return;
+        };
+        entries.sort();
+        for entry in entries {
+            let name = entry.file_name().map(|n| n.to_string_lossy().into_owned());
+            let skipped = name.is_some_and(|name| {
+                (name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_str())) && is_dir(&entry)
+            });
+            if !skipped {
+                search(fs, regex, &entry, matches, files);
+            }
+        }
+        return;
+    }
+
+    let Ok(bytes) = fs.read_bytes(path) else {
+        return;
+    };
+    if bytes[..bytes.len().min(BINARY_SNIFF)].contains(&0) {
+        return;
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    let before = matches.len();
+    for (i, line) in text.lines().enumerate() {
+        let spans: Vec<(usize, usize)> = regex
+            .find_iter(line)
+            .map(|m| (m.start(), m.end()))
+            .filter(|(start, end)| start < end)
+            .collect();
+        if spans.is_empty() {
+            continue;
+        }
+        matches.push(Match {
+            path: path.to_path_buf(),
+            line: i + 1,
+            text: line.to_string(),
+            spans,
+        });
+        if matches.len() >= MAX_MATCHES {
+            break;
+        }
+    }
+    if matches.len() > before {
+        *files += 1;
+    }
+}
+
+pub struct GrepView {
+    title: String,
+    matches: Vec<Match>,
+    files: usize, // with at least one match
+    selected: usize,
+    offset: Cell<usize>, // first visible row; render() keeps the selection in view
+    height: Cell<usize>, // rows that fit, as of the last render
+    open_command: String,
+    tx: Sender<AppEvent>,
+}
+
+impl GrepView {
+    fn move_by(&mut self, step: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(step).min(last);
+    }
+
+    fn open_selected(&self) {
+        let Some(m) = self.matches.get(self.selected) else {
+            return;
+        };
+        let command = self
+            .open_command
+            .replace("{path}", &m.path.display().to_string())
+            .replace("{line}", &m.line.to_string());
+        let _ = self.tx.send(AppEvent::Run(command));
+    }
+}
+
+impl Pane for GrepView {
+    fn title(&self) -> String {
+        let capped = if self.matches.len() >= MAX_MATCHES {
+            "+"
+        } else {
+            ""
+        };
+        format!(
+            "{} ({}{capped} matches in {} files)",
+            self.title,
+            self.matches.len(),
+            self.files
+        )
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if self.matches.is_empty() {
+            let dim = Style::default().add_modifier(Modifier::DIM);
+            frame.render_widget(Line::styled("  No matches", dim), area);
+            return;
+        }
+        self.height.set(area.height as usize);
+        let path_style = Style::default().fg(theme.input_border);
+        let number_style = Style::default().add_modifier(Modifier::DIM);
+        let hit_style = Style::default()
+            .fg(theme.input_text)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|m| {
+                let mut spans = vec![
+                    Span::styled(m.path.display().to_string(), path_style),
+                    Span::styled(format!(":{}: ", m.line), number_style),
+                ];
+                let mut at = 0;
+                for &(start, end) in &m.spans {
+                    spans.push(Span::raw(m.text[at..start].to_string()));
+                    spans.push(Span::styled(m.text[start..end].to_string(), hit_style));
+                    at = end;
+                }
+                spans.push(Span::raw(m.text[at..].to_string()));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default()
+            .with_offset(self.offset.get())
+            .with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+        self.offset.set(state.offset());
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get().max(1) as isize;
+        match key.code {
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Down => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-page),
+            KeyCode::PageDown => self.move_by(page),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.move_by(isize::MAX),
+            KeyCode::Enter => self.open_selected(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[("↑↓", "move"), ("Enter", "open")]
+    }
+}
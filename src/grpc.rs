@@ -0,0 +1,311 @@
+// =============================================================================
+// gRPC — `grpc call`: call any method as JSON, like grpcurl (the `grpc`
+// feature)
+//
+//   cargo run --features grpc
+//   > grpc list localhost:50051
+//   > grpc list localhost:50051 helloworld.Greeter
+//   > grpc call localhost:50051 helloworld.Greeter/SayHello {"name": "ada"}
+//
+// No .proto files needed: the server's reflection service (v1) describes
+// its methods, and the request is built from the JSON — the rest of the
+// line, quoted or not; `{}` if left out — using protobuf's JSON mapping.
+// The response comes back the same way, pretty-printed into Output; a
+// server-streaming method prints each message as it arrives. Calls are
+// jobs, so `cancel` gives up on one. Plain-text connections only (no TLS).
+//
+//   grpc list <host:port> [service]    services, or a service's methods
+// =============================================================================
+
+use std::{error::Error, future::Future, str::FromStr, sync::mpsc::Sender, time::Duration};
+
+use futures_util::{StreamExt, stream};
+use prost::{Message, bytes::Buf};
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor,
+    prost_types::FileDescriptorProto,
+};
+use tonic::{
+    Request, Status as GrpcStatus,
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    codegen::http::uri::PathAndQuery,
+    transport::{Channel, Endpoint},
+};
+use tonic_reflection::pb::v1::{
+    ServerReflectionRequest, server_reflection_client::ServerReflectionClient,
+    server_reflection_request::MessageRequest, server_reflection_response::MessageResponse,
+};
+
+use crate::ctx::{AppEvent, Ctx, ProgressHandle, Status};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const POLL: Duration = Duration::from_millis(250); // how often calls check for `cancel`
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: grpc list <host:port> [service] | \
+                 grpc call <host:port> <package.Service/Method> [json]";
+    let words: Vec<&str> = args.splitn(4, ' ').map(str::trim).collect();
+    match words[..] {
+        ["list", address] | ["list", address, _] => {
+            let (address, service) = (address.to_string(), words.get(2).map(|s| s.to_string()));
+            ctx.spawner.spawn(move |tx| {
+                block_on(&tx, list(&tx, &address, service.as_deref()));
+            });
+            Status::Success
+        }
+        ["call", address, method] | ["call", address, method, _] => {
+            let Some((service, name)) = method.rsplit_once('/') else {
+                return ctx.fail(format!("  {method} isn't package.Service/Method"));
+            };
+            let json = words.get(3).copied().unwrap_or("{}");
+            let json = json
+                .strip_prefix('\'')
+                .and_then(|j| j.strip_suffix('\''))
+                .unwrap_or(json)
+                .to_string();
+            let (address, service, name) =
+                (address.to_string(), service.to_string(), name.to_string());
+            ctx.spawner
+                .spawn_with_progress(format!("grpc {method}"), move |tx, progress| {
+                    progress.unknown();
+                    let call = call(&tx, &progress, &address, &service, &name, &json);
+                    block_on(&tx, call);
+                });
+            Status::Success
+        }
+        _ => ctx.fail(usage),
+    }
+}
+
+fn print(tx: &Sender<AppEvent>, line: String) {
+    let _ = tx.send(AppEvent::Print(line));
+}
+
+// tonic is async; each job runs its own small runtime
+fn block_on(tx: &Sender<AppEvent>, job: impl Future<Output = Result<(), String>>) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build();
+    let result = match runtime {
+        Ok(runtime) => runtime.block_on(job),
+        Err(err) => Err(format!("Can't start the gRPC client: {err}")),
+    };
+    if let Err(why) = result {
+        print(tx, format!("  {why}"));
+    }
+}
+
+async fn connect(address: &str) -> Result<Channel, String> {
+    let endpoint = Endpoint::from_shared(format!("http://{address}"))
+        .map_err(|err| format!("{address} isn't an address: {err}"))?
+        .timeout(TIMEOUT);
+    endpoint
+        .connect()
+        .await
+        .map_err(|err| format!("Can't connect to {address}: {}", with_causes(&err)))
+}
+
+// tonic's errors say little until their causes are added, as in http.rs
+fn with_causes(err: &dyn Error) -> String {
+    let mut text = err.to_string();
+    let mut cause = err.source();
+    while let Some(err) = cause {
+        text += &format!(": {err}");
+        cause = err.source();
+    }
+    text
+}
+
+async fn list(tx: &Sender<AppEvent>, address: &str, service: Option<&str>) -> Result<(), String> {
+    let channel = connect(address).await?;
+    let Some(service) = service else {
+        let names = match reflect(&channel, MessageRequest::ListServices(String::new())).await? {
+            MessageResponse::ListServicesResponse(list) => list.service,
+            _ => Vec::new(),
+        };
+        for service in names {
+            print(tx, format!("  {}", service.name));
+        }
+        return Ok(());
+    };
+    let pool = descriptors(&channel, service).await?;
+    let service = pool
+        .get_service_by_name(service)
+        .ok_or(format!("{address} has no service {service}"))?;
+    for method in service.methods() {
+        let streams = match (method.is_client_streaming(), method.is_server_streaming()) {
+            (false, false) => "",
+            (false, true) => " (server streaming)",
+            (true, false) => " (client streaming)",
+            (true, true) => " (bidirectional)",
+        };
+        print(
+            tx,
+            format!(
+                "  {}({}) → {}{streams}",
+                method.name(),
+                method.input().full_name(),
+                method.output().full_name()
+            ),
+        );
+    }
+    Ok(())
+}
+
+async fn call(
+    tx: &Sender<AppEvent>,
+    progress: &ProgressHandle,
+    address: &str,
+    service: &str,
+    name: &str,
+    json: &str,
+) -> Result<(), String> {
+    let channel = connect(address).await?;
+    let pool = descriptors(&channel, service).await?;
+    let method: MethodDescriptor = pool
+        .get_service_by_name(service)
+        .and_then(|s| s.methods().find(|m| m.name() == name))
+        .ok_or(format!("{address} has no method {service}/{name}"))?;
+    if method.is_client_streaming() {
+        return Err(format!(
+            "{service}/{name} takes a stream; only unary and server-streaming calls work"
+        ));
+    }
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let request = DynamicMessage::deserialize(method.input(), &mut deserializer)
+        .map_err(|err| format!("Not a {}: {err}", method.input().full_name()))?;
+
+    let path = PathAndQuery::from_str(&format!("/{service}/{name}"))
+        .map_err(|err| format!("{service}/{name}: {err}"))?;
+    let codec = DynamicCodec(method.output());
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready()
+        .await
+        .map_err(|err| format!("Can't reach {address}: {err}"))?;
+    let failed = |status: GrpcStatus| format!("{:?}: {}", status.code(), status.message());
+    if !method.is_server_streaming() {
+        let response = grpc
+            .unary(Request::new(request), path, codec)
+            .await
+            .map_err(failed)?;
+        return print_message(tx, response.get_ref());
+    }
+    let mut stream = grpc
+        .server_streaming(Request::new(request), path, codec)
+        .await
+        .map_err(failed)?
+        .into_inner();
+    while !progress.cancelled() {
+        let Ok(next) = tokio::time::timeout(POLL, stream.next()).await else {
+            continue;
+        };
+        match next {
+            Some(Ok(message)) => print_message(tx, &message)?,
+            Some(Err(status)) => return Err(failed(status)),
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+fn print_message(tx: &Sender<AppEvent>, message: &DynamicMessage) -> Result<(), String> {
+    let value =
+        serde_json::to_value(message).map_err(|err| format!("Can't show the response: {err}"))?;
+    let text = serde_json::to_string_pretty(&value).unwrap_or_default();
+    for line in text.lines() {
+        print(tx, format!("  {line}"));
+    }
+    Ok(())
+}
+
+// Ask the reflection service one question
+async fn reflect(channel: &Channel, request: MessageRequest) -> Result<MessageResponse, String> {
+    let mut client = ServerReflectionClient::new(channel.clone());
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(request),
+    };
+    let mut responses = client
+        .server_reflection_info(stream::iter([request]))
+        .await
+        .map_err(|status| format!("The server doesn't offer reflection: {}", status.message()))?
+        .into_inner();
+    match responses.next().await {
+        Some(Ok(response)) => match response.message_response {
+            Some(MessageResponse::ErrorResponse(err)) => Err(err.error_message),
+            Some(response) => Ok(response),
+            None => Err("The server's reflection gave no answer".into()),
+        },
+        Some(Err(status)) => Err(status.message().to_string()),
+        None => Err("The server's reflection gave no answer".into()),
+    }
+}
+
+// The file declaring `symbol`, and every file it imports
+async fn descriptors(channel: &Channel, symbol: &str) -> Result<DescriptorPool, String> {
+    let mut files: Vec<FileDescriptorProto> = Vec::new();
+    let mut wanted = vec![MessageRequest::FileContainingSymbol(symbol.to_string())];
+    while let Some(request) = wanted.pop() {
+        let MessageResponse::FileDescriptorResponse(response) = reflect(channel, request).await?
+        else {
+            continue;
+        };
+        for bytes in response.file_descriptor_proto {
+            let file =
+                FileDescriptorProto::decode(bytes.as_slice()).map_err(|err| err.to_string())?;
+            if files.iter().any(|f| f.name == file.name) {
+                continue;
+            }
+            for dependency in &file.dependency {
+                if !files.iter().any(|f| f.name.as_ref() == Some(dependency)) {
+                    wanted.push(MessageRequest::FileByFilename(dependency.clone()));
+                }
+            }
+            files.push(file);
+        }
+    }
+    let mut pool = DescriptorPool::new();
+    pool.add_file_descriptor_protos(files)
+        .map_err(|err| format!("The server's descriptors don't add up: {err}"))?;
+    Ok(pool)
+}
+
+// DynamicMessages on the wire: encoded as they are, decoded as `0`
+struct DynamicCodec(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicCodec;
+    type Decoder = DynamicCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicCodec(self.0.clone())
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicCodec(self.0.clone())
+    }
+}
+
+impl Encoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = GrpcStatus;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|err| GrpcStatus::internal(err.to_string()))
+    }
+}
+
+impl Decoder for DynamicCodec {
+    type Item = DynamicMessage;
+    type Error = GrpcStatus;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let bytes = src.copy_to_bytes(src.remaining());
+        DynamicMessage::decode(self.0.clone(), bytes)
+            .map(Some)
+            .map_err(|err| GrpcStatus::internal(err.to_string()))
+    }
+}
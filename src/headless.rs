@@ -0,0 +1,130 @@
+// =============================================================================
+// Headless modes — the same command dispatcher over plain stdin/stdout
+//
+// No alternate screen, no raw mode: each line read is one command, and every
+// AppEvent::Print is written straight to stdout.
+//
+//   Repl  (--no-tui)   — interactive, with a "> " prompt. Useful over dumb
+//                        terminals, serial consoles, or anywhere a full-screen
+//                        UI gets in the way.
+//   Batch (piped stdin) — no prompt or banner, waits for background jobs at
+//                        end of input, and the exit code is the last
+//                        command's Status: `echo "hello" | myapp`. The
+//                        lines aren't added to the command history.
+// =============================================================================
+
+use std::{
+    io::{self, BufRead, Write},
+    process::ExitCode,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use color_eyre::Result;
+
+use crate::{App, AppEvent, Status, execute_command};
+
+// How often to check for background job output while waiting for a line
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Repl,
+    Batch,
+}
+
+pub fn run(mode: Mode) -> Result<ExitCode> {
+    let mut app = App::new();
+    let mut stdout = io::stdout();
+    let mut status = Status::Success;
+
+    // Read stdin on its own thread so output from background jobs can be
+    // printed while we're waiting for the next line
+    let (line_tx, line_rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if line_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    if mode == Mode::Repl {
+        writeln!(
+            stdout,
+            "Type 'help' for available commands, Ctrl-D to quit."
+        )?;
+    }
+    for command in app.config.startup.clone() {
+        execute_command(&mut app.ctx(), &command);
+    }
+    print_events(&mut app, &mut stdout)?;
+    prompt(mode, &mut stdout)?;
+
+    while app.running {
+        match line_rx.recv_timeout(POLL_INTERVAL) {
+            Ok(line) => {
+                if !line.trim().is_empty() {
+                    // A script's lines aren't something the user typed
+                    if mode == Mode::Repl {
+                        app.remember(&line);
+                    }
+                    status = execute_command(&mut app.ctx(), &line);
+                }
+                print_events(&mut app, &mut stdout)?;
+                if app.running {
+                    prompt(mode, &mut stdout)?;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => print_events(&mut app, &mut stdout)?,
+            // Ctrl-D / end of input
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        stdout.flush()?;
+    }
+
+    // A script shouldn't exit while its `sleep 1` (or download, or …) is
+    // still running — wait for spawned jobs to finish and show their output
+    if mode == Mode::Batch {
+        while app.running && app.spawner.active() > 0 {
+            thread::sleep(POLL_INTERVAL);
+            print_events(&mut app, &mut stdout)?;
+        }
+        print_events(&mut app, &mut stdout)?;
+    }
+    stdout.flush()?;
+    if mode == Mode::Repl {
+        app.save_history();
+    }
+
+    Ok(match status {
+        Status::Success => ExitCode::SUCCESS,
+        Status::Failure => ExitCode::FAILURE,
+    })
+}
+
+fn prompt(mode: Mode, out: &mut impl Write) -> io::Result<()> {
+    match mode {
+        Mode::Repl => write!(out, "> "),
+        Mode::Batch => Ok(()),
+    }
+}
+
+// The headless equivalent of App::drain_events: output goes to stdout
+// instead of the scrollback, everything else is applied as usual
+fn print_events(app: &mut App, out: &mut impl Write) -> Result<()> {
+    while let Ok(event) = app.rx.try_recv() {
+        match event {
+            AppEvent::Print(line) => writeln!(out, "{line}")?,
+            AppEvent::Toast(level, text) => writeln!(out, "  {level}: {text}")?,
+            AppEvent::Clear => {} // there is no screen to clear
+            AppEvent::Open(_) | AppEvent::ShowPane(_) => {
+                writeln!(out, "  That needs the interactive UI")?;
+            }
+            other => app.apply(other),
+        }
+    }
+    Ok(())
+}
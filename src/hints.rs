@@ -0,0 +1,80 @@
+// =============================================================================
+// Hint line — the keys that matter right now, nano-style, in one row
+//
+//   [layout]
+//   panels = [
+//       { kind = "output", size = "fill" },
+//       { kind = "input",  size = { length = 3 } },
+//       { kind = "hints",  size = { length = 1 } },
+//   ]
+//
+// What's shown follows the keyboard: an open popup's keys, else a focused
+// pane's, else the input bar's. The input bar's come from the keymap, so
+// rebinding a key (or binding a command) changes the hint too. Popups and
+// panes list their own keys with hints(); see overlay.rs and pane.rs.
+// =============================================================================
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    keymap::{Action, Keymap},
+    theme::Theme,
+};
+
+// (key, what it does), most useful first
+pub type Hints = &'static [(&'static str, &'static str)];
+
+// The built-in actions worth a hint, and what to call them
+const INPUT_ACTIONS: [(&str, &str); 6] = [
+    ("submit", "run"),
+    ("history-prev", "history"),
+    ("focus-next", "pane"),
+    ("menu", "menu"),
+    ("clear-input", "clear"),
+    ("quit", "quit"),
+];
+
+// The input bar's keys: built-in actions first, then bound commands
+pub fn for_input(keymap: &Keymap, has_pane: bool) -> Vec<(String, String)> {
+    let entries = keymap.entries();
+    let mut hints: Vec<(String, String)> = INPUT_ACTIONS
+        .iter()
+        .filter(|(action, _)| has_pane || *action != "focus-next")
+        .filter_map(|(action, label)| {
+            let (key, _) = entries.iter().find(|(_, a)| a == action)?;
+            Some((key.clone(), label.to_string()))
+        })
+        .collect();
+    let commands = entries
+        .into_iter()
+        .filter(|(_, action)| matches!(Action::parse(action), Action::Command(_)));
+    hints.extend(commands);
+    hints
+}
+
+// Owned copies of a popup's or pane's static hints
+pub fn owned(hints: Hints) -> Vec<(String, String)> {
+    hints
+        .iter()
+        .map(|(key, label)| (key.to_string(), label.to_string()))
+        .collect()
+}
+
+pub fn render(frame: &mut Frame, area: Rect, hints: &[(String, String)], theme: &Theme) {
+    let key_style = Style::default()
+        .fg(theme.status_fg)
+        .bg(theme.status_bg)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    for (key, label) in hints {
+        spans.push(Span::styled(format!(" {key} "), key_style));
+        spans.push(Span::raw(format!(" {label}  ")));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
@@ -0,0 +1,128 @@
+// =============================================================================
+// Host — what the main loop needs from outside the app, besides a screen
+//
+//   where events come from     Tty: the keyboard (see backend.rs) and a
+//                              --replay; Script: a list of them, for
+//                              end-to-end tests (e2e.rs)
+//   where mode changes go      mouse capture, focus reporting, the kitty
+//                              keyboard protocol, the bell
+//   when to stop regardless    Tty: the terminal hung up; Script: it's out
+//   what a crash would lose    after each key (see recovery.rs)
+//
+// The screen itself is the ratatui Terminal the loop is handed, so with a
+// TestBackend and a Script run() is the same loop a session runs, drawing
+// into memory, without a terminal or files being touched.
+// =============================================================================
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, atomic::AtomicBool, mpsc::Sender},
+    time::Duration,
+};
+
+use color_eyre::{Result, eyre::WrapErr};
+use crossterm::event::Event;
+
+use crate::{
+    backend::{self, Keyboard},
+    ctx::AppEvent,
+    multiplexer::Multiplexer,
+    recording::{Recorder, Replay},
+    recovery,
+    toast::Level,
+};
+
+pub trait Host {
+    // The next event, waiting up to `timeout` for one
+    fn event(&mut self, timeout: Duration) -> Result<Option<Event>>;
+
+    // Where escape sequences for the terminal go
+    fn out(&mut self) -> &mut dyn Write;
+
+    // Whether to ask the terminal to report focus, which not every backend can read
+    fn reports_focus(&self) -> bool {
+        true
+    }
+
+    // Whether the terminal has the kitty keyboard protocol (see keymap.rs)
+    fn kitty_keyboard(&mut self) -> bool {
+        false
+    }
+
+    // True once the loop should stop, whatever the app thinks
+    fn done(&self) -> bool;
+
+    // After each key: the state a crash would lose
+    fn unsaved(&mut self, _input: &str, _history: &[String]) {}
+}
+
+// The real terminal
+pub struct Tty {
+    keyboard: Keyboard,
+    recorder: Option<Recorder>,
+    replay: Option<Replay>,
+    terminated: Arc<AtomicBool>, // SIGHUP or SIGTERM arrived
+    tx: Sender<AppEvent>,        // to say the replay is through
+    out: io::Stdout,
+}
+
+impl Tty {
+    pub fn new(
+        keyboard: Keyboard,
+        recorder: Option<Recorder>,
+        replay: Option<Replay>,
+        tx: Sender<AppEvent>,
+    ) -> Self {
+        Self {
+            keyboard,
+            recorder,
+            replay,
+            terminated: recovery::termination_flag(),
+            tx,
+            out: io::stdout(),
+        }
+    }
+}
+
+impl Host for Tty {
+    fn event(&mut self, mut timeout: Duration) -> Result<Option<Event>> {
+        if let Some(wait) = self.replay.as_ref().and_then(Replay::wait) {
+            timeout = timeout.min(wait);
+        }
+        // The replay's next event if it's due, else the keyboard's
+        let event = match self.replay.as_mut().and_then(Replay::next) {
+            Some(event) => Some(event),
+            None => self.keyboard.next(timeout)?,
+        };
+        if self.replay.as_ref().is_some_and(Replay::finished) {
+            self.replay = None;
+            _ = self
+                .tx
+                .send(AppEvent::Toast(Level::Info, "Replay finished".into()));
+        }
+        if let (Some(recorder), Some(event)) = (&mut self.recorder, &event) {
+            recorder.record(event).wrap_err("recording an event")?;
+        }
+        Ok(event)
+    }
+
+    fn out(&mut self) -> &mut dyn Write {
+        &mut self.out
+    }
+
+    fn reports_focus(&self) -> bool {
+        backend::FOCUS && Multiplexer::current().is_none_or(Multiplexer::reports_focus)
+    }
+
+    fn kitty_keyboard(&mut self) -> bool {
+        Multiplexer::current().is_none() && self.keyboard.kitty()
+    }
+
+    fn done(&self) -> bool {
+        recovery::terminated(&self.terminated)
+    }
+
+    fn unsaved(&mut self, input: &str, history: &[String]) {
+        recovery::record(input, history);
+    }
+}
@@ -0,0 +1,108 @@
+// =============================================================================
+// HTTP — `http get <url>` / `http post <url> <body>` (the `http` feature)
+//
+//   cargo run --features http
+//   > http get https://httpbin.org/json
+//   > http post https://httpbin.org/post {"name": "value"}
+//
+// The request runs as a background job, so the UI keeps going while it
+// waits. The status line and headers are printed first, then the body as it
+// arrives, a line at a time; a JSON body is collected and pretty-printed
+// instead. When the server says how long the body is, a gauge shows how
+// much has come in. A post body that parses as JSON is sent as JSON. The
+// request goes through ctx.effects.http (see effects.rs).
+// =============================================================================
+
+use std::{
+    io::{BufRead, BufReader, Read},
+    sync::mpsc::Sender,
+};
+
+use crate::{
+    ctx::{AppEvent, Ctx, ProgressHandle, Status},
+    effects::{Request, Response},
+};
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: http get <url> | http post <url> <body>";
+    let (method, rest) = args.split_once(' ').unwrap_or((args, ""));
+    let (url, body) = match (method, rest.trim().split_once(' ')) {
+        ("get", None) if !rest.is_empty() => (rest.trim(), None),
+        ("post", Some((url, body))) => (url, Some(body.trim().to_string())),
+        _ => return ctx.fail(usage),
+    };
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return ctx.fail(format!("  Not an http(s) URL: {url}"));
+    }
+
+    let label = format!("{} {url}", method.to_uppercase());
+    ctx.print(format!("  {label}"));
+    let request = Request {
+        url: url.to_string(),
+        body: body.map(
+            |body| match serde_json::from_str::<serde_json::Value>(&body) {
+                Ok(_) => (body, "application/json"),
+                Err(_) => (body, "text/plain"),
+            },
+        ),
+    };
+    let http = ctx.effects.http.clone();
+    ctx.spawner
+        .spawn_with_progress(label, move |tx, progress| match http.send(request) {
+            Ok(response) => print_response(&tx, response, &progress),
+            Err(why) => print(&tx, format!("  Request failed: {why}")),
+        });
+    Status::Success
+}
+
+fn print(tx: &Sender<AppEvent>, line: String) {
+    let _ = tx.send(AppEvent::Print(line));
+}
+
+fn print_response(tx: &Sender<AppEvent>, response: Response, progress: &ProgressHandle) {
+    print(tx, format!("  {} {}", response.version, response.status));
+    for (name, value) in &response.headers {
+        print(tx, format!("  {name}: {value}"));
+    }
+    print(tx, String::new());
+
+    let is_json = response
+        .headers
+        .iter()
+        .any(|(name, value)| name == "content-type" && value.contains("json"));
+    let length = response.length;
+    let mut received = 0;
+    let mut reader = BufReader::new(response.body);
+
+    if is_json {
+        let mut body = Vec::new();
+        if let Err(err) = reader.read_to_end(&mut body) {
+            return print(tx, format!("  Reading the body failed: {err}"));
+        }
+        let pretty = serde_json::from_slice::<serde_json::Value>(&body)
+            .and_then(|json| serde_json::to_string_pretty(&json));
+        let text = pretty.unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+        for line in text.lines() {
+            print(tx, format!("  {line}"));
+        }
+        return;
+    }
+
+    // Anything else streams: each line goes out as soon as it's complete
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => break,
+            Ok(n) => {
+                received += n as u64;
+                if let Some(length) = length.filter(|&l| l > 0) {
+                    progress.set(received as f64 / length as f64);
+                }
+                let text = String::from_utf8_lossy(&line);
+                print(tx, format!("  {}", text.trim_end_matches(['\r', '\n'])));
+            }
+            Err(err) => return print(tx, format!("  Reading the body failed: {err}")),
+        }
+    }
+}
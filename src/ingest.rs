@@ -0,0 +1,39 @@
+// =============================================================================
+// Ingest — `--ingest`: piped stdin is data for Output, not commands
+//
+//   journalctl -f | tui-template --ingest
+//   > filter error|warn        only those lines (a regex; -i ignores case)
+//   > filter                   all of them again
+//
+// Each line read joins Output as it arrives while the UI carries on as
+// usual; the keyboard is read from the terminal itself (/dev/tty), since
+// stdin is taken. Color and other escape sequences are dropped, as Output
+// would print them as garbage. When the pipe closes a toast says so and
+// everything already read stays.
+// =============================================================================
+
+use std::{
+    io::{self, BufRead},
+    sync::mpsc::Sender,
+    thread,
+};
+
+use crate::{ctx::AppEvent, output::plain, toast::Level};
+
+// Read stdin on a thread of its own until it ends
+pub fn start(tx: Sender<AppEvent>) {
+    thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        let mut line = Vec::new();
+        // Bytes, not read_line, so one bad line of non-UTF-8 doesn't stop it
+        while stdin.read_until(b'\n', &mut line).unwrap_or(0) > 0 {
+            let text = String::from_utf8_lossy(&line);
+            let text = plain(text.trim_end_matches(['\r', '\n'])).into_owned();
+            if tx.send(AppEvent::Print(text)).is_err() {
+                return; // the app has gone
+            }
+            line.clear();
+        }
+        let _ = tx.send(AppEvent::Toast(Level::Info, "Piped input ended".into()));
+    });
+}
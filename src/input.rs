@@ -0,0 +1,50 @@
+// =============================================================================
+// Input — what the user did, as the app sees it
+//
+// The main loop turns each terminal event into an Input and hands it to
+// App::handle(); nothing past that point deals with the terminal. So the
+// app can be driven without one — by the fuzz target (fuzz/), benchmarks
+// or tests — with the same code paths a real session takes:
+//
+//   let mut app = App::new();
+//   app.handle(Input::Key("h".parse()?));
+//   app.handle(Input::Key("enter".parse()?));
+//
+// A paste is one Input however long it is, since the loop turns on
+// bracketed paste (see App::handle). A key coming up is a Key too, marked
+// as a release (see keymap.rs), which only a binding for it acts on. Events
+// the app doesn't act on (the mouse wheel) have no Input. Resizes stay with
+// the loop, which debounces them against the terminal's real size.
+// =============================================================================
+
+use crossterm::event::{Event, MouseButton, MouseEventKind};
+
+use crate::keymap::KeyBinding;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Key(KeyBinding),
+    Click { column: u16, row: u16 }, // the left button going down, or dragged
+    Focus(bool),                     // the terminal window gained (true) or lost focus
+    Paste(String),                   // text pasted into the terminal, all at once
+}
+
+impl Input {
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Key(key) => Some(Input::Key((*key).into())),
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left)
+                | MouseEventKind::Drag(MouseButton::Left) => Some(Input::Click {
+                    column: mouse.column,
+                    row: mouse.row,
+                }),
+                _ => None,
+            },
+            Event::FocusGained => Some(Input::Focus(true)),
+            Event::FocusLost => Some(Input::Focus(false)),
+            Event::Paste(text) => Some(Input::Paste(text.clone())),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// =============================================================================
+// Input line — the input bar's text and cursor, as a gap buffer
+//
+//   line.insert('x')           at the cursor
+//   line.insert_str(pasted)
+//   line.delete_back()         backspace; delete_forward() is Delete
+//   line.left() / right() / home() / end()
+//   line.take()                the text, leaving the line empty
+//
+// The characters sit in one Vec with a gap at the cursor, so typing and
+// deleting there just move the gap's edges, however long the line — a
+// pasted script, say — and moving the cursor copies only what it passes
+// over. The gap grows by half the line when it runs out. Positions are in
+// characters, not bytes.
+//
+// On screen, though, a CJK character or an emoji is two columns wide, so
+// visible() works out what of a line to draw, and where its cursor goes,
+// by display width (for LineEdit in prompts too).
+// =============================================================================
+
+use std::{fmt, iter};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const MIN_GAP: usize = 16;
+
+#[derive(Default)]
+pub struct InputLine {
+    buf: Vec<char>, // [..start] before the cursor, [end..] after it
+    start: usize,
+    end: usize,
+}
+
+impl InputLine {
+    pub fn insert(&mut self, c: char) {
+        self.reserve(1);
+        self.buf[self.start] = c;
+        self.start += 1;
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        self.reserve(text.chars().count());
+        for c in text.chars() {
+            self.buf[self.start] = c;
+            self.start += 1;
+        }
+    }
+
+    pub fn delete_back(&mut self) {
+        self.start = self.start.saturating_sub(1);
+    }
+
+    pub fn delete_forward(&mut self) {
+        self.end = (self.end + 1).min(self.buf.len());
+    }
+
+    pub fn left(&mut self) {
+        self.move_to(self.start.saturating_sub(1));
+    }
+
+    pub fn right(&mut self) {
+        self.move_to(self.start + 1);
+    }
+
+    pub fn home(&mut self) {
+        self.move_to(0);
+    }
+
+    pub fn end(&mut self) {
+        self.move_to(self.len());
+    }
+
+    // Replace it all, with the cursor at the end
+    pub fn set(&mut self, text: &str) {
+        self.buf = text.chars().collect();
+        self.start = self.buf.len();
+        self.end = self.buf.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.end = self.buf.len();
+    }
+
+    pub fn take(&mut self) -> String {
+        let text = self.to_string();
+        self.clear();
+        text
+    }
+
+    // Characters before the cursor
+    pub fn cursor(&self) -> usize {
+        self.start
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.buf[..self.start]
+            .iter()
+            .chain(&self.buf[self.end..])
+            .copied()
+    }
+
+    fn len(&self) -> usize {
+        self.buf.len() - (self.end - self.start)
+    }
+
+    // Put the cursor (the gap) before character `to`, or at the end
+    fn move_to(&mut self, to: usize) {
+        let to = to.min(self.len());
+        if to < self.start {
+            let n = self.start - to;
+            self.buf.copy_within(to..self.start, self.end - n);
+            self.start = to;
+            self.end -= n;
+        } else if to > self.start {
+            let n = to - self.start;
+            self.buf.copy_within(self.end..self.end + n, self.start);
+            self.start += n;
+            self.end += n;
+        }
+    }
+
+    // Make the gap at least `n` wide
+    fn reserve(&mut self, n: usize) {
+        let gap = self.end - self.start;
+        if gap >= n {
+            return;
+        }
+        let grow = (n - gap).max(self.len() / 2).max(MIN_GAP);
+        self.buf
+            .splice(self.end..self.end, iter::repeat_n('\0', grow));
+        self.end += grow;
+    }
+}
+
+impl fmt::Display for InputLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.chars().try_for_each(|c| fmt::Write::write_char(f, c))
+    }
+}
+
+// What of `text` fits in `width` columns with the cursor, `cursor`
+// characters in, still in view — scrolled sideways as far as it takes,
+// never splitting a wide character — and the cursor's column in that
+pub fn visible(text: &str, cursor: usize, width: u16) -> (&str, u16) {
+    let before: usize = text
+        .chars()
+        .take(cursor)
+        .map(|c| c.width().unwrap_or(0))
+        .sum();
+    let room = usize::from(width.saturating_sub(1)); // the cursor's own column after it
+    let mut skipped = 0;
+    let mut chars = text.char_indices();
+    let mut start = 0;
+    while before - skipped > room
+        && let Some((i, c)) = chars.next()
+    {
+        skipped += c.width().unwrap_or(0);
+        start = i + c.len_utf8();
+    }
+    (&text[start..], (before - skipped) as u16)
+}
+
+// Columns `text` takes up
+pub fn width(text: &str) -> u16 {
+    text.width().try_into().unwrap_or(u16::MAX)
+}
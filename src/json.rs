@@ -0,0 +1,287 @@
+// =============================================================================
+// JSON — `json <file | inline json> [query]`: pretty-print, colored, in a pane
+//
+//   > json package.json .dependencies
+//   > json {"users": [{"name": "ada"}, {"name": "bob"}]} .users[].name
+//
+// The query is a small subset of jq's paths:
+//
+//   .                  the whole document
+//   .name  ."a key"    a field (quote names that aren't plain words)
+//   [2]  [-1]          an array element, counting from the end if negative
+//   []                 every element (or field value); what follows applies
+//                      to each, and each result is shown
+//
+// e.g. `.users[0].name`, `.items[].id`. Missing fields give null, like jq.
+//
+//   ↑/↓ PgUp/PgDn Home/End   scroll
+// =============================================================================
+
+use std::{cell::Cell, path::Path};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use serde_json::Value;
+
+use crate::{
+    ctx::{Ctx, Status},
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+};
+
+const INDENT: &str = "  ";
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: json <file | inline json> [.path.query]";
+    if args.is_empty() {
+        return ctx.fail(usage);
+    }
+    // Inline JSON runs up to the end of its first value; a file name to the
+    // first space
+    let inline = args.starts_with(['{', '[', '"']);
+    let (source, document, query) = if inline {
+        let mut values = serde_json::Deserializer::from_str(args).into_iter::<Value>();
+        match values.next() {
+            Some(Ok(value)) => {
+                let rest = &args[values.byte_offset()..];
+                ("inline".to_string(), value, rest.trim())
+            }
+            Some(Err(err)) => return ctx.fail(format!("  Not valid JSON: {err}")),
+            None => return ctx.fail(usage),
+        }
+    } else {
+        let (path, query) = args.split_once(' ').unwrap_or((args, ""));
+        let text = match ctx.effects.files.read(Path::new(path)) {
+            Ok(text) => text,
+            Err(err) => return ctx.fail(format!("  Can't read {path}: {err}")),
+        };
+        match serde_json::from_str(&text) {
+            Ok(value) => (path.to_string(), value, query.trim()),
+            Err(err) => return ctx.fail(format!("  {path} isn't valid JSON: {err}")),
+        }
+    };
+
+    let steps = match parse_query(query) {
+        Ok(steps) => steps,
+        Err(why) => return ctx.fail(format!("  Bad query {query}: {why}")),
+    };
+    let results = select(&document, &steps);
+    let title = match query {
+        "" | "." => format!("JSON: {source}"),
+        query => format!("JSON: {source} {query}"),
+    };
+    ctx.show(JsonView::new(title, &results));
+    Status::Success
+}
+
+enum Step {
+    Field(String),
+    Index(i64),
+    Each,
+}
+
+fn parse_query(query: &str) -> Result<Vec<Step>, String> {
+    let mut steps = Vec::new();
+    let mut rest = query.trim();
+    if rest.is_empty() || rest == "." {
+        return Ok(steps);
+    }
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let (inside, after) = after.split_once(']').ok_or("missing ]")?;
+            steps.push(match inside.trim() {
+                "" => Step::Each,
+                n => Step::Index(n.parse().map_err(|_| format!("[{n}] isn't an index"))?),
+            });
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('.') {
+            if let Some(quoted) = after.strip_prefix('"') {
+                let (name, after) = quoted.split_once('"').ok_or("missing closing \"")?;
+                steps.push(Step::Field(name.to_string()));
+                rest = after;
+            } else {
+                let end = after
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                    .unwrap_or(after.len());
+                if end > 0 {
+                    steps.push(Step::Field(after[..end].to_string()));
+                } else if !after.starts_with('[') {
+                    return Err("expected a field name after .".into());
+                }
+                rest = &after[end..];
+            }
+        } else {
+            return Err(format!("unexpected '{rest}'"));
+        }
+    }
+    Ok(steps)
+}
+
+// Everything the query picks out; more than one only after a []
+fn select(value: &Value, steps: &[Step]) -> Vec<Value> {
+    let Some((step, rest)) = steps.split_first() else {
+        return vec![value.clone()];
+    };
+    match step {
+        Step::Field(name) => select(value.get(name).unwrap_or(&Value::Null), rest),
+        Step::Index(i) => {
+            let element = value.as_array().and_then(|items| {
+                let i = if *i < 0 { items.len() as i64 + i } else { *i };
+                usize::try_from(i).ok().and_then(|i| items.get(i))
+            });
+            select(element.unwrap_or(&Value::Null), rest)
+        }
+        Step::Each => {
+            let children: Vec<&Value> = match value {
+                Value::Array(items) => items.iter().collect(),
+                Value::Object(fields) => fields.values().collect(),
+                _ => Vec::new(),
+            };
+            children.into_iter().flat_map(|v| select(v, rest)).collect()
+        }
+    }
+}
+
+// Colors for each kind of token
+struct Palette {
+    key: Style,
+    string: Style,
+    number: Style,
+    literal: Style, // true / false / null
+}
+
+// Pretty-print `value` as colored lines, like serde_json's pretty printer
+fn write_value(
+    value: &Value,
+    depth: usize,
+    palette: &Palette,
+    line: &mut Vec<Span<'static>>,
+    lines: &mut Vec<Line<'static>>,
+) {
+    let indent = |depth: usize| Span::raw(INDENT.repeat(depth));
+    let (open, close, entries): (&str, &str, Vec<(Option<&String>, &Value)>) = match value {
+        Value::Array(items) => ("[", "]", items.iter().map(|v| (None, v)).collect()),
+        Value::Object(fields) => ("{", "}", fields.iter().map(|(k, v)| (Some(k), v)).collect()),
+        Value::String(s) => {
+            line.push(Span::styled(
+                Value::String(s.clone()).to_string(),
+                palette.string,
+            ));
+            return;
+        }
+        Value::Number(n) => {
+            line.push(Span::styled(n.to_string(), palette.number));
+            return;
+        }
+        other => {
+            line.push(Span::styled(other.to_string(), palette.literal));
+            return;
+        }
+    };
+    if entries.is_empty() {
+        line.push(Span::raw(format!("{open}{close}")));
+        return;
+    }
+    line.push(Span::raw(open));
+    let count = entries.len();
+    for (i, (key, child)) in entries.into_iter().enumerate() {
+        lines.push(Line::from(std::mem::take(line)));
+        line.push(indent(depth + 1));
+        if let Some(key) = key {
+            line.push(Span::styled(
+                Value::String(key.clone()).to_string(),
+                palette.key,
+            ));
+            line.push(Span::raw(": "));
+        }
+        write_value(child, depth + 1, palette, line, lines);
+        if i + 1 < count {
+            line.push(Span::raw(","));
+        }
+    }
+    lines.push(Line::from(std::mem::take(line)));
+    line.push(indent(depth));
+    line.push(Span::raw(close));
+}
+
+pub struct JsonView {
+    title: String,
+    values: Vec<Value>,  // what the query picked out
+    count: usize,        // lines, for scrolling
+    top: usize,          // first line on screen
+    height: Cell<usize>, // rows that fit, as of the last render
+}
+
+impl JsonView {
+    fn new(title: String, values: &[Value]) -> Self {
+        let count = values
+            .iter()
+            .map(|v| serde_json::to_string_pretty(v).map_or(1, |s| s.lines().count()))
+            .sum();
+        Self {
+            title,
+            values: values.to_vec(),
+            count,
+            top: 0,
+            height: Cell::new(1),
+        }
+    }
+
+    fn scroll_by(&mut self, step: isize) {
+        let last = self.count.saturating_sub(self.height.get());
+        self.top = self.top.saturating_add_signed(step).min(last);
+    }
+}
+
+impl Pane for JsonView {
+    fn title(&self) -> String {
+        match (self.values.len(), self.count) {
+            (1, 1) => format!("{} (1 line)", self.title),
+            (1, lines) => format!("{} ({lines} lines)", self.title),
+            (n, _) => format!("{} ({n} results)", self.title),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.height.set(area.height as usize);
+        let palette = Palette {
+            key: Style::default().fg(theme.input_border),
+            string: Style::default().fg(Color::Green),
+            number: Style::default().fg(Color::Cyan),
+            literal: Style::default().fg(Color::Magenta),
+        };
+        let mut lines = Vec::new();
+        for value in &self.values {
+            let mut line = Vec::new();
+            write_value(value, 0, &palette, &mut line, &mut lines);
+            lines.push(Line::from(line));
+        }
+        let text = Paragraph::new(lines).scroll((self.top as u16, 0));
+        frame.render_widget(text, area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get().max(1) as isize;
+        match key.code {
+            KeyCode::Up => self.scroll_by(-1),
+            KeyCode::Down => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home => self.top = 0,
+            KeyCode::End => self.scroll_by(isize::MAX),
+            _ => return false,
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[("↑↓", "scroll"), ("PgUp/PgDn", "page")]
+    }
+}
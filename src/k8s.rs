@@ -0,0 +1,382 @@
+// =============================================================================
+// Kubernetes — `k8s`: a namespace's pods in a table, and their logs (the
+// `k8s` feature)
+//
+//   cargo run --features k8s
+//   > k8s
+//   > k8s ns payments
+//   > k8s logs api-7d9f8-x2kq4
+//
+// Uses the kubeconfig kubectl does ($KUBECONFIG or ~/.kube/config), starting
+// with its current context and that context's namespace. The pane lists the
+// pods — how many containers are ready, why one isn't, restarts, age — and
+// is re-read every REFRESH by a background job; sort and filter as in any
+// table (widgets/table.rs).
+//
+//   l            follow the selected pod's logs into Output
+//
+//   k8s contexts               list the kubeconfig's contexts
+//   k8s context <name>         switch context (and to its namespace)
+//   k8s ns <name>              switch namespace
+//   k8s logs <pod> [container] the last LOG_TAIL lines, then follow, tagged
+//                              with the pod's name like `tail`, until
+//                              `cancel`led
+//
+// Switching only lasts for this session; the kubeconfig is left alone.
+// =============================================================================
+
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, mpsc::Sender},
+    time::Duration,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use futures_util::{AsyncBufReadExt, StreamExt};
+use k8s_openapi::{api::core::v1::Pod, jiff::Timestamp};
+use kube::{
+    Api, Client, Config,
+    api::{ListParams, LogParams},
+    config::{KubeConfigOptions, Kubeconfig},
+};
+use ratatui::{Frame, layout::Rect};
+
+use crate::{
+    ctx::{AppEvent, Ctx, ProgressHandle, Status},
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+    widgets::TableView,
+};
+
+const REFRESH: Duration = Duration::from_secs(5);
+const POLL: Duration = Duration::from_millis(250); // how often jobs check for closing / `cancel`
+const LOG_TAIL: i64 = 50;
+const HEADERS: [&str; 6] = ["Name", "Ready", "Status", "Restarts", "Age", "Node"];
+
+// Which cluster and namespace commands talk to; None = the kubeconfig's
+#[derive(Clone, Default)]
+pub struct Target {
+    context: Option<String>,
+    namespace: Option<String>,
+}
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: k8s [contexts | context <name> | ns <name> | logs <pod> [container]]";
+    let words: Vec<&str> = args.split_whitespace().collect();
+    match words[..] {
+        [] => show(ctx),
+        ["contexts"] => contexts(ctx),
+        ["context", name] => {
+            let known = Kubeconfig::read().is_ok_and(|k| k.contexts.iter().any(|c| c.name == name));
+            if !known {
+                return ctx.fail(format!("  No context {name} (see `k8s contexts`)"));
+            }
+            ctx.connections.kube = Target {
+                context: Some(name.to_string()),
+                namespace: None,
+            };
+            show(ctx)
+        }
+        ["ns", namespace] => {
+            ctx.connections.kube.namespace = Some(namespace.to_string());
+            show(ctx)
+        }
+        ["logs", pod] | ["logs", pod, _] => {
+            let (pod, container) = (pod.to_string(), words.get(2).map(|c| c.to_string()));
+            let target = ctx.connections.kube.clone();
+            ctx.spawner
+                .spawn_with_progress(format!("k8s logs {pod}"), move |tx, progress| {
+                    progress.unknown();
+                    block_on(&tx, follow_logs(&tx, &progress, &target, &pod, container));
+                });
+            Status::Success
+        }
+        _ => ctx.fail(usage),
+    }
+}
+
+fn contexts(ctx: &mut Ctx) -> Status {
+    let kubeconfig = match Kubeconfig::read() {
+        Ok(kubeconfig) => kubeconfig,
+        Err(err) => return ctx.fail(format!("  Can't read the kubeconfig: {err}")),
+    };
+    let current = ctx
+        .connections
+        .kube
+        .context
+        .clone()
+        .or(kubeconfig.current_context);
+    for context in &kubeconfig.contexts {
+        let mark = if current.as_ref() == Some(&context.name) {
+            "*"
+        } else {
+            " "
+        };
+        let namespace = context
+            .context
+            .as_ref()
+            .and_then(|c| c.namespace.as_deref());
+        ctx.print(format!(
+            "  {mark} {:<30} {}",
+            context.name,
+            namespace.unwrap_or("default")
+        ));
+    }
+    Status::Success
+}
+
+fn print(tx: &Sender<AppEvent>, line: String) {
+    let _ = tx.send(AppEvent::Print(line));
+}
+
+// kube is async; each job runs its own small runtime
+fn block_on(tx: &Sender<AppEvent>, job: impl Future<Output = ()>) {
+    match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime.block_on(job),
+        Err(err) => print(tx, format!("  Can't start the Kubernetes client: {err}")),
+    }
+}
+
+// The pods API for the target, and the namespace it ended up with
+async fn pods(target: &Target) -> Result<(Api<Pod>, String), String> {
+    let kubeconfig =
+        Kubeconfig::read().map_err(|err| format!("can't read the kubeconfig: {err}"))?;
+    let options = KubeConfigOptions {
+        context: target.context.clone(),
+        ..Default::default()
+    };
+    let config = Config::from_custom_kubeconfig(kubeconfig, &options)
+        .await
+        .map_err(|err| err.to_string())?;
+    let namespace = target
+        .namespace
+        .clone()
+        .unwrap_or(config.default_namespace.clone());
+    let client = Client::try_from(config).map_err(|err| err.to_string())?;
+    Ok((Api::namespaced(client, &namespace), namespace))
+}
+
+async fn follow_logs(
+    tx: &Sender<AppEvent>,
+    progress: &ProgressHandle,
+    target: &Target,
+    pod: &str,
+    container: Option<String>,
+) {
+    let api = match pods(target).await {
+        Ok((api, _)) => api,
+        Err(why) => return print(tx, format!("  [{pod}] {why}")),
+    };
+    let params = LogParams {
+        container,
+        follow: true,
+        tail_lines: Some(LOG_TAIL),
+        ..Default::default()
+    };
+    let mut lines = match api.log_stream(pod, &params).await {
+        Ok(stream) => stream.lines(),
+        Err(err) => return print(tx, format!("  [{pod}] Can't read the logs: {err}")),
+    };
+    while !progress.cancelled() {
+        let Ok(next) = tokio::time::timeout(POLL, lines.next()).await else {
+            continue;
+        };
+        match next {
+            Some(Ok(line)) => print(tx, format!("  [{pod}] {line}")),
+            Some(Err(err)) => return print(tx, format!("  [{pod}] Stopped following: {err}")),
+            None => break, // the pod's gone, or its container stopped
+        }
+    }
+    print(tx, format!("  [{pod}] Stopped following"));
+}
+
+struct Listing {
+    namespace: String, // the target's, or the kubeconfig's default
+    rows: Vec<Vec<String>>,
+}
+
+#[derive(Default)]
+struct State {
+    pods: Option<Result<Listing, String>>, // None until the first read
+    changed: bool,                         // since the pane took them
+    closed: bool,                          // the pane is gone
+}
+
+type Shared = Arc<Mutex<State>>;
+
+fn show(ctx: &mut Ctx) -> Status {
+    let state = Shared::default();
+    let (job_state, target) = (state.clone(), ctx.connections.kube.clone());
+    ctx.show(PodsView {
+        state,
+        table: TableView::new("Pods", HEADERS),
+        namespace: None,
+        error: None,
+        tx: ctx.sender(),
+    });
+    ctx.spawner.spawn_watcher(move |tx| {
+        block_on(&tx, watch(&job_state, &target));
+    });
+    Status::Success
+}
+
+// Re-list the pods every REFRESH, until the pane closes
+async fn watch(state: &Shared, target: &Target) {
+    let api = pods(target).await;
+    loop {
+        let pods = match &api {
+            Ok((api, namespace)) => match api.list(&ListParams::default()).await {
+                Ok(pods) => Ok(Listing {
+                    namespace: namespace.clone(),
+                    rows: pods.items.iter().map(row).collect(),
+                }),
+                Err(err) => Err(format!("can't list pods: {err}")),
+            },
+            Err(why) => Err(why.clone()),
+        };
+        match state.lock() {
+            Ok(mut state) if !state.closed => {
+                state.pods = Some(pods);
+                state.changed = true;
+            }
+            _ => return,
+        }
+        for _ in 0..REFRESH.as_millis() / POLL.as_millis() {
+            tokio::time::sleep(POLL).await;
+            if state.lock().map_or(true, |s| s.closed) {
+                return;
+            }
+        }
+    }
+}
+
+// A pod as `kubectl get pods` shows it
+fn row(pod: &Pod) -> Vec<String> {
+    let status = pod.status.as_ref();
+    let containers = status
+        .and_then(|s| s.container_statuses.as_deref())
+        .unwrap_or_default();
+    let total = pod
+        .spec
+        .as_ref()
+        .map_or(containers.len(), |s| s.containers.len());
+    let ready = containers.iter().filter(|c| c.ready).count();
+    let restarts: i32 = containers.iter().map(|c| c.restart_count).sum();
+
+    // The most telling reason: deleting, then a stuck container, then the phase
+    let waiting = containers.iter().find_map(|c| {
+        let state = c.state.as_ref()?;
+        let waiting = state.waiting.as_ref().and_then(|w| w.reason.clone());
+        waiting.or_else(|| state.terminated.as_ref().and_then(|t| t.reason.clone()))
+    });
+    let phase = status.and_then(|s| s.phase.clone()).unwrap_or_default();
+    let reason = match pod.metadata.deletion_timestamp {
+        Some(_) => "Terminating".to_string(),
+        None => waiting.unwrap_or(phase),
+    };
+
+    let started = status.and_then(|s| s.start_time.as_ref()).map(|t| t.0);
+    let created = pod.metadata.creation_timestamp.as_ref().map(|t| t.0);
+    let age = started.or(created).map_or(String::new(), |at| {
+        age(Timestamp::now().as_second() - at.as_second())
+    });
+    vec![
+        pod.metadata.name.clone().unwrap_or_default(),
+        format!("{ready}/{total}"),
+        reason,
+        restarts.to_string(),
+        age,
+        pod.spec
+            .as_ref()
+            .and_then(|s| s.node_name.clone())
+            .unwrap_or_default(),
+    ]
+}
+
+// 45s, 12m, 5h, 3d
+fn age(secs: i64) -> String {
+    match secs.max(0) {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
+pub struct PodsView {
+    state: Shared,
+    table: TableView,
+    namespace: Option<String>, // as of the last read
+    error: Option<String>,     // the last read failed
+    tx: Sender<AppEvent>,
+}
+
+impl Drop for PodsView {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+    }
+}
+
+impl Pane for PodsView {
+    fn title(&self) -> String {
+        match &self.error {
+            Some(why) => format!("Pods: {why}"),
+            None => self.table.title(),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if !self.table.typing()
+            && key.code == KeyCode::Char('l')
+            && let Some(row) = self.table.selected_row()
+        {
+            // As a command, so it's a job like a typed one
+            let _ = self.tx.send(AppEvent::Run(format!("k8s logs {}", row[0])));
+            return true;
+        }
+        self.table.handle_key(key)
+    }
+
+    fn tick(&mut self) -> bool {
+        let pods = match self.state.lock() {
+            Ok(mut state) if state.changed => {
+                state.changed = false;
+                state.pods.take()
+            }
+            _ => return false,
+        };
+        match pods {
+            Some(Ok(Listing { namespace, rows })) => {
+                self.error = None;
+                // Titled once the kubeconfig has said which namespace it is
+                if self.namespace.as_ref() != Some(&namespace) {
+                    self.table = TableView::new(format!("Pods in {namespace}"), HEADERS);
+                    self.namespace = Some(namespace);
+                }
+                self.table.set_rows(rows);
+            }
+            Some(Err(why)) => self.error = Some(why),
+            None => {}
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("↑↓", "select"),
+            ("l", "logs"),
+            ("1-9", "sort"),
+            ("/", "filter"),
+        ]
+    }
+}
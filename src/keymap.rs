@@ -0,0 +1,372 @@
+// =============================================================================
+// Keymap — which key does what
+//
+// Keys are written the way you'd say them: "esc", "enter", "ctrl-l",
+// "alt-shift-up", "f1", "x". A binding maps a key to either a built-in
+// Action (quit, submit, delete-back, delete-forward, cursor-left,
+// cursor-right, line-start, line-end, clear-input, history-prev,
+// history-next, focus-next, menu, scroll-up, scroll-down, debug) or, if the
+// name isn't one, a command that is run as if you'd typed it:
+//
+//   [keymap]
+//   "ctrl-l" = "clear"        # run the `clear` command
+//   "ctrl-d" = "quit"         # built-in action
+//   "ctrl-u" = ""             # remove a default binding
+//
+// Plain characters that aren't bound are typed into the input bar.
+//
+// Legacy terminal escapes can't tell ctrl-enter or shift-enter from enter,
+// nor say when a key is let go. Where the terminal has the kitty keyboard
+// protocol (kitty, WezTerm, foot, Ghostty, recent iTerm2 and Alacritty) and
+// no tmux or screen is in the way, the loop turns it on (kitty_keyboard =
+// false in the config says not to), and those have names of their own —
+// "release-" being when the key comes up:
+//
+//   "shift-enter" = "submit"
+//   "release-f5" = "hello"    # fires once the key is let go
+//
+// Elsewhere they just never arrive, and the plain key does what it did.
+//
+// At runtime, `bind <key> <action|command>` and `unbind <key>` change the
+// keymap for the session; `bind save` writes those changes to the config.
+// =============================================================================
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Submit,        // run what's in the input bar
+    DeleteBack,    // backspace
+    DeleteForward, // the character under the cursor
+    CursorLeft,    // move the cursor within the input line
+    CursorRight,
+    LineStart,
+    LineEnd,
+    ClearInput,  // throw away the input line
+    HistoryPrev, // recall the previous command
+    HistoryNext, // …and walk back towards the newest
+    FocusNext,   // switch the keyboard between the input bar and a pane
+    Menu,        // open the menu bar (see menu.rs)
+    ScrollUp,    // page the Output panel back…
+    ScrollDown,  // …and forward again, to following new output
+    Debug,       // show or hide the debug overlay (see debug.rs)
+    Command(String),
+}
+
+impl Action {
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "quit" => Action::Quit,
+            "submit" => Action::Submit,
+            "delete-back" => Action::DeleteBack,
+            "delete-forward" => Action::DeleteForward,
+            "cursor-left" => Action::CursorLeft,
+            "cursor-right" => Action::CursorRight,
+            "line-start" => Action::LineStart,
+            "line-end" => Action::LineEnd,
+            "clear-input" => Action::ClearInput,
+            "history-prev" => Action::HistoryPrev,
+            "history-next" => Action::HistoryNext,
+            "focus-next" => Action::FocusNext,
+            "menu" => Action::Menu,
+            "scroll-up" => Action::ScrollUp,
+            "scroll-down" => Action::ScrollDown,
+            "debug" => Action::Debug,
+            command => Action::Command(command.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Action::Quit => "quit",
+            Action::Submit => "submit",
+            Action::DeleteBack => "delete-back",
+            Action::DeleteForward => "delete-forward",
+            Action::CursorLeft => "cursor-left",
+            Action::CursorRight => "cursor-right",
+            Action::LineStart => "line-start",
+            Action::LineEnd => "line-end",
+            Action::ClearInput => "clear-input",
+            Action::HistoryPrev => "history-prev",
+            Action::HistoryNext => "history-next",
+            Action::FocusNext => "focus-next",
+            Action::Menu => "menu",
+            Action::ScrollUp => "scroll-up",
+            Action::ScrollDown => "scroll-down",
+            Action::Debug => "debug",
+            Action::Command(command) => command,
+        })
+    }
+}
+
+// =============================================================================
+// Key names
+// =============================================================================
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+    release: bool, // the key coming up (kitty keyboard protocol only)
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, mut modifiers: KeyModifiers, release: bool) -> Self {
+        // Shift is already baked into the character ('A' vs 'a'), and
+        // terminals disagree on whether they also report the modifier
+        if let KeyCode::Char(_) = code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self {
+            code,
+            modifiers,
+            release,
+        }
+    }
+
+    pub fn is_release(&self) -> bool {
+        self.release
+    }
+}
+
+// A repeat (a held key, when the terminal reports those) is a press again
+impl From<KeyEvent> for KeyBinding {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers, key.kind == KeyEventKind::Release)
+    }
+}
+
+// The key event that would match, e.g. for feeding keys in tests
+impl From<KeyBinding> for KeyEvent {
+    fn from(key: KeyBinding) -> Self {
+        let kind = match key.release {
+            true => KeyEventKind::Release,
+            false => KeyEventKind::Press,
+        };
+        KeyEvent::new_with_kind(key.code, key.modifiers, kind)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "ctrl-alt-x" → modifiers [ctrl, alt], key "x". A trailing "-" is
+        // the minus key itself ("ctrl--"), as is "-" alone.
+        let (mods, key) = match s.strip_suffix("--") {
+            Some(rest) => (rest, "-"),
+            None if s == "-" => ("", s),
+            None => s.rsplit_once('-').unwrap_or(("", s)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut release = false;
+        for m in mods.split('-').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "release" => {
+                    release = true;
+                    continue;
+                }
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{m}' in key '{s}'")),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{key}' in '{s}'")),
+                },
+            },
+        };
+
+        Ok(Self::new(code, modifiers, release))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.release {
+            f.write_str("release-")?;
+        }
+        for (flag, name) in [
+            (KeyModifiers::CONTROL, "ctrl-"),
+            (KeyModifiers::ALT, "alt-"),
+            (KeyModifiers::SHIFT, "shift-"),
+        ] {
+            if self.modifiers.contains(flag) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+// =============================================================================
+// The keymap itself
+//
+// Bindings from the config are layered over the defaults, so a config only
+// needs to mention the keys it changes.
+// =============================================================================
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(
+    try_from = "HashMap<String, String>",
+    into = "BTreeMap<String, String>"
+)]
+pub struct Keymap {
+    bindings: HashMap<KeyBinding, Action>,
+}
+
+impl Keymap {
+    pub fn get(&self, key: KeyEvent) -> Option<&Action> {
+        self.get_binding(KeyBinding::from(key))
+    }
+
+    pub fn get_binding(&self, key: KeyBinding) -> Option<&Action> {
+        self.bindings.get(&key)
+    }
+
+    pub fn bind(&mut self, key: KeyBinding, action: Action) {
+        self.bindings.insert(key, action);
+    }
+
+    // Returns false if the key wasn't bound
+    pub fn unbind(&mut self, key: KeyBinding) -> bool {
+        self.bindings.remove(&key).is_some()
+    }
+
+    // Bindings as (key, action) text, sorted by key name
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries: Vec<_> = self
+            .bindings
+            .iter()
+            .map(|(key, action)| (key.to_string(), action.to_string()))
+            .collect();
+        entries.sort();
+        entries
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = [
+            ("enter", "submit"),
+            ("backspace", "delete-back"),
+            ("delete", "delete-forward"),
+            ("left", "cursor-left"),
+            ("right", "cursor-right"),
+            ("home", "line-start"),
+            ("end", "line-end"),
+            ("esc", "quit"),
+            ("up", "history-prev"),
+            ("down", "history-next"),
+            ("ctrl-u", "clear-input"),
+            ("tab", "focus-next"),
+            ("f10", "menu"),
+            ("pageup", "scroll-up"),
+            ("pagedown", "scroll-down"),
+            ("ctrl-y", "clips insert"),
+            ("f9", "mouse"),
+            ("f12", "debug"),
+        ];
+        let bindings = bindings
+            .into_iter()
+            .map(|(key, action)| {
+                (
+                    key.parse().expect("valid default key"),
+                    Action::parse(action),
+                )
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl TryFrom<HashMap<String, String>> for Keymap {
+    type Error = String;
+
+    fn try_from(map: HashMap<String, String>) -> Result<Self, Self::Error> {
+        let mut keymap = Keymap::default();
+        for (key, action) in map {
+            let key = key.parse()?;
+            match action.as_str() {
+                "" => {
+                    keymap.unbind(key);
+                }
+                action => keymap.bind(key, Action::parse(action)),
+            }
+        }
+        Ok(keymap)
+    }
+}
+
+// The inverse of try_from: every binding, plus "" for each default that has
+// been unbound, so loading the result gives back exactly this keymap
+impl From<Keymap> for BTreeMap<String, String> {
+    fn from(keymap: Keymap) -> Self {
+        let mut map: Self = keymap.entries().into_iter().collect();
+        for key in Keymap::default().bindings.keys() {
+            if !keymap.bindings.contains_key(key) {
+                map.insert(key.to_string(), String::new());
+            }
+        }
+        map
+    }
+}
+
+// Sorted key → action pairs, so `config` output is stable
+impl fmt::Debug for Keymap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.entries()).finish()
+    }
+}
@@ -0,0 +1,212 @@
+// =============================================================================
+// Declarative layout — describes the screen split as data instead of code
+//
+// render() never hardcodes Constraints. It asks LayoutConfig to carve the
+// terminal into named areas, then draws whichever panels are present. To
+// change the layout of a cloned app, edit LayoutConfig::default() (or the
+// config) rather than render():
+//
+//   panels     — rows from top to bottom, each with a size. Output and
+//                Input are the template's own; Status (one row), Title
+//                (a one-row header: app name, profile, pane, breadcrumbs),
+//                Menu (a one-row menu bar, see menu.rs) and Hints (the
+//                keys that apply right now, see hints.rs) are optional
+//                extras
+//   side_panel — optional column split off the Output row
+//   minimap    — a thin overview of the scrollback inside Output (minimap.rs)
+//   wrap       — long lines in Output carry on onto the next rows instead
+//                of being cut off at its edge (output.rs)
+//   breakpoints — widths below which the layout gives way, so a narrow
+//                terminal gets fewer panels rather than slivers of all of
+//                them:
+//
+//   [layout.breakpoints]         # narrower than this many columns…
+//   stack_side_panel = 80        # …the side panel goes under Output
+//   hide_side_panel = 50         # …or away (a pane goes over Output)
+//   hide_bars = 60               # …the status, title and hints rows go
+//
+// 0 turns one off. With the status row gone, job gauges move into the
+// input bar's border as they do in a layout without one. The menu bar
+// stays: it's how the menu is found.
+// =============================================================================
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::{Deserialize, Serialize};
+
+// The panels render() knows how to draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelKind {
+    Output, // scrollback of messages
+    Input,  // the command bar
+    Status, // one-line status bar
+    Title,  // one-line header with breadcrumbs
+    Menu,   // one-line menu bar
+    Hints,  // one-line key hints
+}
+
+impl PanelKind {
+    // The one-row extras that breakpoints.hide_bars drops
+    fn is_bar(self) -> bool {
+        matches!(self, Self::Status | Self::Title | Self::Hints)
+    }
+}
+
+// How much room a row (or the side panel) gets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PanelSize {
+    Fill,         // share whatever space is left
+    Length(u16),  // exactly N rows / columns
+    Percent(u16), // N% of the available space
+}
+
+impl PanelSize {
+    fn constraint(self) -> Constraint {
+        match self {
+            PanelSize::Fill => Constraint::Min(1),
+            PanelSize::Length(n) => Constraint::Length(n),
+            PanelSize::Percent(p) => Constraint::Percentage(p),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PanelSpec {
+    pub kind: PanelKind,
+    pub size: PanelSize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SidePanel {
+    pub position: Side,
+    pub width: PanelSize,
+}
+
+const STACKED_PERCENT: u16 = 40; // of the Output row, for a side panel moved under it
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub panels: Vec<PanelSpec>,        // top to bottom
+    pub side_panel: Option<SidePanel>, // split off the Output row
+    pub minimap: bool,                 // inside Output, on its right edge
+    pub wrap: bool,                    // Output's long lines, rather than cutting them off
+    pub breakpoints: Breakpoints,      // narrower than these, panels stack or go
+}
+
+// Terminal widths, in columns; 0 is never
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Breakpoints {
+    pub stack_side_panel: u16,
+    pub hide_side_panel: u16,
+    pub hide_bars: u16,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {
+            stack_side_panel: 80,
+            hide_side_panel: 50,
+            hide_bars: 60,
+        }
+    }
+}
+
+impl Default for LayoutConfig {
+    // The classic template layout: big output area + 3-row input bar
+    fn default() -> Self {
+        Self {
+            panels: vec![
+                PanelSpec {
+                    kind: PanelKind::Output,
+                    size: PanelSize::Fill,
+                },
+                PanelSpec {
+                    kind: PanelKind::Input,
+                    size: PanelSize::Length(3),
+                }, // 1 text + 2 border
+            ],
+            side_panel: None,
+            minimap: false,
+            wrap: false,
+            breakpoints: Breakpoints::default(),
+        }
+    }
+}
+
+// The rectangles render() draws into. A panel missing from the config is None.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Areas {
+    pub output: Option<Rect>,
+    pub input: Option<Rect>,
+    pub status: Option<Rect>,
+    pub title: Option<Rect>,
+    pub menu: Option<Rect>,
+    pub hints: Option<Rect>,
+    pub side: Option<Rect>,
+}
+
+impl LayoutConfig {
+    pub fn split(&self, area: Rect) -> Areas {
+        let narrower = |than: u16| area.width < than;
+        let bars = !narrower(self.breakpoints.hide_bars);
+        let panels: Vec<_> = self
+            .panels
+            .iter()
+            .filter(|p| bars || !p.kind.is_bar())
+            .collect();
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(panels.iter().map(|p| p.size.constraint()))
+            .split(area);
+
+        let mut areas = Areas::default();
+        for (spec, &rect) in panels.iter().zip(rows.iter()) {
+            match spec.kind {
+                PanelKind::Output => areas.output = Some(rect),
+                PanelKind::Input => areas.input = Some(rect),
+                PanelKind::Status => areas.status = Some(rect),
+                PanelKind::Title => areas.title = Some(rect),
+                PanelKind::Menu => areas.menu = Some(rect),
+                PanelKind::Hints => areas.hints = Some(rect),
+            }
+        }
+
+        // The side panel steals a column from the Output row, or where
+        // that's too narrow, the bottom of it
+        let side_panel = self
+            .side_panel
+            .filter(|_| !narrower(self.breakpoints.hide_side_panel));
+        if let (Some(side), Some(output)) = (side_panel, areas.output) {
+            if narrower(self.breakpoints.stack_side_panel) {
+                let [above, below] =
+                    Layout::vertical([Constraint::Min(1), Constraint::Percentage(STACKED_PERCENT)])
+                        .areas(output);
+                areas.output = Some(above);
+                areas.side = Some(below);
+                return areas;
+            }
+            let (constraints, side_index) = match side.position {
+                Side::Left => ([side.width.constraint(), Constraint::Min(1)], 0),
+                Side::Right => ([Constraint::Min(1), side.width.constraint()], 1),
+            };
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(constraints)
+                .split(output);
+            areas.side = Some(cols[side_index]);
+            areas.output = Some(cols[1 - side_index]);
+        }
+
+        areas
+    }
+}
@@ -0,0 +1,2634 @@
+// =============================================================================
+// Ratatui TUI Template — Clone this and customize for each new app
+//
+// Architecture:
+//   1. start()       — parse flags, init terminal, run loop, restore terminal
+//                      (or hand off to headless.rs for --no-tui / piped stdin);
+//                      main.rs just calls it
+//   2. App struct    — all your application state lives here
+//   3. run()         — the core loop: draw → read input → update state;
+//                      run_loop() gets events from a Host (host.rs), so
+//                      e2e.rs can script it against a TestBackend
+//   4. render()      — builds the UI from current state (immediate mode)
+//   5. App::handle() — applies an Input (input.rs) — keys via handle_key()
+//                      and the keymap (keymap.rs) — with no terminal involved
+//   6. execute_command() — YOUR CUSTOM LOGIC GOES HERE
+//      Commands get a Ctx (ctx.rs), not the App: they print and request
+//      changes by sending AppEvents, which the loop applies to App state.
+//
+// To make a new app from this template:
+//   1. Copy the project, rename in Cargo.toml
+//   2. Add fields to App for your state
+//   3. Add your commands in execute_command()
+//   4. Rearrange panels in layout.rs (LayoutConfig), or customize render()
+//      if you need more panels/widgets
+//
+// It's a library with a small binary on top so the fuzz target (fuzz/) and
+// the benchmarks (benches/) can drive App without a terminal; only App,
+// Input, KeyBinding and render() are public.
+//
+// Users can tweak colors, keys, layout etc. in ~/.config/<app>/config.toml —
+// see config.rs for the format.
+// =============================================================================
+
+mod alerts;
+mod animation;
+mod backend;
+mod cli;
+mod clipboard;
+mod clock;
+mod config;
+mod csv;
+mod ctx;
+mod cursor;
+mod debug;
+#[cfg(feature = "docker")]
+mod docker;
+#[cfg(test)]
+mod e2e;
+mod effects;
+mod env;
+#[cfg(feature = "feeds")]
+mod feeds;
+#[cfg(feature = "git")]
+mod git;
+mod grep;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod headless;
+mod hints;
+mod host;
+#[cfg(feature = "http")]
+mod http;
+mod ingest;
+mod input;
+mod input_line;
+mod json;
+#[cfg(feature = "k8s")]
+mod k8s;
+mod keymap;
+mod layout;
+mod log_view;
+mod logging;
+#[cfg(feature = "media")]
+mod media;
+mod menu;
+mod metrics;
+mod minimap;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+mod multiplexer;
+mod net;
+mod notifications;
+mod output;
+mod overlay;
+mod pane;
+mod paths;
+#[cfg(feature = "sysinfo")]
+mod ps;
+mod recording;
+mod recovery;
+mod screen_reader;
+#[cfg(feature = "serial")]
+mod serial;
+mod session;
+mod shell;
+mod smoke;
+#[cfg(feature = "sqlite")]
+mod sql;
+mod ssh;
+#[cfg(feature = "sysinfo")]
+mod sysinfo;
+mod tail;
+#[cfg(test)]
+mod testing;
+mod theme;
+mod toast;
+mod validate;
+mod watch;
+#[cfg(feature = "websocket")]
+mod websocket;
+mod widgets;
+
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    io::{self, IsTerminal},
+    mem,
+    path::Path,
+    process::ExitCode,
+    sync::mpsc::{self, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+use color_eyre::{
+    Report, Result,
+    eyre::{WrapErr, eyre},
+};
+use crossterm::{
+    cursor::SetCursorStyle,
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute,
+    style::Print,
+    terminal::SetTitle,
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::Backend,
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use regex::RegexBuilder;
+
+use alerts::Alerts;
+use animation::Reveal;
+use cli::Args;
+use clipboard::Clipboard;
+use config::{Config, ConfigWatcher};
+use ctx::{AppEvent, Connections, Ctx, Spawner, Status};
+use debug::DebugOverlay;
+use effects::{Effects, Files};
+use host::{Host, Tty};
+pub use input::Input;
+use input_line::InputLine;
+use keymap::Action;
+pub use keymap::KeyBinding;
+use layout::PanelKind;
+use menu::MenuBar;
+use metrics::Metrics;
+use output::{Filter, Scrollback, Shown};
+use overlay::{Modal, Outcome};
+use pane::{Loading, Pane, Split};
+use recording::{Recorder, Replay};
+use session::{Session, StateExport};
+use theme::Theme;
+use toast::{Level, Toasts};
+use validate::Problem;
+use widgets::{
+    Calendar, ChartView, Choice, ColorPicker, Confirm, Date, DiffView, EditorView, FilePicker,
+    Form, HexView, MarkdownView, Prompt, Select, TableView, TreeNode, TreeView,
+};
+
+// =============================================================================
+// App State — add whatever your app needs here
+// =============================================================================
+pub struct App {
+    running: bool,
+    input: InputLine,                 // what the user is currently typing
+    messages: Scrollback,             // output history / log
+    output_top: Option<usize>,        // first row shown if scrolled back; None follows the end
+    output_filter: Option<Filter>,    // `filter`: Output shows only the lines matching it
+    output_rows: Cell<usize>,         // lines Output fits, as of the last render
+    output_width: Cell<u16>,          // …and columns, which wrapping needs
+    minimap_area: Cell<Option<Rect>>, // where the minimap was drawn, for clicks
+    history: Vec<String>,             // submitted commands, oldest first
+    history_pos: Option<usize>,       // where Up/Down is in `history`, if browsing
+    unsaved_history: Vec<String>,     // not yet in the history file (see recovery.rs)
+    vars: BTreeMap<String, String>,   // set with `set name value`
+    config: Config,
+    config_watcher: Option<ConfigWatcher>, // None when there's no config location
+    key_overrides: Vec<(KeyBinding, Option<Action>)>, // `bind`/`unbind` this session
+    last_autosave: Instant,
+    overlays: Vec<Modal>,        // popups, topmost last; it gets the keyboard
+    pane: Option<Box<dyn Pane>>, // a command's view, beside or over Output
+    pane_focused: bool,          // keys go to the pane before the keymap
+    terminal_focused: bool,      // as the terminal last said (see notifications.rs)
+    throttled: bool,             // the last drain left events waiting (see run_loop)
+    monochrome: bool,            // NO_COLOR or --no-color (see theme.rs)
+    mouse: bool,                 // `mouse on|off`: let the minimap capture it
+    progress: BTreeMap<u64, (String, f64, Instant)>, // running jobs' (label, fraction, started)
+    metrics: Metrics,            // drawn in the side panel
+    toasts: Toasts,              // top-right notices, expired on tick
+    alerts: Alerts,              // the bell / flash, waiting for the main loop
+    debug: DebugOverlay,         // F12
+    breadcrumbs: Vec<String>,    // shown in the title bar; set with `crumbs`
+    clipboard: Clipboard,
+    connections: Connections, // the optional integrations' (see ctx.rs)
+    effects: Effects,         // what commands do outside the app (see effects.rs)
+    spawner: Spawner,
+    tx: Sender<AppEvent>,   // handed to commands via Ctx
+    rx: Receiver<AppEvent>, // drained by the main loop
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    pub fn new() -> Self {
+        let mut app = Self::unloaded();
+        app.load_profile();
+        app
+    }
+
+    // With the defaults, before the profile's config and history are read
+    // (and with no watcher on the config file): what tests start from
+    fn unloaded() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            running: true,
+            input: InputLine::default(),
+            messages: Scrollback::default(),
+            output_top: None,
+            output_filter: None,
+            output_rows: Cell::new(1),
+            output_width: Cell::new(1),
+            minimap_area: Cell::new(None),
+            history: Vec::new(),
+            history_pos: None,
+            unsaved_history: Vec::new(),
+            vars: BTreeMap::new(),
+            config: Config::default(),
+            config_watcher: None,
+            key_overrides: Vec::new(),
+            last_autosave: clock::now(),
+            overlays: Vec::new(),
+            pane: None,
+            pane_focused: false,
+            terminal_focused: true,
+            throttled: false,
+            monochrome: theme::Depth::detect() == theme::Depth::Mono,
+            mouse: true,
+            progress: BTreeMap::new(),
+            metrics: Metrics::default(),
+            toasts: Toasts::default(),
+            alerts: Alerts::default(),
+            debug: DebugOverlay::default(),
+            breadcrumbs: Vec::new(),
+            clipboard: Clipboard::default(),
+            connections: Connections::default(),
+            effects: Effects::default(),
+            spawner: Spawner::new(tx.clone()),
+            tx,
+            rx,
+        }
+    }
+
+    // Everything the user does goes through here (see input.rs), followed by
+    // whatever it caused. True if the screen needs redrawing.
+    pub fn handle(&mut self, input: Input) -> bool {
+        let changed = match input {
+            // Windows reports every key coming up, as does the kitty
+            // keyboard protocol: only a binding for that acts on one
+            Input::Key(key) if key.is_release() => match self.config.keymap.get_binding(key) {
+                Some(action) => {
+                    perform(self, action.clone());
+                    true
+                }
+                None => false,
+            },
+            Input::Key(key) => {
+                handle_key(self, key);
+                true
+            }
+            Input::Click { column, row } => handle_click(self, column, row),
+            Input::Focus(focused) => {
+                self.terminal_focused = focused;
+                focused // to catch up on what wasn't drawn meanwhile
+            }
+            Input::Paste(text) => {
+                paste(self, &text);
+                true
+            }
+        };
+        self.drain_events() || changed
+    }
+
+    // What's in the input bar
+    pub fn input(&self) -> String {
+        self.input.to_string()
+    }
+
+    // Add a line to Output, as a command's ctx.print() would
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.messages.push(line.into());
+    }
+
+    fn greet(&mut self) {
+        self.messages
+            .push("Welcome! Type 'help' for available commands.");
+        self.messages.push("Press Esc to quit.");
+    }
+
+    // (Re)load everything that lives in the profile's directories: the
+    // config file (and its watcher) and the input history
+    fn load_profile(&mut self) {
+        self.config = Config::default();
+        self.config_watcher = config::config_path().map(ConfigWatcher::new);
+
+        // Load the config file if there is one; a broken file is reported
+        // but doesn't stop the app from starting with defaults
+        if let Some(watcher) = &self.config_watcher
+            && watcher.path().exists()
+        {
+            match Config::load(watcher.path()) {
+                Ok((config, problems)) => {
+                    self.config = config;
+                    self.report_config_problems(watcher.path(), &problems);
+                }
+                Err(err) => self.report_config_error(&err),
+            }
+        }
+        self.apply_key_overrides();
+
+        self.history = session::load_history(&*self.effects.files);
+        self.history_pos = None;
+        let excess = self.history.len().saturating_sub(self.config.history_size);
+        self.history.drain(..excess);
+    }
+
+    // Called on the way out (and before switching profiles)
+    fn save_history(&mut self) {
+        match session::save_history(&*self.effects.files, &self.history) {
+            Ok(()) => self.unsaved_history.clear(),
+            Err(err) => tracing::warn!("saving history: {err:#}"),
+        }
+    }
+
+    // Parse errors span several lines (the offending TOML is quoted with a
+    // caret under it), so send them line by line
+    fn report_config_error(&self, err: &Report) {
+        tracing::warn!("config error: {err:#}");
+        for (i, line) in format!("{err:#}").trim_end().lines().enumerate() {
+            let prefix = if i == 0 { "  Config error: " } else { "    " };
+            let _ = self.tx.send(AppEvent::Print(format!("{prefix}{line}")));
+        }
+    }
+
+    fn report_config_problems(&self, path: &Path, problems: &[Problem]) {
+        if problems.is_empty() {
+            return;
+        }
+        let _ = self.tx.send(AppEvent::Print(format!(
+            "  {} problem(s) in {} (using defaults for these):",
+            problems.len(),
+            path.display()
+        )));
+        for problem in problems {
+            tracing::warn!("{}: {problem}", path.display());
+            let _ = self.tx.send(AppEvent::Print(format!("    {problem}")));
+        }
+    }
+
+    // Echo a command to the output and run it, as if typed into the input bar
+    fn submit(&mut self, command: &str) -> Status {
+        self.messages.push(format!("> {}", command));
+        let status = execute_command(&mut self.ctx(), command);
+        if status == Status::Failure {
+            self.alerts.raise(self.config.alerts.on_error);
+        }
+        self.drain_events();
+        status
+    }
+
+    // Remember a submitted command for Up/Down, capped at history_size
+    fn remember(&mut self, command: &str) {
+        self.history_pos = None;
+        if self.history.last().is_some_and(|last| last == command) {
+            return; // don't fill history with repeats
+        }
+        self.history.push(command.to_string());
+        self.unsaved_history.push(command.to_string());
+        let excess = self.history.len().saturating_sub(self.config.history_size);
+        self.history.drain(..excess);
+    }
+
+    // Up = older (-1), Down = newer (+1). Walking past the newest entry
+    // leaves history and clears the input.
+    fn browse_history(&mut self, step: isize) {
+        let pos = match (self.history_pos, step < 0) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => return,
+            (Some(pos), true) => Some(pos.saturating_sub(1)),
+            (Some(pos), false) => Some(pos + 1).filter(|&p| p < self.history.len()),
+        };
+        self.history_pos = pos;
+        match pos {
+            Some(p) => self.input.set(&self.history[p]),
+            None => self.input.clear(),
+        }
+    }
+
+    // A popup or toast is still opening (animation.rs)
+    fn animating(&self) -> bool {
+        self.animate()
+            && (self.toasts.animating()
+                || self.overlays.iter().any(|m| animation::running(m.opened())))
+    }
+
+    fn animate(&self) -> bool {
+        !self.config.reduced_motion && !self.screen_reader()
+    }
+
+    // See screen_reader.rs
+    fn screen_reader(&self) -> bool {
+        self.config.screen_reader || screen_reader::forced()
+    }
+
+    // A notice in the corner, or in screen reader mode a line in Output
+    fn toast(&mut self, level: Level, text: String) {
+        match self.screen_reader() {
+            true => self.messages.push(format!("  {level}: {text}")),
+            false => self.toasts.push(level, text),
+        }
+    }
+
+    // Drop down menu `index` of the menu bar; its item's command runs as if
+    // typed
+    fn open_menu(&mut self, index: usize) {
+        if self.config.menu.is_empty() {
+            return;
+        }
+        let config = &self.config;
+        let bar = MenuBar::new(&config.menu, &config.keymap, &config.layout, index);
+        self.overlays.push(Modal::new(bar, |ctx, command| {
+            execute_command(ctx, &command);
+        }));
+    }
+
+    // The lines Output shows: all of them, or those `filter` lets through,
+    // wrapped at Output's width if [layout] says to (or for a screen reader)
+    fn shown(&self) -> Shown<'_> {
+        let wrap =
+            (self.config.layout.wrap || self.screen_reader()).then(|| self.output_width.get());
+        Shown::new(&self.messages, self.output_filter.as_ref(), wrap)
+    }
+
+    // What has the keyboard: the top popup, a focused pane, or the input bar
+    fn mode(&self) -> cursor::Mode {
+        match (self.overlays.is_empty(), self.pane_focused) {
+            (false, _) => cursor::Mode::Popup,
+            (true, true) => cursor::Mode::Pane,
+            (true, false) => cursor::Mode::Input,
+        }
+    }
+
+    // The terminal window's title: the app's name, then what it's up to —
+    // the newest job with a gauge, else the pane, else the breadcrumbs
+    fn window_title(&self) -> String {
+        let mut title = env!("CARGO_PKG_NAME").to_string();
+        if let Some(profile) = paths::profile() {
+            title += &format!(" ({profile})");
+        }
+        let doing = match (self.progress.values().next_back(), &self.pane) {
+            (Some((label, ..)), _) => Some(label.clone()),
+            (None, Some(pane)) => Some(pane.title()),
+            (None, None) => (!self.breadcrumbs.is_empty()).then(|| self.breadcrumbs.join(" › ")),
+        };
+        if let Some(doing) = doing {
+            title += &format!(" — {doing}");
+        }
+        title
+    }
+
+    // Scroll Output so `top` is its first row; at (or past) the end it
+    // follows new output again
+    fn scroll_output_to(&mut self, top: usize) {
+        let last = self.shown().rows().saturating_sub(self.output_rows.get());
+        self.output_top = (top < last).then_some(top);
+    }
+
+    // The terminal has settled at a new size: work out Output's rows and
+    // columns now rather than at the next draw, and keep the message at the
+    // top of a scrolled-back Output there, though re-wrapping moves its row
+    fn resized(&mut self, area: Rect) {
+        let anchor = self.output_top.map(|top| self.shown().at_row(top).0);
+        if let Some(output) = self.config.layout.split(area).output {
+            let (text, _) = output_areas(self, output);
+            self.output_width.set(text.width.max(1));
+            self.output_rows.set(usize::from(text.height).max(1));
+        }
+        if let Some(n) = anchor {
+            let top = self.shown().row_of(n);
+            self.scroll_output_to(top);
+        }
+    }
+
+    fn scroll_output_by(&mut self, step: isize) {
+        let last = self.shown().rows().saturating_sub(self.output_rows.get());
+        let top = self.output_top.unwrap_or(last);
+        self.scroll_output_to(top.saturating_add_signed(step));
+    }
+
+    // The slice of the app a command is allowed to use
+    fn ctx(&mut self) -> Ctx<'_> {
+        Ctx::new(
+            &self.config,
+            &mut self.vars,
+            &mut self.clipboard,
+            &self.spawner,
+            &mut self.connections,
+            &self.effects,
+            &self.tx,
+        )
+    }
+
+    // Apply what commands and background jobs have sent, for up to
+    // DRAIN_BUDGET; the rest waits for the next call (and `throttled` says
+    // some was left). Returns true if anything arrived.
+    fn drain_events(&mut self) -> bool {
+        let started = clock::now();
+        let mut changed = false;
+        self.throttled = false;
+        while let Ok(event) = self.rx.try_recv() {
+            self.apply(event);
+            changed = true;
+            if clock::since(started) >= DRAIN_BUDGET {
+                self.throttled = true;
+                break;
+            }
+        }
+        changed
+    }
+
+    fn apply(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Print(line) => self.messages.push(line),
+            AppEvent::Toast(level, text) => {
+                if level == Level::Error {
+                    self.alerts.raise(self.config.alerts.on_error);
+                }
+                self.toast(level, text);
+            }
+            AppEvent::SetBreadcrumbs(crumbs) => self.breadcrumbs = crumbs,
+            AppEvent::SetMouse(on) => {
+                self.mouse = on.unwrap_or(!self.mouse);
+                let note = match (self.mouse, self.config.layout.minimap) {
+                    (false, _) => {
+                        "  Mouse off: the terminal selects text again (`mouse on` undoes)"
+                    }
+                    (true, true) => {
+                        "  Mouse on: the minimap takes clicks (`mouse off` to select text)"
+                    }
+                    (true, false) => {
+                        "  Mouse on, for when the minimap is ([layout] minimap = true)"
+                    }
+                };
+                self.ctx().print(note);
+            }
+            AppEvent::Clear => {
+                self.messages.clear();
+                self.output_top = None;
+            }
+            AppEvent::Quit => self.running = false,
+            AppEvent::FilterOutput(filter) => {
+                self.output_filter = filter.map(Filter::new);
+                self.output_top = None;
+            }
+            AppEvent::SaveSession(name) => {
+                let result = self.session().save(&*self.effects.files, &name);
+                self.ctx().print(match result {
+                    Ok(path) => format!("  Saved session '{name}' to {}", path.display()),
+                    Err(err) => {
+                        tracing::warn!("saving session '{name}': {err:#}");
+                        format!("  Session error: {err:#}")
+                    }
+                });
+            }
+            AppEvent::LoadSession(name) => {
+                match Session::load(&*self.effects.files, name.as_deref()) {
+                    Ok((name, session)) => {
+                        self.restore(session);
+                        self.ctx().print(format!("  Loaded session '{name}'"));
+                    }
+                    Err(err) => {
+                        tracing::warn!("loading session: {err:#}");
+                        self.ctx().print(format!("  Session error: {err:#}"));
+                    }
+                }
+            }
+            AppEvent::ExportState(path) => {
+                let export = StateExport::new(self.session(), self.config.clone());
+                match export.write(&*self.effects.files, &path) {
+                    Ok(()) => self
+                        .ctx()
+                        .print(format!("  Exported state to {}", path.display())),
+                    Err(err) => {
+                        tracing::warn!("exporting state: {err:#}");
+                        self.ctx().print(format!("  Export error: {err:#}"));
+                    }
+                }
+            }
+            AppEvent::ImportState(path) => match StateExport::read(&*self.effects.files, &path) {
+                Ok((export, problems)) => {
+                    self.restore(export.session);
+                    // Not what someone else's file says to run
+                    self.config = Config {
+                        startup: mem::take(&mut self.config.startup),
+                        open_command: mem::take(&mut self.config.open_command),
+                        ..export.settings
+                    };
+                    self.apply_key_overrides();
+                    self.ctx().print(format!(
+                        "  Imported state from {} (written by {}); kept your own \
+                         startup and open_command",
+                        path.display(),
+                        export.app
+                    ));
+                    self.report_config_problems(&path, &problems);
+                }
+                Err(err) => {
+                    tracing::warn!("importing state: {err:#}");
+                    self.ctx().print(format!("  Import error: {err:#}"));
+                }
+            },
+            AppEvent::SwitchProfile(name) => {
+                self.save_history();
+                paths::set_profile(name.as_deref());
+                self.load_profile();
+                tracing::info!(
+                    profile = name.as_deref().unwrap_or("default"),
+                    "switched profile"
+                );
+                self.ctx().print(format!(
+                    "  Switched to profile '{}'",
+                    name.as_deref().unwrap_or("default")
+                ));
+            }
+            AppEvent::Recover { discard: true } => {
+                recovery::discard();
+                self.ctx().print("  Discarded the recovered input");
+            }
+            AppEvent::Recover { discard: false } => match recovery::pending() {
+                Some(recovered) => {
+                    self.input.set(&recovered.input);
+                    self.history.extend(recovered.history.iter().cloned());
+                    self.unsaved_history.extend(recovered.history);
+                    self.save_history();
+                    recovery::discard();
+                    self.ctx().print("  Restored the unsaved input and history");
+                }
+                None => self.ctx().print("  Nothing to recover"),
+            },
+            AppEvent::SetTheme(theme) => self.config.theme = *theme,
+            AppEvent::Bind(key, action) => {
+                self.key_overrides.retain(|(k, _)| *k != key);
+                self.key_overrides.push((key, action));
+                self.apply_key_overrides();
+            }
+            AppEvent::SaveBindings => self.save_bindings(),
+            AppEvent::Progress {
+                id,
+                label,
+                fraction,
+            } => {
+                let started = self.progress.get(&id).map_or_else(clock::now, |j| j.2);
+                self.progress.insert(id, (label, fraction, started));
+            }
+            AppEvent::ProgressDone(id) => {
+                self.progress.remove(&id);
+            }
+            // One spawned outside any command has no name to announce
+            AppEvent::JobDone { label, .. } if label.is_empty() => {}
+            AppEvent::JobDone { label, started } => {
+                let took = clock::since(started);
+                if took >= self.config.alerts.long_job() {
+                    self.alerts.raise(self.config.alerts.on_long_job);
+                }
+                if took >= self.config.notifications.after() {
+                    let summary = notifications::summary(&label, took);
+                    self.messages.push_highlighted(format!("  {summary}"));
+                    if !self.terminal_focused {
+                        notifications::job_finished(&self.config.notifications, &label, took);
+                    }
+                }
+            }
+            AppEvent::Metric { name, value, max } => self.metrics.push(name, value, max),
+            AppEvent::ClearMetrics => self.metrics.clear(),
+            AppEvent::Run(command) => {
+                self.messages.push(format!("> {command}"));
+                if execute_command(&mut self.ctx(), &command) == Status::Failure {
+                    self.alerts.raise(self.config.alerts.on_error);
+                }
+            }
+            AppEvent::Insert(text) => {
+                self.input.insert_str(&one_line(&text));
+                self.pane_focused = false; // so it's where the text goes
+            }
+            AppEvent::Open(modal) => self.overlays.push(modal),
+            AppEvent::ShowPane(pane) => {
+                self.pane = Some(pane);
+                self.pane_focused = true;
+            }
+            AppEvent::ClosePane => {
+                self.pane = None;
+                self.pane_focused = false;
+            }
+        }
+    }
+
+    // Everything `session save` persists
+    fn session(&self) -> Session {
+        Session {
+            messages: self.messages.iter().collect(),
+            history: self.history.clone(),
+            vars: self.vars.clone(),
+        }
+    }
+
+    fn apply_key_overrides(&mut self) {
+        for (key, action) in &self.key_overrides {
+            match action {
+                Some(action) => self.config.keymap.bind(*key, action.clone()),
+                None => {
+                    self.config.keymap.unbind(*key);
+                }
+            }
+        }
+    }
+
+    fn save_bindings(&mut self) {
+        let Some(path) = config::config_path() else {
+            self.ctx()
+                .print("  Can't find a config directory (is $HOME set?)");
+            return;
+        };
+        if self.key_overrides.is_empty() {
+            self.ctx().print("  No bindings changed this session");
+            return;
+        }
+
+        // An unbind is written as an empty action (see keymap.rs)
+        let entries: Vec<(String, String)> = self
+            .key_overrides
+            .iter()
+            .map(|(key, action)| {
+                let action = action.as_ref().map(Action::to_string);
+                (key.to_string(), action.unwrap_or_default())
+            })
+            .collect();
+        match config::save_keymap(&path, &entries) {
+            Ok(()) => self.ctx().print(format!(
+                "  Saved {} binding(s) to {}",
+                entries.len(),
+                path.display()
+            )),
+            Err(err) => self.report_config_error(&err),
+        }
+    }
+
+    fn restore(&mut self, session: Session) {
+        self.messages = session.messages.into();
+        self.output_top = None;
+        self.history = session.history;
+        self.history_pos = None;
+        self.vars = session.vars;
+    }
+
+    // Quietly snapshot the session and history; problems only go to the log
+    fn autosave(&mut self) {
+        self.last_autosave = clock::now();
+        match self
+            .session()
+            .save(&*self.effects.files, session::AUTOSAVE_NAME)
+        {
+            Ok(path) => tracing::debug!("autosaved to {}", path.display()),
+            Err(err) => {
+                tracing::warn!("autosave failed: {err:#}");
+                self.toasts
+                    .push(Level::Warn, format!("Autosave failed: {err:#}"));
+            }
+        }
+        self.save_history();
+    }
+
+    // How often to tick, `quiet` after the screen last changed: tick_rate_ms
+    // while something moves with the ticks — a pane (charts, logs and tables
+    // poll on tick), a job's gauge or output, toasts, an alert, the debug
+    // overlay's fps — or has only just stopped, and less often otherwise,
+    // or while nobody's looking
+    fn tick_rate(&self, quiet: Duration) -> Duration {
+        if !self.terminal_focused && self.config.slow_when_unfocused {
+            return self.config.tick_rate() * IDLE_TICKS;
+        }
+        let busy = self.pane.is_some()
+            || !self.progress.is_empty()
+            || self.spawner.active() > 0
+            || !self.toasts.is_empty()
+            || self.alerts.flashing()
+            || self.debug.shown;
+        let rate = self.config.tick_rate();
+        match busy || quiet < IDLE_AFTER {
+            true => rate,
+            false => rate * IDLE_TICKS,
+        }
+    }
+
+    // Called every tick (tick_rate_ms). Returns true if something changed on screen.
+    fn on_tick(&mut self) -> bool {
+        let mut changed = self.toasts.expire();
+        changed |= self.debug.shown; // its fps goes stale otherwise
+        changed |= self.alerts.expire();
+        #[cfg(feature = "media")]
+        {
+            changed |= self.connections.media.changed();
+        }
+
+        if let Some(interval) = self.config.session.autosave_interval()
+            && clock::since(self.last_autosave) >= interval
+        {
+            self.autosave();
+            changed = true; // in case it failed and says so
+        }
+
+        let Some(watcher) = &mut self.config_watcher else {
+            return changed;
+        };
+        let Some(result) = watcher.poll() else {
+            return changed;
+        };
+
+        // Apply the new config live; on a parse error keep the old one
+        match result {
+            Ok((config, problems)) => {
+                let path = watcher.path().to_path_buf();
+                self.config = config;
+                self.apply_key_overrides(); // session binds win over the file
+                let reloaded = format!("Config reloaded from {}", path.display());
+                self.toast(Level::Info, reloaded);
+                self.report_config_problems(&path, &problems);
+            }
+            Err(err) => self.report_config_error(&err),
+        }
+        true
+    }
+}
+
+// =============================================================================
+// Entry point
+// =============================================================================
+pub fn start() -> Result<ExitCode> {
+    // Install color-eyre for nice panic/error backtraces
+    color_eyre::install()?;
+
+    let args = Args::parse();
+    paths::set_profile(args.profile.as_deref()); // before anything looks up a path
+    if args.no_color {
+        theme::set_no_color();
+    }
+    if args.screen_reader {
+        screen_reader::force();
+    }
+    // Logging is a nice-to-have: if the log file can't be opened, say so and carry on
+    let _log_guard = logging::init(args.log_level.as_deref()) // flushes logs on drop
+        .inspect_err(|err| eprintln!("warning: logging disabled: {err:#}"))
+        .ok();
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "starting");
+
+    // Before the stdin check: CI runs it with no terminal (see smoke.rs)
+    if args.smoke_test {
+        return smoke::run();
+    }
+
+    // `echo hello | myapp` — run the piped commands and exit with the
+    // last one's status (see headless.rs)
+    if !io::stdin().is_terminal() && !args.ingest {
+        return headless::run(headless::Mode::Batch);
+    }
+    if args.no_tui {
+        return headless::run(headless::Mode::Repl);
+    }
+
+    // Before the screen changes, so a bad path is reported plainly
+    let recorder = match &args.record {
+        Some(path) => Some(
+            Recorder::create(path)
+                .wrap_err_with(|| format!("can't record to {}", path.display()))?,
+        ),
+        None => None,
+    };
+    let replay = match &args.replay {
+        Some(path) => Some(
+            Replay::load(&effects::System, path)
+                .map_err(|why| eyre!("can't replay {}: {why}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    // backend::init() does three things (with crossterm, or as the
+    // features choose: see backend.rs):
+    //   1. Switches to the alternate screen (so your app doesn't trash scroll history)
+    //   2. Enables raw mode (keypresses arrive immediately, no line buffering)
+    //   3. Returns a Terminal handle you draw to, and the keyboard to read
+    // With --inline, the viewport is the bottom rows instead, under the
+    // shell's scrollback, and raw mode is all that changes
+    let (terminal, keyboard) = backend::init(args.inline)?;
+
+    // After backend::init, so the stash happens before its hook restores the terminal
+    recovery::install_panic_hook();
+
+    let inline = args.inline.is_some();
+    let result = run(terminal, keyboard, args.ingest, inline, recorder, replay);
+
+    // ALWAYS restore the terminal, even if the app errored.
+    // This undoes raw mode + alternate screen so the user's shell is normal again.
+    backend::restore();
+
+    result.map(|()| ExitCode::SUCCESS)
+}
+
+// =============================================================================
+// Core loop: draw → read → update
+//
+// The loop wakes up at least once per tick (tick_rate_ms in the config) even
+// when the user is idle, so background work (like noticing the config file
+// changed) gets a chance to run. With nothing on screen that moves with the
+// ticks and nothing changed for IDLE_AFTER, they come IDLE_TICKS times less
+// often (see App::tick_rate), to spare the battery. So do they while the
+// terminal window is out of focus (slow_when_unfocused), and then nothing is
+// drawn either: the screen catches up the moment focus comes back.
+//
+// Resize events are debounced: dragging a terminal window fires dozens of
+// Event::Resize in a row, and redrawing (re-wrapping, re-scrolling) for each
+// one makes the UI stutter. Instead we remember that a resize is pending and
+// only relayout once the size has been stable for RESIZE_DEBOUNCE, when
+// App::resized catches the scroll position up with the new width. Below
+// MIN_WIDTH×MIN_HEIGHT, render() says so instead of drawing the panels.
+//
+// Likewise for input: once one event arrives, every other one that's
+// already waiting is handled too before drawing, so a held key or a pasted
+// burst costs one redraw rather than one per keystroke. A flood that never
+// lets up still gets a frame every BATCH_LIMIT.
+//
+// And for background output: a job printing thousands of lines a second
+// would otherwise have each wake-up spend all its time appending them. Each
+// drain takes what arrives in DRAIN_BUDGET and leaves the rest for the
+// next, which comes a frame later rather than a tick; while there's a
+// backlog, Output's title says "output throttled" and the screen is
+// redrawn at most every THROTTLED_FRAME, so keys still get through.
+//
+// Where the terminal has the kitty keyboard protocol (and kitty_keyboard
+// isn't off), it's pushed at the start and popped at the end, so keys
+// legacy escapes can't tell apart come through as themselves (keymap.rs).
+//
+// The window's title follows App::window_title. The one it had before is
+// pushed onto the terminal's title stack at the start and popped back at
+// the end (terminals without one ignore both).
+// =============================================================================
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(50);
+const BATCH_LIMIT: Duration = animation::FRAME;
+const IDLE_AFTER: Duration = Duration::from_secs(2);
+const IDLE_TICKS: u32 = 4;
+const DRAIN_BUDGET: Duration = Duration::from_millis(10);
+const THROTTLED_FRAME: Duration = Duration::from_millis(100);
+const KITTY_FLAGS: KeyboardEnhancementFlags = KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+    .union(KeyboardEnhancementFlags::REPORT_EVENT_TYPES);
+const PUSH_TITLE: &str = "\x1b[22;0t"; // xterm's window ops; crossterm has neither
+const POP_TITLE: &str = "\x1b[23;0t";
+
+fn run(
+    mut terminal: backend::Screen,
+    keyboard: backend::Keyboard,
+    ingest: bool,
+    inline: bool,
+    recorder: Option<Recorder>,
+    replay: Option<Replay>,
+) -> Result<()> {
+    #[cfg(feature = "images")]
+    widgets::image_view::detect(); // asks the terminal, so before any events are read
+    let mut app = App::new();
+    #[cfg(feature = "media")]
+    app.connections.media.watch(); // for the status bar, so not in batch mode
+    if app.config.watch_clipboard {
+        #[cfg(feature = "system-clipboard")]
+        app.clipboard.watch();
+        #[cfg(not(feature = "system-clipboard"))]
+        app.messages
+            .push("  watch_clipboard needs the `system-clipboard` feature");
+    }
+    app.greet();
+    app.drain_events(); // startup problems (e.g. config errors) go after the greeting
+    if ingest {
+        ingest::start(app.tx.clone()); // ahead of the session, which could be large
+    }
+    let saved = session::list(&*app.effects.files).unwrap_or_default();
+    if app.config.session.autoload && !saved.is_empty() {
+        app.apply(AppEvent::LoadSession(None));
+        app.drain_events();
+    }
+    if let Some(recovered) = recovery::pending() {
+        app.messages
+            .push("  Found unsaved work from a session that ended unexpectedly:");
+        if !recovered.input.is_empty() {
+            app.messages.push(format!("    input: {}", recovered.input));
+        }
+        if !recovered.history.is_empty() {
+            app.messages.push(format!(
+                "    {} command(s) missing from history",
+                recovered.history.len()
+            ));
+        }
+        app.messages
+            .push("  Type 'recover' to restore it, or 'recover discard' to drop it.");
+    }
+    for command in app.config.startup.clone() {
+        app.submit(&command);
+    }
+
+    let mut tty = Tty::new(keyboard, recorder, replay, app.tx.clone());
+    let result = run_loop(&mut terminal, &mut app, &mut tty);
+    // The shell's prompt comes back where the viewport was. Failing that
+    // isn't worth losing the history and recovery below for.
+    if inline && let Err(err) = terminal.clear() {
+        tracing::warn!("clearing the inline viewport: {err}");
+    }
+    app.save_history();
+    // The terminal hung up (SSH dropped) or we were killed: keep the input
+    // line for next time
+    if tty.done() {
+        recovery::record(&app.input.to_string(), &app.unsaved_history);
+        recovery::stash();
+    }
+    result
+}
+
+// Until the app quits or the host is done (see host.rs)
+fn run_loop<B>(terminal: &mut Terminal<B>, app: &mut App, host: &mut impl Host) -> Result<()>
+where
+    B: Backend,
+    B::Error: Send + Sync + 'static,
+{
+    let mut last_tick = clock::now();
+    let mut pending_resize: Option<Instant> = None; // when the last resize arrived
+
+    // Draw the entire UI based on current state
+    terminal.draw(|frame| render(frame, app))?;
+    let mut mouse_captured = false; // only while the minimap wants clicks, and may have them
+    if host.reports_focus() {
+        execute!(&mut host.out(), EnableFocusChange)?; // for notifications.rs
+    }
+    execute!(&mut host.out(), EnableBracketedPaste)?; // a paste as one Input::Paste
+    // ctrl-enter, shift-enter and key releases, told apart (see keymap.rs)
+    let kitty = app.config.kitty_keyboard && host.kitty_keyboard();
+    if kitty {
+        execute!(&mut host.out(), PushKeyboardEnhancementFlags(KITTY_FLAGS))?;
+    }
+    execute!(&mut host.out(), Print(PUSH_TITLE))?;
+    let mut title = String::new(); // as last set
+    let mut cursor = SetCursorStyle::DefaultUserShape; // likewise
+    let mut was_animating = false;
+    let mut last_draw = clock::now();
+    let mut owed_redraw = false; // put off while throttled
+    let mut last_change = clock::now(); // for the idle tick rate
+    capture_mouse(app, host, &mut mouse_captured)?;
+
+    while app.running && !host.done() {
+        // Sleep until the next tick, or the end of the resize window if sooner
+        let tick_rate = app.tick_rate(clock::since(last_change));
+        let mut timeout = tick_rate.saturating_sub(clock::since(last_tick));
+        if let Some(last) = pending_resize {
+            timeout = timeout.min(RESIZE_DEBOUNCE.saturating_sub(clock::since(last)));
+        }
+        // An animation wants frames, and one more once it's done
+        let animating = app.animating() && app.terminal_focused;
+        if animating || app.throttled {
+            timeout = timeout.min(animation::FRAME);
+        }
+        let mut needs_redraw = animating || was_animating || owed_redraw;
+        was_animating = animating;
+
+        // Wait for the user to do something (key press, mouse, resize), then
+        // take whatever else has already arrived
+        let mut next = host.event(timeout)?;
+        let batch = clock::now();
+        let mut typed = false;
+        while let Some(event) = next {
+            app.debug.event(&event);
+            match Input::from_event(&event) {
+                Some(input) => {
+                    typed |= matches!(&input, Input::Key(key) if !key.is_release())
+                        || matches!(input, Input::Paste(_));
+                    needs_redraw |= app.handle(input);
+                }
+                // Just note it; the relayout happens once the burst settles
+                None if matches!(event, Event::Resize(..)) => pending_resize = Some(clock::now()),
+                None => {}
+            }
+            if !app.running || host.done() || clock::since(batch) >= BATCH_LIMIT {
+                break;
+            }
+            next = host.event(Duration::ZERO)?;
+        }
+        if typed {
+            host.unsaved(&app.input.to_string(), &app.unsaved_history);
+        }
+
+        // No resize for a full debounce window — the size has settled
+        if let Some(last) = pending_resize
+            && clock::since(last) >= RESIZE_DEBOUNCE
+        {
+            pending_resize = None;
+            terminal.autoresize()?;
+            app.resized(terminal.get_frame().area()); // the viewport, if --inline
+            needs_redraw = true;
+        }
+
+        if clock::since(last_tick) >= tick_rate {
+            last_tick = clock::now();
+            needs_redraw |= app.on_tick();
+            needs_redraw |= !app.progress.is_empty(); // gauges may be cycling
+            needs_redraw |= app.pane.as_mut().is_some_and(|p| p.tick());
+        }
+
+        // Pick up output from background jobs
+        needs_redraw |= app.drain_events();
+        app.alerts.ring(host.out())?;
+
+        // While a resize is still settling, keystrokes update state but the
+        // redraw waits for the final size; while output is throttled, it
+        // waits for THROTTLED_FRAME to pass, and while the window is out of
+        // focus, for focus
+        if needs_redraw {
+            last_change = clock::now();
+        }
+        capture_mouse(app, host, &mut mouse_captured)?;
+        let now_cursor = app.config.cursor.style(app.mode());
+        if now_cursor != cursor {
+            execute!(&mut host.out(), now_cursor)?;
+            cursor = now_cursor;
+        }
+        let now_title = app.window_title();
+        if now_title != title {
+            execute!(&mut host.out(), SetTitle(&now_title))?;
+            title = now_title;
+        }
+        let held =
+            !app.terminal_focused || app.throttled && clock::since(last_draw) < THROTTLED_FRAME;
+        owed_redraw = needs_redraw && held;
+        if needs_redraw && !held && pending_resize.is_none() {
+            let started = clock::now();
+            terminal.draw(|frame| render(frame, app))?;
+            app.debug.frame(clock::since(started));
+            last_draw = started;
+        }
+    }
+
+    if host.reports_focus() {
+        execute!(&mut host.out(), DisableFocusChange)?;
+    }
+    execute!(&mut host.out(), DisableBracketedPaste)?;
+    if kitty {
+        execute!(&mut host.out(), PopKeyboardEnhancementFlags)?;
+    }
+    execute!(&mut host.out(), Print(POP_TITLE))?;
+    if cursor != SetCursorStyle::DefaultUserShape {
+        execute!(&mut host.out(), SetCursorStyle::DefaultUserShape)?;
+    }
+    if mouse_captured {
+        execute!(&mut host.out(), DisableMouseCapture)?;
+    }
+    Ok(())
+}
+
+// Turn mouse capture on or off if the app has changed its mind
+fn capture_mouse(app: &App, host: &mut impl Host, captured: &mut bool) -> Result<()> {
+    if (app.config.layout.minimap && app.mouse) != *captured {
+        *captured = !*captured;
+        match *captured {
+            true => execute!(&mut host.out(), EnableMouseCapture)?,
+            false => execute!(&mut host.out(), DisableMouseCapture)?,
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Rendering — builds the UI each frame from app state
+//
+// Layout:
+// ┌─────────────── Output ───────────────┐
+// │ Welcome! Type 'help' for commands.   │
+// │ > hello                              │
+// │   Hello, world!                      │
+// │                                      │
+// └──────────────────────────────────────┘
+// ┌─────────────── Command ──────────────┐
+// │ your typing here█                    │
+// └──────────────────────────────────────┘
+//
+// A terminal smaller than MIN_WIDTH×MIN_HEIGHT gets a note asking for more
+// room instead, until it's resized. In screen reader mode the panels are a
+// plain transcript instead (see screen_reader.rs).
+// =============================================================================
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 10;
+
+pub fn render(frame: &mut Frame, app: &App) {
+    if frame.area().width < MIN_WIDTH || frame.area().height < MIN_HEIGHT {
+        app.minimap_area.set(None); // not on screen, so not clickable
+        render_too_small(frame, app);
+        return;
+    }
+
+    if app.screen_reader() {
+        screen_reader::render(frame, app);
+    } else {
+        render_panels(frame, app);
+    }
+
+    // Popups go over everything, oldest first, then toasts over them. A
+    // popup that has just opened unrolls downwards.
+    let animate = app.animate();
+    for modal in &app.overlays {
+        let opening = animate && animation::running(modal.opened());
+        let before = opening.then(|| frame.buffer_mut().clone());
+        modal.render(frame, &app.config.theme);
+        if let Some(before) = before {
+            let progress = animation::progress(modal.opened());
+            animation::reveal(frame, &before, progress, Reveal::Down);
+        }
+    }
+    let corner = frame.area().inner(Margin::new(1, 1)); // clear of panel borders
+    app.toasts.render(frame, corner, &app.config.theme, animate);
+    if app.debug.shown {
+        render_debug(frame, app);
+    }
+    if app.screen_reader() {
+        screen_reader::plain(frame.buffer_mut());
+    }
+    if app.monochrome {
+        monochrome(frame.buffer_mut());
+    }
+}
+
+fn render_panels(frame: &mut Frame, app: &App) {
+    // Carve the terminal into panels as described by the layout config
+    let areas = app.config.layout.split(frame.area());
+
+    // A pane goes in the side panel if there is one, else over Output
+    let theme = &app.config.theme;
+    match (&app.pane, areas.side) {
+        (Some(p), Some(side)) => {
+            if let Some(area) = areas.output {
+                render_output(frame, app, area);
+            }
+            pane::render(frame, p.as_ref(), side, theme, app.pane_focused);
+        }
+        (Some(p), None) => {
+            if let Some(area) = areas.output {
+                pane::render(frame, p.as_ref(), area, theme, app.pane_focused);
+            }
+        }
+        (None, side) => {
+            if let Some(area) = areas.output {
+                render_output(frame, app, area);
+            }
+            if let Some(area) = side {
+                render_side_panel(frame, app, area);
+            }
+        }
+    }
+    if let Some(area) = areas.title {
+        render_title_bar(frame, app, area);
+    }
+    if let Some(area) = areas.menu {
+        menu::render_bar(frame, area, &app.config.menu, None, &app.config.theme);
+    }
+    if let Some(area) = areas.hints {
+        // Whatever has the keyboard: the top popup, a focused pane, or the
+        // input bar
+        let hints = match (app.overlays.last(), &app.pane) {
+            (Some(modal), _) => hints::owned(modal.hints()),
+            (None, Some(pane)) if app.pane_focused => {
+                let mut hints = hints::owned(pane.hints());
+                hints.push(("Esc".into(), "input bar".into()));
+                hints
+            }
+            (None, pane) => hints::for_input(&app.config.keymap, pane.is_some()),
+        };
+        hints::render(frame, area, &hints, &app.config.theme);
+    }
+    if let Some(area) = areas.status {
+        render_status_bar(frame, app, area);
+    }
+    if let Some(area) = areas.input {
+        // Without a status row, job gauges go in the input bar's border
+        render_input(frame, app, area, areas.status.is_none());
+    }
+}
+
+// No colors: what had a background is reversed instead and colored text is
+// bold, so the status bar, selections and errors still stand out. Grays
+// are plain text.
+fn monochrome(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        if cell.bg != Color::Reset {
+            cell.modifier |= Modifier::REVERSED;
+        } else if !matches!(
+            cell.fg,
+            Color::Reset | Color::Black | Color::DarkGray | Color::Gray | Color::White
+        ) {
+            cell.modifier |= Modifier::BOLD;
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+fn render_too_small(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let text = vec![
+        Line::from("Terminal too small"),
+        Line::from(format!("need {MIN_WIDTH}×{MIN_HEIGHT}")),
+        Line::from(format!("have {}×{}", area.width, area.height)),
+    ];
+    let top = area.height.saturating_sub(text.len() as u16) / 2;
+    let [_, middle] = Layout::vertical([Constraint::Length(top), Constraint::Fill(1)]).areas(area);
+    let style = Style::default().fg(app.config.theme.output_border);
+    frame.render_widget(Paragraph::new(text).style(style).centered(), middle);
+}
+
+// What the debug overlay needs from App
+fn render_debug(frame: &mut Frame, app: &App) {
+    let mode = match app.mode() {
+        cursor::Mode::Popup => "popup".to_string(),
+        cursor::Mode::Pane => format!(
+            "pane: {}",
+            app.pane.as_ref().map_or(String::new(), |p| p.title())
+        ),
+        cursor::Mode::Input => "input".to_string(),
+    };
+    let total = app.shown().rows();
+    let scroll = match app.output_top {
+        Some(top) => format!("line {top} of {total}"),
+        None => format!("following (of {total})"),
+    };
+    let bytes = app.messages.bytes();
+    let rows = vec![
+        ("mode", mode),
+        ("scroll", scroll),
+        ("scrollback", format!("{:.1} KiB", bytes as f64 / 1024.0)),
+    ];
+    app.debug.render(frame, rows, &app.config.theme);
+}
+
+// Output's text within its border, and the minimap beside it if it's on
+fn output_areas(app: &App, area: Rect) -> (Rect, Option<Rect>) {
+    let text_area = Block::default().borders(Borders::ALL).inner(area);
+    if !app.config.layout.minimap || text_area.width <= minimap::WIDTH * 4 {
+        return (text_area, None);
+    }
+    let [text, map] = Layout::horizontal([Constraint::Fill(1), Constraint::Length(minimap::WIDTH)])
+        .areas(text_area);
+    (text, Some(map))
+}
+
+fn render_output(frame: &mut Frame, app: &App, area: Rect) {
+    let (text_area, map_area) = output_areas(app, area);
+    app.minimap_area.set(map_area);
+    app.output_width.set(text_area.width);
+    let lines = app.shown();
+
+    // Follow the end unless scrolled back (PgUp, or a click on the minimap)
+    let visible_height = text_area.height as usize;
+    let last_top = lines.rows().saturating_sub(visible_height);
+    let top = app.output_top.unwrap_or(last_top).min(last_top);
+    app.output_rows.set(visible_height.max(1));
+
+    let below = last_top - top;
+    let mut title = " Output ".to_string();
+    if let Some(filter) = &app.output_filter {
+        title += &format!("/{filter}/ {} of {} ", lines.len(), app.messages.len());
+    }
+    if below > 0 {
+        title += &format!("↓ {below} more ");
+    }
+    if app.throttled {
+        title += "output throttled ";
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(match app.alerts.flashing() {
+            true => Color::Red, // as for error toasts
+            false => app.config.theme.output_border,
+        }));
+    frame.render_widget(block, area);
+
+    // The minimap counts messages; wrapped, Output's top row may be partway
+    // through one
+    if let Some(map) = map_area {
+        let first = lines.at_row(top).0;
+        let last = lines.at_row((top + visible_height).saturating_sub(1)).0;
+        let shown = last.saturating_sub(first) + 1;
+        let theme = &app.config.theme;
+        minimap::render(frame, map, &lines.summaries(), first, shown, theme);
+    }
+
+    // Only the rows on screen, borrowing the messages (see output.rs)
+    let text = lines.rendered(top..top + visible_height);
+    frame.render_widget(Paragraph::new(text), text_area);
+}
+
+// A click or drag on the minimap scrolls Output to put that line mid-screen
+fn handle_click(app: &mut App, column: u16, row: u16) -> bool {
+    let Some(map) = app.minimap_area.get() else {
+        return false;
+    };
+    if !map.contains((column, row).into()) {
+        return false;
+    }
+    let shown = app.shown();
+    let line = shown.row_of(minimap::line_at(map, shown.len(), row));
+    app.scroll_output_to(line.saturating_sub(app.output_rows.get() / 2));
+    true
+}
+
+fn render_input(frame: &mut Frame, app: &App, area: Rect, show_progress: bool) {
+    let title = match app.pane.as_ref().and_then(|p| p.input_label()) {
+        Some(label) => format!(" {label} (/command to run one) "),
+        None => " Command ".to_string(),
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(app.config.theme.input_border));
+    if show_progress && !app.progress.is_empty() {
+        let gauges = progress_gauges(app, area.width.saturating_sub(14) as usize);
+        block = block.title(Line::from(format!(" {gauges} ")).right_aligned());
+    }
+    // A line longer than the bar scrolls sideways to keep the cursor in
+    // view, unless a focused pane has the keyboard (and the cursor: an
+    // editor shows it; other panes hide it)
+    let text = app.input.to_string();
+    let (shown, column) = match app.pane_focused {
+        true => (text.as_str(), 0),
+        false => input_line::visible(&text, app.input.cursor(), area.width.saturating_sub(2)),
+    };
+    let input_bar = Paragraph::new(shown)
+        .block(block)
+        .style(Style::default().fg(app.config.theme.input_text));
+
+    frame.render_widget(input_bar, area);
+
+    // Place the blinking cursor where the typing goes inside the input bar
+    // +1 on each axis to account for the border
+    if !app.pane_focused {
+        frame.set_cursor_position((area.x + column + 1, area.y + 1));
+    }
+}
+
+// Only drawn when the layout includes a Title row
+// "tui-template │ profile: work │ Tree: src │ home › projects › notes"
+fn render_title_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.config.theme;
+    let bar = Style::default().fg(theme.status_fg).bg(theme.status_bg);
+    let mut spans = vec![Span::styled(
+        format!(" {} ", env!("CARGO_PKG_NAME")),
+        bar.add_modifier(Modifier::BOLD),
+    )];
+    let mut section = |text: String| spans.push(Span::styled(format!("│ {text} "), bar));
+    if let Some(profile) = paths::profile() {
+        section(format!("profile: {profile}"));
+    }
+    if let Some(pane) = &app.pane {
+        section(pane.title());
+    }
+    if !app.breadcrumbs.is_empty() {
+        section(app.breadcrumbs.join(" › "));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)).style(bar), area);
+}
+
+fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let mut status = format!(" {} messages │ Esc to quit ", app.messages.len());
+    #[cfg(feature = "media")]
+    if let Some(track) = app.connections.media.track() {
+        status += &format!("│ ♪ {track} ");
+    }
+    if !app.progress.is_empty() {
+        let room = (area.width as usize).saturating_sub(status.chars().count() + 3);
+        status += &format!("│ {}", progress_gauges(app, room));
+    }
+    let theme = &app.config.theme;
+    let bar =
+        Paragraph::new(status).style(Style::default().fg(theme.status_fg).bg(theme.status_bg));
+    frame.render_widget(bar, area);
+}
+
+// Running jobs as "label ▕███▌    ▏ 45%", side by side. When they don't all
+// fit in `width`, show as many as fit and rotate through the rest.
+fn progress_gauges(app: &App, width: usize) -> String {
+    const LABEL: usize = 12;
+    const BAR: usize = 8;
+    const GAUGE: usize = LABEL + BAR + 9; // label, ▕bar▏, " 100%", spacing
+
+    let jobs: Vec<_> = app.progress.values().collect();
+    let per_page = (width / GAUGE).max(1);
+    let pages = jobs.len().div_ceil(per_page);
+    let page = if pages > 1 {
+        // Counted from when the first of them started
+        let first = jobs.iter().map(|(_, _, started)| *started).min();
+        let secs = first.map_or(0, |started| clock::since(started).as_secs());
+        (secs / 2) as usize % pages // a new page every two seconds
+    } else {
+        0
+    };
+
+    let mut gauges: Vec<String> = jobs
+        .iter()
+        .skip(page * per_page)
+        .take(per_page)
+        .map(|(label, fraction, _)| {
+            let label: String = label.chars().take(LABEL).collect();
+            if fraction.is_nan() {
+                return format!("{label} ▕{}▏ live", "░".repeat(BAR)); // progress.unknown()
+            }
+            let eighths = (fraction * (BAR * 8) as f64).round() as usize;
+            let mut bar = "█".repeat(eighths / 8);
+            bar += ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"][eighths % 8];
+            let pct = (fraction * 100.0).round();
+            format!("{label} ▕{bar:BAR$}▏{pct:>4}%")
+        })
+        .collect();
+    if pages > 1 {
+        gauges.push(format!("({}/{pages})", page + 1));
+    }
+    gauges.join("  ")
+}
+
+// Only drawn when the layout has a side_panel — put your app's extra view
+// here. Until then it shows metrics, once any have been reported.
+fn render_side_panel(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.config.theme.output_border));
+    if app.metrics.is_empty() {
+        let panel = Paragraph::new("Customize render_side_panel()").block(block.title(" Side "));
+        frame.render_widget(panel, area);
+    } else {
+        let block = block.title(" Metrics ");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        app.metrics.render(frame, inner, &app.config.theme);
+    }
+}
+
+// =============================================================================
+// Input handling — maps key events to state changes
+//
+// Keys are looked up in the keymap (keymap.rs, configurable) first. Anything
+// unbound that produces a character is typed into the input bar.
+// =============================================================================
+fn handle_key(app: &mut App, key: KeyBinding) {
+    let key: KeyEvent = key.into();
+
+    // An open popup takes every key, bypassing the keymap
+    if let Some(modal) = app.overlays.last_mut() {
+        match modal.handle_key(key) {
+            Outcome::Open => {}
+            Outcome::Cancel => {
+                app.overlays.pop();
+            }
+            Outcome::Done(finish) => {
+                app.overlays.pop();
+                finish(&mut app.ctx());
+                app.drain_events();
+            }
+        }
+        return;
+    }
+
+    if app.pane_focused
+        && let Some(pane) = &mut app.pane
+    {
+        if key.code == KeyCode::Esc {
+            app.pane_focused = false;
+            return;
+        }
+        if pane.handle_key(key) {
+            return;
+        }
+    }
+
+    if let Some(action) = app.config.keymap.get(key).cloned() {
+        perform(app, action);
+        return;
+    }
+    // With a menu bar on screen, alt-<letter> opens its menus
+    if let KeyCode::Char(c) = key.code
+        && key.modifiers == KeyModifiers::ALT
+        && app
+            .config
+            .layout
+            .panels
+            .iter()
+            .any(|p| p.kind == PanelKind::Menu)
+        && let Some(i) = menu::find(&app.config.menu, c)
+    {
+        app.open_menu(i);
+        return;
+    }
+    if app.pane_focused {
+        return; // don't type into an input bar that doesn't have focus
+    }
+
+    // Typing a character — insert it at the cursor (ctrl/alt combos are shortcuts,
+    // not text, so unbound ones are ignored)
+    if let KeyCode::Char(c) = key.code
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        app.input.insert(c);
+    }
+}
+
+// A paste goes into the input bar in one go, however long, and each line
+// break in it submits what's before it, as Enter would. A popup or a
+// focused pane gets it key by key instead, as if typed.
+fn paste(app: &mut App, text: &str) {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    if !app.overlays.is_empty() || app.pane_focused {
+        for c in text.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            handle_key(app, KeyEvent::new(code, KeyModifiers::NONE).into());
+        }
+        return;
+    }
+    let mut rest = text.as_str();
+    while let Some((line, after)) = rest.split_once('\n') {
+        app.input.insert_str(&one_line(line));
+        perform(app, Action::Submit);
+        if !app.running {
+            return;
+        }
+        rest = after;
+    }
+    app.input.insert_str(&one_line(rest));
+}
+
+// `text` fit for the input bar, which is one line: controls become spaces
+fn one_line(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+fn perform(app: &mut App, action: Action) {
+    match action {
+        // Submit the command
+        Action::Submit => {
+            let command = app.input.take();
+            if command.is_empty() {
+                return;
+            }
+            app.remember(&command);
+            // A pane that takes input gets the line, unless it's a /command
+            match &mut app.pane {
+                Some(pane) if pane.input_label().is_some() => match command.strip_prefix('/') {
+                    Some(command) => {
+                        app.submit(command);
+                    }
+                    None => pane.submit(&command),
+                },
+                _ => {
+                    app.submit(&command);
+                }
+            }
+        }
+        Action::DeleteBack => app.input.delete_back(),
+        Action::DeleteForward => app.input.delete_forward(),
+        Action::CursorLeft => app.input.left(),
+        Action::CursorRight => app.input.right(),
+        Action::LineStart => app.input.home(),
+        Action::LineEnd => app.input.end(),
+        Action::ClearInput => app.input.clear(),
+        Action::HistoryPrev => app.browse_history(-1),
+        Action::HistoryNext => app.browse_history(1),
+        Action::FocusNext => app.pane_focused = app.pane.is_some() && !app.pane_focused,
+        Action::Menu => app.open_menu(0),
+        Action::ScrollUp => app.scroll_output_by(-(app.output_rows.get() as isize)),
+        Action::ScrollDown => app.scroll_output_by(app.output_rows.get() as isize),
+        Action::Debug => app.debug.shown = !app.debug.shown,
+        Action::Quit => app.running = false,
+        // A key bound to a command runs it directly, leaving the input alone
+        Action::Command(command) => {
+            app.submit(&command);
+        }
+    }
+}
+
+// =============================================================================
+// Command execution — THIS IS WHAT YOU CUSTOMIZE PER APP
+//
+// When you clone this template for a new project, this is the main function
+// you'll rewrite. Add your own commands, call into your own modules, etc.
+// =============================================================================
+// Shown by `help` — keep in sync with the match below
+const COMMANDS: &[(&str, &str)] = &[
+    ("help", "show this message"),
+    ("hello [name]", "say hello"),
+    ("config", "show the active configuration"),
+    ("copy [text]", "copy text to the clipboard (again)"),
+    (
+        "clips [insert|clear]",
+        "earlier copies: copy one again, or type it in",
+    ),
+    ("sleep <secs>", "run a background job that finishes later"),
+    ("jobs", "list background jobs"),
+    ("cancel <id> | all", "stop a background job"),
+    (
+        "tail [-f] [-n <lines>] <path>",
+        "show a file's last lines (-f: follow it)",
+    ),
+    (
+        "grep [-i] <pattern> <path>…",
+        "search files; Enter opens a match",
+    ),
+    (
+        "csv <path>",
+        "a CSV file as a table you can sort and filter",
+    ),
+    (
+        "json <file | inline json> [.path]",
+        "pretty-print JSON, optionally just part of it",
+    ),
+    ("!<shell command>", "run it through the shell, as a job"),
+    (
+        "env [set <name> <value> | unset <name>]",
+        "the environment `!`, watch and ssh pass on",
+    ),
+    (
+        "watch <path>… -- <shell command>",
+        "re-run a command when files change",
+    ),
+    ("toast [info|warn|error] <text>", "show a notice that fades"),
+    (
+        "mouse [on|off]",
+        "let the minimap have the mouse, or the terminal",
+    ),
+    (
+        "crumbs [a/b/c]",
+        "set (or clear) the title bar's breadcrumbs",
+    ),
+    (
+        "metric <name> <value> [max] | demo | clear",
+        "chart a value in the side panel",
+    ),
+    ("set <name> <value>", "set a variable, used as $name"),
+    ("unset <name>", "remove a variable"),
+    ("vars", "list variables"),
+    (
+        "session save|load|list [name]",
+        "persist or restore this session",
+    ),
+    ("export [file.json]", "write session and settings to a file"),
+    (
+        "import [file.json]",
+        "restore an export (no file = pick one)",
+    ),
+    ("recover [discard]", "restore input left over from a crash"),
+    (
+        "profile [list | switch <name>]",
+        "show, list or switch profiles",
+    ),
+    ("theme [list | <name>]", "switch colors"),
+    ("theme set <key> [color]", "change one color of the theme"),
+    (
+        "bind [<key> <action> | save]",
+        "list, change or save key bindings",
+    ),
+    ("unbind <key>", "remove a key binding"),
+    ("keys", "show key bindings in a table"),
+    ("tree [dir]", "browse a directory as a tree"),
+    ("chart [window-secs]", "show a live chart demo"),
+    ("diff <file-a> <file-b>", "compare two files side by side"),
+    ("hex <file>", "dump a file's bytes (g goes to an offset)"),
+    ("show image <path>", "show a picture in a pane"),
+    (
+        "http get <url> | post <url> <body>",
+        "make a request in the background",
+    ),
+    ("ws connect <url>", "open a WebSocket; the input bar sends"),
+    (
+        "net connect tcp|udp <host:port>",
+        "a raw socket; the input bar sends (x: hex)",
+    ),
+    (
+        "ssh <[user@]host> <command>",
+        "run a command remotely, as a job",
+    ),
+    (
+        "mqtt connect <host[:port]> | sub | pub",
+        "MQTT; messages print as [topic] payload",
+    ),
+    (
+        "serial list | open <port> [baud]",
+        "a serial port monitor; the input bar sends",
+    ),
+    ("git", "the repository's branch, changes and commits"),
+    ("sysinfo", "CPU, memory and network as live sparklines"),
+    ("ps", "processes: sort, filter, k to kill"),
+    (
+        "docker [start|stop|restart|logs <name>]",
+        "containers in a table; s start/stop, l logs",
+    ),
+    (
+        "feed [add <url> | remove [url] | list]",
+        "RSS / Atom entries in a table; Enter reads",
+    ),
+    (
+        "grpc call <host:port> <Service/Method> [json]",
+        "call a method as JSON (`grpc list` shows them)",
+    ),
+    (
+        "k8s [logs <pod> | ns <name> | context <name>]",
+        "pods in a table; l logs, `k8s contexts` lists",
+    ),
+    (
+        "media [play|pause|toggle|next|prev]",
+        "what's playing, and control it (MPRIS)",
+    ),
+    (
+        "sql open <db> | tables | <query>",
+        "query SQLite; :name binds a variable",
+    ),
+    (
+        "edit [--preview] [+line] <file>",
+        "edit a file in a pane (ctrl-s saves)",
+    ),
+    (
+        "preview <file.md>",
+        "render markdown, updating as it changes",
+    ),
+    ("logs [level] [target]", "this app's log, live, in a table"),
+    ("close", "close the table, tree, diff… (any pane)"),
+    ("pick [--dirs] [dir]", "choose a path into $picked"),
+    ("date [YYYY-MM-DD]", "choose a date into $date"),
+    ("color [color]", "choose a color into $color"),
+    ("form", "fill in a demo form into variables"),
+    (
+        "filter [-i] [pattern]",
+        "show only Output lines matching a regex",
+    ),
+    ("clear", "clear the output"),
+    ("quit", "exit the app"),
+];
+
+fn execute_command(ctx: &mut Ctx, cmd: &str) -> Status {
+    let cmd = &expand_vars(ctx.vars, cmd);
+    let outer = ctx.spawner.name_jobs(cmd.trim());
+    let status = dispatch(ctx, cmd);
+    ctx.spawner.name_jobs(&outer);
+    tracing::info!(command = cmd, ?status, "executed");
+    status
+}
+
+fn dispatch(ctx: &mut Ctx, cmd: &str) -> Status {
+    // `!ls -la`: the whole line is the shell's, so no splitting into words
+    if let Some(line) = cmd.trim().strip_prefix('!') {
+        return shell::command(ctx, line.trim());
+    }
+    let (name, args) = cmd
+        .trim()
+        .split_once(' ')
+        .map_or((cmd.trim(), ""), |(name, args)| (name, args.trim()));
+
+    match name {
+        "help" => {
+            ctx.print("  Available commands:");
+            let width = COMMANDS
+                .iter()
+                .map(|(usage, _)| usage.len())
+                .max()
+                .unwrap_or(0);
+            for (usage, summary) in COMMANDS {
+                ctx.print(format!("    {usage:width$}  — {summary}"));
+            }
+        }
+        "hello" => {
+            let who = if args.is_empty() { "world" } else { args };
+            ctx.print(format!("  Hello, {who}!"));
+        }
+        "config" => {
+            for line in format!("{:#?}", ctx.config).lines() {
+                ctx.print(format!("  {line}"));
+            }
+        }
+        "copy" => {
+            // With no argument, copy whatever was copied last time
+            let text = match (args, ctx.clipboard.last()) {
+                ("", Some(last)) => last,
+                ("", None) => return ctx.fail("  Usage: copy <text>"),
+                (text, _) => text.to_string(),
+            };
+            match ctx.clipboard.copy(&text) {
+                Ok(()) => ctx.print(format!("  Copied {} characters", text.chars().count())),
+                Err(err) => return ctx.fail(format!("  Copy failed: {err}")),
+            }
+        }
+        "sleep" => {
+            // Demonstrates the spawn handle: the UI stays responsive while this runs
+            let Ok(secs) = args.parse::<u64>() else {
+                return ctx.fail("  Usage: sleep <secs>");
+            };
+            ctx.print(format!("  Sleeping {secs}s in the background…"));
+            let label = format!("sleep {secs}");
+            ctx.spawner.spawn_with_progress(label, move |tx, progress| {
+                let steps = secs * 10;
+                for step in 1..=steps {
+                    if progress.cancelled() {
+                        let _ = tx.send(AppEvent::Print(format!("  Stopped sleeping {secs}s")));
+                        return;
+                    }
+                    std::thread::sleep(Duration::from_millis(100));
+                    progress.set(step as f64 / steps as f64);
+                }
+                let _ = tx.send(AppEvent::Print(format!("  Done sleeping {secs}s")));
+            });
+        }
+        "jobs" => {
+            let jobs = ctx.spawner.jobs();
+            if jobs.is_empty() {
+                ctx.print("  No jobs running");
+            }
+            for (id, label) in jobs {
+                ctx.print(format!("  {id:>3}  {label}"));
+            }
+        }
+        "cancel" => {
+            let ids: Vec<u64> = match args {
+                "all" => ctx.spawner.jobs().into_iter().map(|(id, _)| id).collect(),
+                id => match id.parse() {
+                    Ok(id) => vec![id],
+                    Err(_) => return ctx.fail("  Usage: cancel <id> | all  (see `jobs`)"),
+                },
+            };
+            for id in ids {
+                if !ctx.spawner.cancel(id) {
+                    return ctx.fail(format!("  No job {id} (see `jobs`)"));
+                }
+                ctx.print(format!("  Cancelling job {id}"));
+            }
+        }
+        "tail" => return tail::command(ctx, args),
+        "watch" => return watch::command(ctx, args),
+        "env" => return env::command(ctx, args),
+        "logs" => return log_view::command(ctx, args),
+        "clips" => return clipboard::command(ctx, args),
+        "grep" => return grep::command(ctx, args),
+        "json" => return json::command(ctx, args),
+        "csv" => return csv::command(ctx, args),
+        "mouse" => ctx.send(AppEvent::SetMouse(match args {
+            "" => None,
+            "on" => Some(true),
+            "off" => Some(false),
+            _ => return ctx.fail("  Usage: mouse [on|off]"),
+        })),
+        "crumbs" => {
+            // "crumbs home/projects/notes"; no path clears them
+            let crumbs = args
+                .split('/')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect();
+            ctx.send(AppEvent::SetBreadcrumbs(crumbs));
+        }
+        "toast" => {
+            let (level, text) = match args.split_once(' ') {
+                Some(("info", text)) => (Level::Info, text),
+                Some(("warn", text)) => (Level::Warn, text),
+                Some(("error", text)) => (Level::Error, text),
+                _ => (Level::Info, args),
+            };
+            if text.is_empty() {
+                return ctx.fail("  Usage: toast [info|warn|error] <text>");
+            }
+            ctx.toast(level, text);
+        }
+        "metric" => {
+            let usage = "  Usage: metric <name> <value> [max] | demo | clear";
+            match args.split_whitespace().collect::<Vec<_>>()[..] {
+                ["clear"] => ctx.send(AppEvent::ClearMetrics),
+                ["demo"] => {
+                    // Samples from a background job, the way a monitor would
+                    ctx.print("  Charting demo metrics for 30s…");
+                    ctx.spawner.spawn_watcher(|tx| {
+                        for tick in 0..150 {
+                            let t = tick as f64 / 5.0;
+                            let samples = [
+                                ("wave", 50.0 + 40.0 * t.sin(), None),
+                                ("sawtooth", (tick % 25) as f64, None),
+                                ("load", 50.0 + 45.0 * (t / 3.0).sin(), Some(100.0)),
+                            ];
+                            for (name, value, max) in samples {
+                                let name = name.to_string();
+                                let _ = tx.send(AppEvent::Metric { name, value, max });
+                            }
+                            std::thread::sleep(Duration::from_millis(200));
+                        }
+                    });
+                }
+                [name, value, ref rest @ ..] if rest.len() <= 1 => {
+                    let Ok(value) = value.parse::<f64>() else {
+                        return ctx.fail(usage);
+                    };
+                    let max = match rest.first().map(|m| m.parse::<f64>()) {
+                        None => None,
+                        Some(Ok(max)) => Some(max),
+                        Some(Err(_)) => return ctx.fail(usage),
+                    };
+                    let name = name.to_string();
+                    ctx.send(AppEvent::Metric { name, value, max });
+                }
+                _ => return ctx.fail(usage),
+            }
+        }
+        "set" => {
+            let Some((var, value)) = args.split_once(' ') else {
+                return ctx.fail("  Usage: set <name> <value>");
+            };
+            ctx.vars.insert(var.to_string(), value.trim().to_string());
+        }
+        "unset" => {
+            if ctx.vars.remove(args).is_none() {
+                return ctx.fail(format!("  No variable named '{args}'"));
+            }
+        }
+        "vars" => {
+            if ctx.vars.is_empty() {
+                ctx.print("  No variables set");
+            }
+            for (var, value) in ctx.vars.iter() {
+                ctx.print(format!("  {var} = {value}"));
+            }
+        }
+        "session" => {
+            let (op, session_name) = args.split_once(' ').unwrap_or((args, ""));
+            let session_name = session_name.trim();
+            match op {
+                "save" => {
+                    let session_name = match session_name {
+                        "" => session::DEFAULT_NAME,
+                        n => n,
+                    };
+                    ctx.send(AppEvent::SaveSession(session_name.to_string()));
+                }
+                "load" => ctx.send(AppEvent::LoadSession(
+                    Some(session_name.to_string()).filter(|n| !n.is_empty()),
+                )),
+                "list" => match session::list(&*ctx.effects.files) {
+                    Ok(names) if names.is_empty() => ctx.print("  No saved sessions"),
+                    Ok(names) => {
+                        for n in names {
+                            ctx.print(format!("  {n}"));
+                        }
+                    }
+                    Err(err) => return ctx.fail(format!("  Session error: {err:#}")),
+                },
+                _ => return ctx.fail("  Usage: session save|load|list [name]"),
+            }
+        }
+        "export" if args.is_empty() => {
+            let files = ctx.effects.files.clone();
+            let is_dir = move |name: &str| files.metadata(Path::new(name)).is_ok_and(|m| m.is_dir);
+            let prompt = Prompt::new("Export to file:")
+                .initial("export.json")
+                .validate(move |name| match name.trim() {
+                    "" => Err("A file name is needed".into()),
+                    name if is_dir(name) => Err(format!("{name} is a directory")),
+                    _ => Ok(()),
+                });
+            ctx.open(prompt, |ctx, name| {
+                ctx.send(AppEvent::ExportState(name.trim().into()));
+            });
+        }
+        // Importing replaces what's on screen, so make sure first
+        "import" if args.is_empty() => ctx.open(
+            FilePicker::new(ctx.effects.files.clone(), "."),
+            |ctx, path| {
+                let question = format!(
+                    "Replace this session's output, history and variables with {}?",
+                    path.display()
+                );
+                let confirm = Confirm::new(question)
+                    .labels("Import", "Keep")
+                    .default(Choice::No);
+                ctx.open(confirm, move |ctx, import| {
+                    if import {
+                        ctx.send(AppEvent::ImportState(path));
+                    }
+                });
+            },
+        ),
+        "export" => ctx.send(AppEvent::ExportState(args.into())),
+        "import" => ctx.send(AppEvent::ImportState(args.into())),
+        "pick" => {
+            let (dirs, start) = match args.strip_prefix("--dirs") {
+                Some(rest) => (true, rest.trim()),
+                None => (false, args),
+            };
+            let start = if start.is_empty() { "." } else { start };
+            let picker = FilePicker::new(ctx.effects.files.clone(), start);
+            let picker = if dirs { picker.directories() } else { picker };
+            ctx.open(picker, |ctx, path| {
+                let path = path.display().to_string();
+                ctx.print(format!("  Picked {path} (as $picked)"));
+                ctx.vars.insert("picked".into(), path);
+            });
+        }
+        "date" => {
+            let mut calendar = Calendar::new("Pick a date");
+            if !args.is_empty() {
+                let Some(date) = Date::parse(args) else {
+                    return ctx.fail("  Usage: date [YYYY-MM-DD]");
+                };
+                calendar = calendar.initial(date);
+            }
+            ctx.open(calendar, |ctx, date| {
+                let date = date.to_string();
+                ctx.print(format!("  Picked {date} (as $date)"));
+                ctx.vars.insert("date".into(), date);
+            });
+        }
+        "color" => {
+            let mut picker = ColorPicker::new("Pick a color");
+            if !args.is_empty() {
+                let Ok(color) = args.parse() else {
+                    return ctx.fail("  Usage: color [name | 0-255 | #rrggbb]");
+                };
+                picker = picker.initial(color);
+            }
+            ctx.open(picker, |ctx, color| {
+                let color = widgets::color::name(color);
+                ctx.print(format!("  Picked {color} (as $color)"));
+                ctx.vars.insert("color".into(), color);
+            });
+        }
+        "form" => {
+            let form = Form::new("Sign up")
+                .text("name", "Name", "")
+                .validate(|name| match name.trim() {
+                    "" => Err("required".into()),
+                    _ => Ok(()),
+                })
+                .text("email", "Email", "")
+                .validate(|email| match email.split_once('@') {
+                    Some((user, host)) if !user.is_empty() && host.contains('.') => Ok(()),
+                    _ => Err("not an email address".into()),
+                })
+                .select("plan", "Plan", ["free", "pro", "team"])
+                .checkbox("newsletter", "Newsletter", false);
+            ctx.open(form, |ctx, values| {
+                let names: Vec<String> = values.keys().map(|k| format!("${k}")).collect();
+                ctx.print(format!("  Set {}", names.join(", ")));
+                ctx.vars.extend(values);
+            });
+        }
+        "recover" => match args {
+            "" => ctx.send(AppEvent::Recover { discard: false }),
+            "discard" => ctx.send(AppEvent::Recover { discard: true }),
+            _ => return ctx.fail("  Usage: recover [discard]"),
+        },
+        "profile" => match args.split_once(' ') {
+            _ if args.is_empty() => {
+                let current = paths::profile();
+                ctx.print(format!(
+                    "  Profile: {}",
+                    current.as_deref().unwrap_or("default")
+                ));
+            }
+            _ if args == "list" => {
+                ctx.print("  default");
+                for name in paths::profiles() {
+                    ctx.print(format!("  {name}"));
+                }
+            }
+            _ if args == "switch" => {
+                let mut names = vec!["default".to_string()];
+                names.extend(paths::profiles());
+                ctx.open(Select::new("Switch profile", names), |ctx, name| {
+                    execute_command(ctx, &format!("profile switch {name}"));
+                });
+            }
+            Some(("switch", profile)) => match profile.trim() {
+                "default" => ctx.send(AppEvent::SwitchProfile(None)),
+                p if paths::is_valid_name(p) => {
+                    ctx.send(AppEvent::SwitchProfile(Some(p.to_string())));
+                }
+                _ => {
+                    return ctx
+                        .fail("  Profile names may only contain letters, digits, '-' and '_'");
+                }
+            },
+            _ => return ctx.fail("  Usage: profile [list | switch <name>]"),
+        },
+        "theme" if args == "set" || args.starts_with("set ") => {
+            let mut words = args.split_whitespace().skip(1);
+            let (Some(key), value) = (words.next(), words.next()) else {
+                return ctx.fail(format!(
+                    "  Usage: theme set <{}> [color]",
+                    Theme::KEYS.join("|")
+                ));
+            };
+            let mut theme = ctx.config.theme.clone();
+            let Some(&mut current) = theme.color_mut(key) else {
+                return ctx.fail(format!(
+                    "  '{key}' is not a theme key ({})",
+                    Theme::KEYS.join(", ")
+                ));
+            };
+            let key = key.to_string();
+            let title = key.clone();
+            let apply = move |ctx: &mut Ctx, color| {
+                if let Some(slot) = theme.color_mut(&key) {
+                    *slot = color;
+                }
+                ctx.send(AppEvent::SetTheme(Box::new(theme.for_terminal())));
+                ctx.print(format!("  Set {key} to {}", widgets::color::name(color)));
+            };
+            match value {
+                None => ctx.open(ColorPicker::new(title).initial(current), apply),
+                Some(value) => match value.parse() {
+                    Ok(color) => apply(ctx, color),
+                    Err(_) => return ctx.fail(format!("  '{value}' is not a color")),
+                },
+            }
+        }
+        "theme" => match args {
+            "" => match theme::list(&*ctx.effects.files) {
+                Ok(names) if !names.is_empty() => {
+                    ctx.open(Select::new("Theme", names), |ctx, name| {
+                        execute_command(ctx, &format!("theme {name}"));
+                    });
+                }
+                _ => return ctx.fail("  Usage: theme list | theme <name>"),
+            },
+            "list" => match theme::list(&*ctx.effects.files) {
+                Ok(names) if names.is_empty() => {
+                    let dir = theme::themes_dir().map(|d| d.display().to_string());
+                    ctx.print(format!(
+                        "  No themes (*.toml) found in {}",
+                        dir.unwrap_or_else(|_| "the themes directory".into())
+                    ));
+                }
+                Ok(names) => {
+                    ctx.print("  Themes (*.toml in the themes directory):");
+                    for n in names {
+                        ctx.print(format!("    {n}"));
+                    }
+                }
+                Err(err) => return ctx.fail(format!("  Theme error: {err:#}")),
+            },
+            theme_name => match Theme::load(&*ctx.effects.files, theme_name) {
+                Ok(theme) => {
+                    ctx.send(AppEvent::SetTheme(Box::new(theme)));
+                    ctx.print(format!("  Switched to theme '{theme_name}'"));
+                }
+                Err(err) => return ctx.fail(format!("  Theme error: {err:#}")),
+            },
+        },
+        "bind" => match args.split_once(' ') {
+            _ if args.is_empty() => {
+                for (key, action) in ctx.config.keymap.entries() {
+                    ctx.print(format!("  {key:12} {action}"));
+                }
+            }
+            _ if args == "save" => ctx.send(AppEvent::SaveBindings),
+            Some((key, action)) => match key.parse::<KeyBinding>() {
+                Ok(key) => {
+                    let action = Action::parse(action.trim());
+                    ctx.print(format!("  {key} → {action}"));
+                    ctx.send(AppEvent::Bind(key, Some(action)));
+                }
+                Err(err) => return ctx.fail(format!("  {err}")),
+            },
+            None => return ctx.fail("  Usage: bind <key> <action|command>"),
+        },
+        "unbind" => match args.parse::<KeyBinding>() {
+            Ok(key) if ctx.config.keymap.get_binding(key).is_some() => {
+                ctx.send(AppEvent::Bind(key, None));
+            }
+            Ok(key) => return ctx.fail(format!("  {key} isn't bound")),
+            Err(err) => return ctx.fail(format!("  {err}")),
+        },
+        "keys" => {
+            let rows = ctx
+                .config
+                .keymap
+                .entries()
+                .into_iter()
+                .map(|(key, action)| {
+                    let kind = match Action::parse(&action) {
+                        Action::Command(_) => "command",
+                        _ => "built-in",
+                    };
+                    vec![key, action, kind.to_string()]
+                });
+            let table = TableView::new("Key bindings", ["Key", "Action", "Kind"]).rows(rows);
+            ctx.show(table.sorted_by(0));
+        }
+        "tree" => {
+            // A big or slow (network) directory takes a moment to list
+            let dir = if args.is_empty() { "." } else { args }.to_string();
+            let (loading, slot) = Loading::new(format!("Tree: {dir}"));
+            ctx.show(loading);
+            let files = ctx.effects.files.clone();
+            ctx.spawner.spawn(move |_| {
+                if let Err(err) = files.read_dir(Path::new(&dir)) {
+                    return slot.failed(format!("Can't read {dir}: {err}"));
+                }
+                let root = TreeNode::branch(&dir, directory_nodes(&*files, &dir)).expanded();
+                slot.ready(
+                    TreeView::new(format!("Tree: {dir}"), vec![root])
+                        .loader(move |dir| directory_nodes(&*files, dir)),
+                );
+            });
+        }
+        "chart" => {
+            // A live demo: three series recorded from a background job
+            let window = match args {
+                "" => 30,
+                secs => match secs.parse::<u64>() {
+                    Ok(secs) => secs,
+                    Err(_) => return ctx.fail("  Usage: chart [window-secs]"),
+                },
+            };
+            let (chart, feed) = ChartView::new("Chart demo");
+            ctx.show(chart.window(Duration::from_secs(window)));
+            ctx.spawner.spawn_watcher(move |_| {
+                for tick in 0..1200 {
+                    if feed.closed() {
+                        break;
+                    }
+                    let t = tick as f64 / 10.0;
+                    feed.record("sin", t.sin() * 10.0);
+                    feed.record("cos", (t / 2.0).cos() * 6.0);
+                    feed.record("drift", (t / 20.0) - 3.0);
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+            });
+        }
+        "diff" => {
+            let [a, b] = args.split_whitespace().collect::<Vec<_>>()[..] else {
+                return ctx.fail("  Usage: diff <file-a> <file-b>");
+            };
+            let read = |path: &str| {
+                (ctx.effects.files.read(Path::new(path)))
+                    .map_err(|err| format!("  Can't read {path}: {err}"))
+            };
+            let (old, new) = match (read(a), read(b)) {
+                (Ok(old), Ok(new)) => (old, new),
+                (Err(err), _) | (_, Err(err)) => return ctx.fail(err),
+            };
+            // Diffing big files takes a while; do it off the UI thread
+            let (a, b) = (a.to_string(), b.to_string());
+            let (loading, slot) = Loading::new(format!("Diff: {a} → {b}"));
+            ctx.show(loading);
+            ctx.spawner.spawn(move |_| {
+                let diff = DiffView::new(format!("Diff: {a} → {b}"), &old, &new);
+                slot.ready(diff.labels(&a, &b));
+            });
+        }
+        "hex" => {
+            if args.is_empty() {
+                return ctx.fail("  Usage: hex <file>");
+            }
+            match ctx.effects.files.metadata(Path::new(args)) {
+                Ok(meta) if !meta.is_dir => {}
+                Ok(_) => return ctx.fail(format!("  {args} isn't a file")),
+                Err(err) => return ctx.fail(format!("  Can't read {args}: {err}")),
+            }
+            let path = args.to_string();
+            let (loading, slot) = Loading::new(format!("Hex: {path}"));
+            let files = ctx.effects.files.clone();
+            ctx.show(loading);
+            ctx.spawner
+                .spawn(move |_| match files.read_bytes(Path::new(&path)) {
+                    Ok(bytes) => slot.ready(HexView::new(format!("Hex: {path}"), bytes)),
+                    Err(err) => slot.failed(format!("Can't read {path}: {err}")),
+                });
+        }
+        "http" => {
+            #[cfg(feature = "http")]
+            return http::command(ctx, args);
+            #[cfg(not(feature = "http"))]
+            return ctx.fail("  Can't make requests: built without the `http` feature");
+        }
+        "ws" => {
+            #[cfg(feature = "websocket")]
+            return websocket::command(ctx, args);
+            #[cfg(not(feature = "websocket"))]
+            return ctx.fail("  Can't open WebSockets: built without the `websocket` feature");
+        }
+        "net" => return net::command(ctx, args),
+        "ssh" => return ssh::command(ctx, args),
+        "mqtt" => {
+            #[cfg(feature = "mqtt")]
+            return mqtt::command(ctx, args);
+            #[cfg(not(feature = "mqtt"))]
+            return ctx.fail("  Can't use MQTT: built without the `mqtt` feature");
+        }
+        "serial" => {
+            #[cfg(feature = "serial")]
+            return serial::command(ctx, args);
+            #[cfg(not(feature = "serial"))]
+            return ctx.fail("  Can't open serial ports: built without the `serial` feature");
+        }
+        "git" => {
+            #[cfg(feature = "git")]
+            return git::command(ctx, args);
+            #[cfg(not(feature = "git"))]
+            return ctx.fail("  Can't show git status: built without the `git` feature");
+        }
+        "sysinfo" => {
+            #[cfg(feature = "sysinfo")]
+            return sysinfo::command(ctx, args);
+            #[cfg(not(feature = "sysinfo"))]
+            return ctx.fail("  Can't show system metrics: built without the `sysinfo` feature");
+        }
+        "ps" => {
+            #[cfg(feature = "sysinfo")]
+            return ps::command(ctx, args);
+            #[cfg(not(feature = "sysinfo"))]
+            return ctx.fail("  Can't list processes: built without the `sysinfo` feature");
+        }
+        "docker" => {
+            #[cfg(feature = "docker")]
+            return docker::command(ctx, args);
+            #[cfg(not(feature = "docker"))]
+            return ctx.fail("  Can't reach Docker: built without the `docker` feature");
+        }
+        "feed" => {
+            #[cfg(feature = "feeds")]
+            return feeds::command(ctx, args);
+            #[cfg(not(feature = "feeds"))]
+            return ctx.fail("  Can't read feeds: built without the `feeds` feature");
+        }
+        "grpc" => {
+            #[cfg(feature = "grpc")]
+            return grpc::command(ctx, args);
+            #[cfg(not(feature = "grpc"))]
+            return ctx.fail("  Can't call gRPC: built without the `grpc` feature");
+        }
+        "k8s" => {
+            #[cfg(feature = "k8s")]
+            return k8s::command(ctx, args);
+            #[cfg(not(feature = "k8s"))]
+            return ctx.fail("  Can't reach Kubernetes: built without the `k8s` feature");
+        }
+        "media" => {
+            #[cfg(feature = "media")]
+            return media::command(ctx, args);
+            #[cfg(not(feature = "media"))]
+            return ctx.fail("  Can't reach media players: built without the `media` feature");
+        }
+        "sql" => {
+            #[cfg(feature = "sqlite")]
+            return sql::command(ctx, args);
+            #[cfg(not(feature = "sqlite"))]
+            return ctx.fail("  Can't open databases: built without the `sqlite` feature");
+        }
+        "edit" => {
+            // Options first; the rest is the path, spaces and all
+            let (mut preview, mut line, mut path) = (false, 1, args);
+            loop {
+                let (word, rest) = path.split_once(' ').unwrap_or((path, ""));
+                match word.strip_prefix('+').map(str::parse) {
+                    _ if word == "--preview" => preview = true,
+                    Some(Ok(n)) => line = n,
+                    Some(Err(_)) => return ctx.fail("  Usage: edit [--preview] [+line] <file>"),
+                    None => break,
+                }
+                path = rest.trim_start();
+            }
+            if path.is_empty() {
+                return ctx.fail("  Usage: edit [--preview] [+line] <file>");
+            }
+            let editor = EditorView::open(ctx.effects.files.clone(), path).at_line(line);
+            match preview {
+                true => {
+                    let (editor, live) = editor.live();
+                    ctx.show(Split::new(editor, MarkdownView::live("Preview", live)));
+                }
+                false => ctx.show(editor),
+            }
+        }
+        "show" => {
+            let Some(("image", path)) = args.split_once(' ') else {
+                return ctx.fail("  Usage: show image <path>");
+            };
+            #[cfg(feature = "images")]
+            {
+                // Decoding a big picture takes a moment
+                let path = path.trim().to_string();
+                let (loading, slot) = Loading::new(format!("Image: {path}"));
+                let files = ctx.effects.files.clone();
+                ctx.show(loading);
+                ctx.spawner
+                    .spawn(move |_| match widgets::ImageView::open(&*files, &path) {
+                        Ok(image) => slot.ready(image),
+                        Err(err) => slot.failed(format!("{err:#}")),
+                    });
+            }
+            #[cfg(not(feature = "images"))]
+            return ctx.fail(format!(
+                "  Can't show {}: built without the `images` feature",
+                path.trim()
+            ));
+        }
+        "preview" => {
+            if args.is_empty() {
+                return ctx.fail("  Usage: preview <file.md>");
+            }
+            ctx.show(MarkdownView::file(ctx.effects.files.clone(), args));
+        }
+        "close" => ctx.send(AppEvent::ClosePane),
+        "filter" => {
+            if args.is_empty() {
+                ctx.send(AppEvent::FilterOutput(None));
+                return Status::Success;
+            }
+            let (ignore_case, pattern) = match args.strip_prefix("-i ") {
+                Some(rest) => (true, rest.trim_start()),
+                None => (false, args),
+            };
+            match RegexBuilder::new(pattern)
+                .case_insensitive(ignore_case)
+                .build()
+            {
+                Ok(regex) => ctx.send(AppEvent::FilterOutput(Some(regex))),
+                Err(err) => {
+                    // The last line of regex's message says what's wrong, as in grep.rs
+                    let text = err.to_string();
+                    let why = text.lines().last().unwrap_or_default();
+                    return ctx.fail(format!(
+                        "  Bad pattern: {}",
+                        why.trim_start_matches("error: ")
+                    ));
+                }
+            }
+        }
+        "clear" => {
+            ctx.clear();
+        }
+        "quit" => {
+            ctx.quit();
+        }
+        _ => {
+            return ctx.fail(format!("  Unknown command: '{}'. Try 'help'.", cmd.trim()));
+        }
+    }
+    Status::Success
+}
+
+// Replace $name with the value of variable `name`. Unknown variables are
+// left as typed, so a stray `$` doesn't silently vanish.
+fn expand_vars(vars: &BTreeMap<String, String>, cmd: &str) -> String {
+    let mut out = String::with_capacity(cmd.len());
+    let mut rest = cmd;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let len = after
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        match vars.get(&after[..len]) {
+            Some(value) if len > 0 => out.push_str(value),
+            _ => out.push_str(&rest[start..start + 1 + len]),
+        }
+        rest = &after[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// The `tree` command's loader: a directory's entries, subdirectories first,
+// each loaded only when it's expanded
+fn directory_nodes(files: &dyn Files, dir: &str) -> Vec<TreeNode> {
+    let Ok(entries) = files.read_dir(Path::new(dir)) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<(bool, String)> = entries
+        .into_iter()
+        .filter_map(|path| {
+            let is_dir = files.metadata(&path).is_ok_and(|meta| meta.is_dir);
+            Some((!is_dir, path.file_name()?.to_string_lossy().into_owned()))
+        })
+        .collect();
+    entries.sort();
+    entries
+        .into_iter()
+        .map(|(is_file, name)| {
+            let path = Path::new(dir).join(&name);
+            match is_file {
+                true => TreeNode::leaf(name),
+                false => TreeNode::lazy(name, path.to_string_lossy()),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,206 @@
+// =============================================================================
+// Log view — `logs`: the app's own tracing events in a pane, as they happen
+//
+//   > logs                     everything --log-level lets through
+//   > logs warn                warnings and errors only
+//   > logs debug sql           debug and up, from targets starting "tui_template::sql"
+//                              (or any target starting "sql")
+//
+// logging.rs hands every event to Capture as well as to the log file, and
+// the last KEEP are held here, so the pane opens with what already
+// happened and grows as more arrives. It's a table, so `/` filters it
+// further (`message=timeout`) and 1–4 sort it. Times are UTC, as in the file.
+// =============================================================================
+
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::event::KeyEvent;
+use ratatui::{Frame, layout::Rect};
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::{
+    ctx::{Ctx, Status},
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+    widgets::TableView,
+};
+
+const KEEP: usize = 1000;
+
+struct Record {
+    seq: u64, // counts up from 0, so a view knows what it's already shown
+    time: String,
+    level: Level,
+    target: String,
+    message: String,
+}
+
+static RECORDS: Mutex<VecDeque<Record>> = Mutex::new(VecDeque::new());
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let mut words = args.split_whitespace();
+    let level = match words.next() {
+        None => Level::TRACE,
+        Some(word) => match word.parse() {
+            Ok(level) => level,
+            Err(_) => return ctx.fail("  Usage: logs [error|warn|info|debug|trace] [target]"),
+        },
+    };
+    ctx.show(LogView::new(level, words.next().map(str::to_string)));
+    Status::Success
+}
+
+// The tracing layer that keeps events for the pane (see logging.rs)
+pub struct Capture;
+
+impl<S: Subscriber> Layer<S> for Capture {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        let Ok(mut records) = RECORDS.lock() else {
+            return;
+        };
+        let seq = records.back().map_or(0, |r| r.seq + 1);
+        records.push_back(Record {
+            seq,
+            time: utc_time(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: fields.message + &fields.rest,
+        });
+        if records.len() > KEEP {
+            records.pop_front();
+        }
+    }
+}
+
+// "executed command=help status=Success"
+#[derive(Default)]
+struct Fields {
+    message: String,
+    rest: String,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            name => _ = write!(self.rest, " {name}={value}"),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            name => _ = write!(self.rest, " {name}={value:?}"),
+        }
+    }
+}
+
+// HH:MM:SS.mmm
+fn utc_time() -> String {
+    let since = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = since.as_secs() % 86_400;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        since.subsec_millis()
+    )
+}
+
+pub struct LogView {
+    table: TableView,
+    level: Level,           // the least severe shown
+    target: Option<String>, // a prefix, with or without the crate's name
+    next: u64,              // the first record not looked at yet
+}
+
+impl LogView {
+    fn new(level: Level, target: Option<String>) -> Self {
+        let mut view = Self {
+            table: TableView::new("Logs", ["Time", "Level", "Target", "Message"]),
+            level,
+            target,
+            next: 0,
+        };
+        view.pull();
+        view
+    }
+
+    // Add what's arrived since last time; true if any of it is shown
+    fn pull(&mut self) -> bool {
+        let Ok(records) = RECORDS.lock() else {
+            return false;
+        };
+        let crate_name = env!("CARGO_PKG_NAME").replace('-', "_");
+        let rows: Vec<Vec<String>> = records
+            .iter()
+            .filter(|r| r.seq >= self.next)
+            .filter(|r| r.level <= self.level) // ERROR is the "smallest"
+            .filter(|r| {
+                self.target.as_ref().is_none_or(|t| {
+                    r.target.starts_with(t.as_str())
+                        || r.target.starts_with(&format!("{crate_name}::{t}"))
+                })
+            })
+            .map(|r| {
+                vec![
+                    r.time.clone(),
+                    r.level.to_string(),
+                    r.target.clone(),
+                    r.message.clone(),
+                ]
+            })
+            .collect();
+        self.next = records.back().map_or(0, |r| r.seq + 1);
+        let any = !rows.is_empty();
+        if any {
+            self.table.push_rows(rows);
+        }
+        any
+    }
+}
+
+impl Pane for LogView {
+    fn title(&self) -> String {
+        let mut title = self.table.title();
+        if self.level != Level::TRACE {
+            title += &format!(" — {} and up", self.level);
+        }
+        if let Some(target) = &self.target {
+            title += &format!(" — {target}");
+        }
+        title
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.table.handle_key(key)
+    }
+
+    fn tick(&mut self) -> bool {
+        self.pull()
+    }
+
+    fn hints(&self) -> Hints {
+        self.table.hints()
+    }
+}
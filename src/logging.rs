@@ -0,0 +1,71 @@
+// =============================================================================
+// Logging — tracing events go to a rolling file, never to the terminal
+//
+// Anything written to stdout/stderr while the TUI is up lands in the middle
+// of the UI, so logs go to <state dir>/logs/<app>.YYYY-MM-DD.log instead
+// (see paths.rs). One file per day, the last week kept.
+//
+// Use the tracing macros anywhere:
+//
+//   tracing::info!(command = %cmd, "executing");
+//   tracing::warn!("config error: {err:#}");
+//
+// and follow along with `tail -f ~/.local/state/<app>/logs/*.log`, or
+// inside the app with `logs` (see log_view.rs), which works even when the
+// file can't be written.
+// =============================================================================
+
+use std::fs;
+
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, WrapErr},
+};
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::{log_view::Capture, paths};
+
+const DEFAULT_LEVEL: &str = "info";
+const KEEP_FILES: usize = 7;
+
+// --log-level is validated by cli.rs, so errors here are about the log file.
+// Keep the returned guard alive for the whole program — dropping it flushes
+// and stops the background writer thread.
+pub fn init(level: Option<&str>) -> Result<WorkerGuard> {
+    let filter =
+        EnvFilter::try_new(level.unwrap_or(DEFAULT_LEVEL)).wrap_err("invalid --log-level")?;
+    let (writer, guard) = match log_file() {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(writer), Ok(guard))
+        }
+        Err(err) => (None, Err(err)),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(writer.map(|writer| fmt::layer().with_writer(writer).with_ansi(false)))
+        .with(Capture)
+        .init();
+
+    guard
+}
+
+fn log_file() -> Result<RollingFileAppender> {
+    let dir = paths::state_dir()
+        .ok_or_eyre("can't find a state directory (is $HOME set?)")?
+        .join("logs");
+    fs::create_dir_all(&dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(env!("CARGO_PKG_NAME"))
+        .filename_suffix("log")
+        .max_log_files(KEEP_FILES)
+        .build(&dir)
+        .wrap_err_with(|| format!("opening a log file in {}", dir.display()))
+}
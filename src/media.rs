@@ -0,0 +1,190 @@
+// =============================================================================
+// Media — `media`: what's playing, and play / pause / next, over D-Bus MPRIS
+// (the `media` feature)
+//
+//   cargo run --features media
+//   > media                    the track, and which players are running
+//   > media toggle
+//   > media next
+//
+//   media play|pause|toggle|next|prev
+//
+// Players (Spotify, VLC, mpv with mpv-mpris, browsers…) answer on the
+// session bus the way the desktop's media keys expect, so this works
+// wherever one runs — on Linux, normally. With several players, commands go
+// to the one that's playing, else the first. The status bar shows what's
+// playing, re-read every POLL by a background thread.
+// =============================================================================
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, mpsc::Sender},
+    thread,
+    time::Duration,
+};
+
+use zbus::{
+    blocking::{Connection, Proxy, fdo::DBusProxy, proxy::Builder},
+    proxy::CacheProperties,
+    zvariant::OwnedValue,
+};
+
+use crate::ctx::{AppEvent, Ctx, Status};
+
+const POLL: Duration = Duration::from_secs(2);
+const PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let method = match args.trim() {
+        "" => None,
+        "play" => Some("Play"),
+        "pause" => Some("Pause"),
+        "toggle" => Some("PlayPause"),
+        "next" => Some("Next"),
+        "prev" => Some("Previous"),
+        _ => return ctx.fail("  Usage: media [play | pause | toggle | next | prev]"),
+    };
+    // D-Bus calls wait on the player, so not on the UI thread
+    ctx.spawner.spawn(move |tx| {
+        let result = match method {
+            Some(method) => control(method),
+            None => list(&tx),
+        };
+        if let Err(why) = result {
+            let _ = tx.send(AppEvent::Print(format!("  {why}")));
+        }
+    });
+    Status::Success
+}
+
+fn list(tx: &Sender<AppEvent>) -> Result<(), String> {
+    let bus = session()?;
+    let players = players(&bus)?;
+    if players.is_empty() {
+        return Err("No media players running".into());
+    }
+    for (name, player) in &players {
+        let status: String = player.get_property("PlaybackStatus").unwrap_or_default();
+        let track = track(player).unwrap_or_else(|| "nothing".into());
+        let _ = tx.send(AppEvent::Print(format!("  {name:<16} {status:<8} {track}")));
+    }
+    Ok(())
+}
+
+fn control(method: &str) -> Result<(), String> {
+    let bus = session()?;
+    let Some((name, player)) = current(&bus)? else {
+        return Err("No media players running".into());
+    };
+    player
+        .call_method(method, &())
+        .map(|_| ())
+        .map_err(|err| format!("{name}: {err}"))
+}
+
+fn session() -> Result<Connection, String> {
+    Connection::session().map_err(|err| format!("Can't reach the session bus: {err}"))
+}
+
+// Every player on the bus, as (short name, e.g. "spotify", proxy)
+fn players(bus: &Connection) -> Result<Vec<(String, Proxy<'static>)>, String> {
+    let names = DBusProxy::new(bus)
+        .map_err(zbus::fdo::Error::from)
+        .and_then(|dbus| dbus.list_names())
+        .map_err(|err| format!("Can't list D-Bus names: {err}"))?;
+    let mut players: Vec<_> = names
+        .iter()
+        .filter_map(|name| {
+            let short = name.strip_prefix(PREFIX)?.to_string();
+            // Properties change under us; read them fresh each time
+            let proxy = Builder::new(bus)
+                .destination(name.to_string())
+                .and_then(|b| b.path("/org/mpris/MediaPlayer2"))
+                .and_then(|b| b.interface("org.mpris.MediaPlayer2.Player"))
+                .map(|b| b.cache_properties(CacheProperties::No))
+                .and_then(|b| b.build())
+                .ok()?;
+            Some((short, proxy))
+        })
+        .collect();
+    players.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(players)
+}
+
+// The player that's playing, else the first
+fn current(bus: &Connection) -> Result<Option<(String, Proxy<'static>)>, String> {
+    let mut players = players(bus)?;
+    let playing = players.iter().position(|(_, p)| playing(p));
+    Ok(match playing {
+        Some(i) => Some(players.swap_remove(i)),
+        None => players.into_iter().next(),
+    })
+}
+
+fn playing(player: &Proxy) -> bool {
+    player
+        .get_property::<String>("PlaybackStatus")
+        .is_ok_and(|s| s == "Playing")
+}
+
+// "Artist – Title", or just the title
+fn track(player: &Proxy) -> Option<String> {
+    let metadata: HashMap<String, OwnedValue> = player.get_property("Metadata").ok()?;
+    let text = |key: &str| String::try_from(metadata.get(key)?.try_clone().ok()?).ok();
+    let title = text("xesam:title").filter(|t| !t.is_empty())?;
+    let artists = metadata
+        .get("xesam:artist")
+        .and_then(|a| Vec::<String>::try_from(a.try_clone().ok()?).ok())
+        .unwrap_or_default();
+    Some(match artists.is_empty() {
+        true => title,
+        false => format!("{} – {title}", artists.join(", ")),
+    })
+}
+
+// What the status bar shows; empty until watch() and while nothing plays
+#[derive(Default)]
+pub struct NowPlaying(Arc<Mutex<Shown>>);
+
+#[derive(Default)]
+struct Shown {
+    track: Option<String>,
+    changed: bool, // since the app last asked
+}
+
+impl NowPlaying {
+    // Follow the playing track until the app (holding the other Arc) is gone
+    pub fn watch(&self) {
+        let shown = self.0.clone();
+        thread::spawn(move || {
+            let Ok(bus) = Connection::session() else {
+                return; // no session bus, so no players either
+            };
+            while Arc::strong_count(&shown) > 1 {
+                let track = current(&bus)
+                    .ok()
+                    .flatten()
+                    .filter(|(_, p)| playing(p))
+                    .and_then(|(_, p)| track(&p));
+                if let Ok(mut shown) = shown.lock()
+                    && shown.track != track
+                {
+                    shown.track = track;
+                    shown.changed = true;
+                }
+                thread::sleep(POLL);
+            }
+        });
+    }
+
+    pub fn track(&self) -> Option<String> {
+        self.0.lock().ok()?.track.clone()
+    }
+
+    // True once per change, so the status bar gets redrawn
+    pub fn changed(&self) -> bool {
+        self.0
+            .lock()
+            .is_ok_and(|mut shown| std::mem::take(&mut shown.changed))
+    }
+}
@@ -0,0 +1,284 @@
+// =============================================================================
+// Menu bar — File / View / Help style dropdowns whose items run commands
+//
+// Shown as a one-row panel when the layout has one:
+//
+//   [layout]
+//   panels = [
+//       { kind = "menu",   size = { length = 1 } },
+//       { kind = "output", size = "fill" },
+//       { kind = "input",  size = { length = 3 } },
+//   ]
+//
+// F10 (the `menu` action) opens the first menu, with or without the bar;
+// with the bar, alt-<letter> opens the menu starting with that letter. In
+// a menu, ←/→ switch menus, ↑/↓ pick an item, Enter runs it, Esc closes.
+//
+// The menus are config, not code — any command can be an item, and a key
+// bound to the same command is shown beside it:
+//
+//   [[menu]]
+//   title = "Tools"
+//   items = [
+//       { label = "Say hello", command = "hello" },
+//       { label = "Live chart", command = "chart 60" },
+//   ]
+//
+// Setting [[menu]] at all replaces the default menus below.
+// =============================================================================
+
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hints::Hints,
+    keymap::Keymap,
+    layout::LayoutConfig,
+    overlay::{Outcome, Overlay},
+    theme::Theme,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Menu {
+    pub title: String,
+    pub items: Vec<MenuItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MenuItem {
+    pub label: String,
+    pub command: String,
+}
+
+pub fn defaults() -> Vec<Menu> {
+    let menu = |title: &str, items: &[(&str, &str)]| Menu {
+        title: title.into(),
+        items: items
+            .iter()
+            .map(|&(label, command)| MenuItem {
+                label: label.into(),
+                command: command.into(),
+            })
+            .collect(),
+    };
+    vec![
+        menu(
+            "File",
+            &[
+                ("Save session", "session save"),
+                ("Export…", "export"),
+                ("Import…", "import"),
+                ("Quit", "quit"),
+            ],
+        ),
+        menu(
+            "View",
+            &[
+                ("Clear output", "clear"),
+                ("Theme…", "theme"),
+                ("Key bindings", "keys"),
+                ("Close pane", "close"),
+            ],
+        ),
+        menu("Help", &[("Commands", "help"), ("Configuration", "config")]),
+    ]
+}
+
+// Where each title starts on the bar, relative to its left edge
+fn title_offsets(menus: &[Menu]) -> Vec<u16> {
+    let mut x = 1;
+    menus
+        .iter()
+        .map(|menu| {
+            let at = x;
+            x += menu.title.chars().count() as u16 + 2;
+            at
+        })
+        .collect()
+}
+
+// The menu whose title starts with `letter`, for alt-<letter>
+pub fn find(menus: &[Menu], letter: char) -> Option<usize> {
+    let letter = letter.to_ascii_lowercase();
+    menus.iter().position(|m| {
+        m.title
+            .chars()
+            .next()
+            .is_some_and(|c| c.to_ascii_lowercase() == letter)
+    })
+}
+
+// The bar itself; `open` is the menu to highlight, if one is showing
+pub fn render_bar(
+    frame: &mut Frame,
+    area: Rect,
+    menus: &[Menu],
+    open: Option<usize>,
+    theme: &Theme,
+) {
+    let bar = Style::default().fg(theme.status_fg).bg(theme.status_bg);
+    let mut spans = vec![Span::styled(" ", bar)];
+    for (i, menu) in menus.iter().enumerate() {
+        let style = match open == Some(i) {
+            true => bar.add_modifier(Modifier::REVERSED),
+            false => bar,
+        };
+        // The first letter is underlined: it's the alt-<letter> shortcut
+        let mut chars = menu.title.chars();
+        let first = chars.next().map(String::from).unwrap_or_default();
+        spans.push(Span::styled(" ", style));
+        spans.push(Span::styled(
+            first,
+            style.add_modifier(Modifier::UNDERLINED),
+        ));
+        spans.push(Span::styled(format!("{} ", chars.as_str()), style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)).style(bar), area);
+    let hint = Paragraph::new("F10 ").style(bar.add_modifier(Modifier::DIM));
+    frame.render_widget(hint.right_aligned(), area);
+}
+
+// =============================================================================
+// An open menu — a popup answering with the command to run
+// =============================================================================
+pub struct MenuBar {
+    menus: Vec<Menu>,
+    layout: LayoutConfig, // to find the bar row as the screen resizes
+    shortcuts: HashMap<String, String>, // command → the key bound to it
+    open: usize,
+    selected: usize,
+}
+
+impl MenuBar {
+    pub fn new(menus: &[Menu], keymap: &Keymap, layout: &LayoutConfig, open: usize) -> Self {
+        let shortcuts = keymap
+            .entries()
+            .into_iter()
+            .map(|(key, action)| (action, key))
+            .collect();
+        Self {
+            menus: menus.to_vec(),
+            layout: layout.clone(),
+            shortcuts,
+            open: open.min(menus.len().saturating_sub(1)),
+            selected: 0,
+        }
+    }
+
+    fn items(&self) -> &[MenuItem] {
+        self.menus.get(self.open).map_or(&[], |m| &m.items)
+    }
+
+    fn switch(&mut self, step: isize) {
+        let count = self.menus.len().max(1) as isize;
+        self.open = (self.open as isize + step).rem_euclid(count) as usize;
+        self.selected = 0;
+    }
+}
+
+impl Overlay for MenuBar {
+    type Answer = String;
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        // Over the bar when there is one, otherwise along the top row
+        let screen = frame.area();
+        let row = self.layout.split(screen).menu.unwrap_or(Rect {
+            height: 1,
+            ..screen
+        });
+        render_bar(frame, row, &self.menus, Some(self.open), theme);
+
+        let items = self.items();
+        let keys: Vec<&str> = items
+            .iter()
+            .map(|item| self.shortcuts.get(&item.command).map_or("", String::as_str))
+            .collect();
+        let label_width = items
+            .iter()
+            .map(|i| i.label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let key_width = keys.iter().map(|k| k.chars().count()).max().unwrap_or(0);
+        let gap = if key_width > 0 { 3 } else { 0 };
+        let width = (label_width + gap + key_width + 4) as u16;
+        let x = row.x
+            + title_offsets(&self.menus)
+                .get(self.open)
+                .copied()
+                .unwrap_or(0);
+        let area = Rect {
+            x,
+            y: row.bottom(),
+            width,
+            height: items.len() as u16 + 2,
+        }
+        .intersection(screen);
+
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let lines: Vec<Line> = items
+            .iter()
+            .zip(&keys)
+            .enumerate()
+            .map(|(i, (item, key))| {
+                let style = match i == self.selected {
+                    true => Style::default().add_modifier(Modifier::REVERSED),
+                    false => Style::default(),
+                };
+                let label = format!(" {:label_width$}{:gap$}", item.label, "");
+                Line::from(vec![
+                    Span::styled(label, style),
+                    Span::styled(format!("{key:>key_width$} "), style.patch(dim)),
+                ])
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.input_border));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<String> {
+        let count = self.items().len();
+        match key.code {
+            KeyCode::Esc | KeyCode::F(10) => return Outcome::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::ALT) => {
+                if let Some(i) = find(&self.menus, c) {
+                    self.open = i;
+                    self.selected = 0;
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(item) = self.items().get(self.selected) {
+                    return Outcome::Done(item.command.clone());
+                }
+            }
+            KeyCode::Left => self.switch(-1),
+            KeyCode::Right => self.switch(1),
+            KeyCode::Up if count > 0 => self.selected = (self.selected + count - 1) % count,
+            KeyCode::Down if count > 0 => self.selected = (self.selected + 1) % count,
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.selected = count.saturating_sub(1),
+            _ => {}
+        }
+        Outcome::Open
+    }
+
+    fn hints(&self) -> Hints {
+        &[("←→", "menus"), ("Enter", "run"), ("Esc", "close")]
+    }
+}
@@ -0,0 +1,122 @@
+// =============================================================================
+// Metrics — named values drawn as sparklines (or gauges) in the side panel
+//
+// Anything that can send an AppEvent can report a value; the first sample
+// registers the metric:
+//
+//   let _ = tx.send(AppEvent::Metric { name: "cpu".into(), value: 37.5, max: Some(100.0) });
+//   let _ = tx.send(AppEvent::Metric { name: "req/s".into(), value: 812.0, max: None });
+//
+// A metric with a max is a gauge: only its latest value is shown, as a bar
+// filled value/max of the way. Without one it's a sparkline of the last
+// HISTORY samples, scaled to the largest of them. Push from a background job
+// on whatever interval suits (see Spawner in ctx.rs) — the panel redraws as
+// samples arrive.
+//
+// The panel only exists when the layout has a side_panel, and only while no
+// pane is using it; with no metrics it shows render_side_panel()'s
+// placeholder.
+// =============================================================================
+
+use std::collections::{BTreeMap, VecDeque};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Gauge, Paragraph, Sparkline},
+};
+
+use crate::theme::Theme;
+
+// Samples kept per metric — wider than any sensible side panel
+const HISTORY: usize = 200;
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    series: BTreeMap<String, Series>, // drawn in name order
+}
+
+#[derive(Debug)]
+struct Series {
+    samples: VecDeque<f64>,
+    max: Option<f64>, // Some = gauge
+}
+
+impl Metrics {
+    pub fn push(&mut self, name: String, value: f64, max: Option<f64>) {
+        let series = self.series.entry(name).or_insert_with(|| Series {
+            samples: VecDeque::with_capacity(HISTORY),
+            max,
+        });
+        if series.samples.len() == HISTORY {
+            series.samples.pop_front();
+        }
+        series.samples.push_back(value);
+        series.max = max;
+    }
+
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    // One row per metric: a "name  value" line, then its gauge (1 row) or
+    // sparkline (2 rows). Metrics that don't fit are left off the bottom.
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let heights = self.series.values().map(|s| match s.max {
+            Some(_) => Constraint::Length(2),
+            None => Constraint::Length(3),
+        });
+        let rows = Layout::vertical(heights.chain([Constraint::Fill(1)])).split(area);
+
+        let label_style = Style::default().add_modifier(Modifier::BOLD);
+        let chart_style = Style::default().fg(theme.input_border);
+        for ((name, series), &row) in self.series.iter().zip(rows.iter()) {
+            let [label_row, chart_row] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(row);
+            let latest = series.samples.back().copied().unwrap_or(0.0);
+            let label = match series.max {
+                Some(max) => format!("{name}  {} / {}", number(latest), number(max)),
+                None => format!("{name}  {}", number(latest)),
+            };
+            frame.render_widget(Paragraph::new(label).style(label_style), label_row);
+
+            if let Some(max) = series.max {
+                let ratio = if max > 0.0 { latest / max } else { 0.0 };
+                let gauge = Gauge::default()
+                    .gauge_style(chart_style)
+                    .ratio(ratio.clamp(0.0, 1.0));
+                frame.render_widget(gauge, chart_row);
+            } else {
+                // Sparkline wants integers: scale the visible window to 0..=100
+                let shown = series.samples.len().min(chart_row.width as usize);
+                let window = series.samples.range(series.samples.len() - shown..);
+                let top = window.clone().fold(0.0_f64, |a, &b| a.max(b));
+                let data: Vec<u64> = window
+                    .map(|&v| match top > 0.0 {
+                        true => (v.max(0.0) / top * 100.0).round() as u64,
+                        false => 0,
+                    })
+                    .collect();
+                let sparkline = Sparkline::default().data(&data).max(100).style(chart_style);
+                frame.render_widget(sparkline, chart_row);
+            }
+        }
+    }
+}
+
+// Short enough for a narrow panel: 812, 37.5, 0.25
+fn number(value: f64) -> String {
+    if value.fract() == 0.0 || value.abs() >= 100.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
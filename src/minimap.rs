@@ -0,0 +1,103 @@
+// =============================================================================
+// Minimap — the whole scrollback squeezed into a column beside Output
+//
+//   [layout]
+//   minimap = true
+//
+// Each row of the column stands for a slice of the output: the shade says
+// how much text is there, red marks a slice with an error in it, and the
+// rows covering what's on screen are highlighted. Clicking (or dragging) in
+// the column scrolls Output there; PgUp/PgDn (scroll-up / scroll-down in
+// the keymap) scroll it a page at a time, and scrolling back to the bottom
+// follows new output again.
+//
+// Turning the minimap on also turns on mouse reporting, which means the
+// terminal's own click-and-drag selection needs Shift held. `mouse off`
+// (or F9, which toggles) hands the mouse back to the terminal for a while;
+// `mouse on` takes it again.
+// =============================================================================
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::theme::Theme;
+
+pub const WIDTH: u16 = 2;
+
+const SHADES: [&str; 5] = ["  ", "░░", "▒▒", "▓▓", "██"];
+
+// A line that looks like a failure, for the red markers
+fn is_error(line: &str) -> bool {
+    let line = line.to_lowercase();
+    line.contains("error") || line.contains("failed") || line.contains("unknown command")
+}
+
+// What the minimap needs of a line, worked out once as it's printed
+#[derive(Debug, Clone, Copy)]
+pub struct Summary {
+    chars: u32,
+    error: bool,
+}
+
+impl Summary {
+    pub fn of(line: &str) -> Self {
+        Self {
+            chars: line.chars().count().try_into().unwrap_or(u32::MAX),
+            error: is_error(line),
+        }
+    }
+}
+
+// How many lines each row of a `height`-row column stands for
+fn lines_per_row(total: usize, height: u16) -> usize {
+    total.div_ceil(height.max(1) as usize).max(1)
+}
+
+// `lines` sum up the scrollback; `top` and `visible` are the part Output shows
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    lines: &[Summary],
+    top: usize,
+    visible: usize,
+    theme: &Theme,
+) {
+    let per_row = lines_per_row(lines.len(), area.height);
+    let widest = lines
+        .iter()
+        .map(|l| l.chars as usize)
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let rows: Vec<Line> = lines
+        .chunks(per_row)
+        .enumerate()
+        .take(area.height as usize)
+        .map(|(row, chunk)| {
+            let chars: usize = chunk.iter().map(|l| l.chars as usize).sum();
+            let density = chars * (SHADES.len() - 1) / (widest * chunk.len());
+            let shade = SHADES[density.clamp(usize::from(chars > 0), SHADES.len() - 1)];
+            let first = row * per_row;
+            let mut style = Style::default().fg(theme.output_border);
+            if chunk.iter().any(|l| l.error) {
+                style = style.fg(Color::Red);
+            }
+            if first < top + visible && top < first + chunk.len() {
+                style = style.bg(theme.input_border);
+            }
+            Line::from(Span::styled(shade, style))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(rows), area);
+}
+
+// The line a click on row `y` of the column points at
+pub fn line_at(area: Rect, total: usize, y: u16) -> usize {
+    let row = y.saturating_sub(area.y).min(area.height.saturating_sub(1)) as usize;
+    (row * lines_per_row(total, area.height)).min(total.saturating_sub(1))
+}
@@ -0,0 +1,236 @@
+// =============================================================================
+// MQTT — `mqtt connect`, `mqtt sub`, `mqtt pub` (the `mqtt` feature)
+//
+//   cargo run --features mqtt
+//   > mqtt connect broker.local
+//   > mqtt sub sensors/+/temperature
+//   > mqtt pub lights/kitchen on
+//   [sensors/hall/temperature] 21.5
+//
+// One broker at a time, over plain TCP (port 1883 unless host:port says
+// otherwise; no TLS). A background job keeps the connection, printing each
+// message that arrives on a subscription, tagged with its topic like
+// `tail`, so several subscriptions can share Output. If the broker goes
+// away the job keeps trying, and subscribes again once it's back.
+//
+//   mqtt sub <topic>              + and # wildcards work
+//   mqtt unsub <topic>
+//   mqtt pub <topic> <payload>    the rest of the line, spaces and all
+//   mqtt disconnect               or `cancel` the job (see `jobs`)
+//
+// Messages are QoS 0 both ways — fine for watching and poking at devices.
+// =============================================================================
+
+use std::{
+    collections::BTreeSet,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rumqttc::{Client, Connection, Event, MqttOptions, Packet, QoS, RecvTimeoutError};
+
+use crate::ctx::{AppEvent, Ctx, ProgressHandle, Status};
+
+const PORT: u16 = 1883;
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const POLL: Duration = Duration::from_millis(250); // how often the job checks for `cancel`
+const RETRY: Duration = Duration::from_secs(2); // between reconnection attempts
+
+pub struct Session {
+    client: Client,
+    broker: String,                       // host:port, for messages
+    topics: Arc<Mutex<BTreeSet<String>>>, // subscribed to, for reconnecting
+    running: Arc<AtomicBool>,             // false once either this or the job is gone
+}
+
+// The job notices, disconnects and says so
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let (op, rest) = args.split_once(' ').unwrap_or((args, ""));
+    let rest = rest.trim();
+    match op {
+        "connect" if !rest.is_empty() => connect(ctx, rest),
+        "disconnect" => match connected(ctx).is_some() {
+            true => {
+                ctx.connections.mqtt = None;
+                Status::Success
+            }
+            false => ctx.fail("  Not connected"),
+        },
+        "sub" | "unsub" if !rest.is_empty() && !rest.contains(' ') => {
+            let Some(session) = connected(ctx) else {
+                return ctx.fail("  Not connected — `mqtt connect <host>` first");
+            };
+            let (result, verb) = if op == "sub" {
+                (
+                    session.client.try_subscribe(rest, QoS::AtMostOnce),
+                    "Subscribed to",
+                )
+            } else {
+                (session.client.try_unsubscribe(rest), "Unsubscribed from")
+            };
+            let broker = session.broker.clone();
+            if let Ok(mut topics) = session.topics.lock() {
+                match op {
+                    "sub" => topics.insert(rest.to_string()),
+                    _ => topics.remove(rest),
+                };
+            }
+            match result {
+                Ok(()) => {
+                    ctx.print(format!("  {verb} {rest} on {broker}"));
+                    Status::Success
+                }
+                Err(err) => ctx.fail(format!("  Can't {op} {rest}: {err}")),
+            }
+        }
+        "pub" if rest.contains(' ') => {
+            let (topic, payload) = rest.split_once(' ').unwrap_or((rest, ""));
+            let Some(session) = connected(ctx) else {
+                return ctx.fail("  Not connected — `mqtt connect <host>` first");
+            };
+            let result =
+                session
+                    .client
+                    .try_publish(topic, QoS::AtMostOnce, false, payload.as_bytes());
+            match result {
+                Ok(()) => Status::Success,
+                Err(err) => ctx.fail(format!("  Can't publish to {topic}: {err}")),
+            }
+        }
+        _ => ctx.fail(
+            "  Usage: mqtt connect <host[:port]> | sub <topic> | unsub <topic> \
+             | pub <topic> <payload> | disconnect",
+        ),
+    }
+}
+
+// The session, unless its job has been cancelled since
+fn connected<'a>(ctx: &'a mut Ctx) -> Option<&'a Session> {
+    if ctx
+        .connections
+        .mqtt
+        .as_ref()
+        .is_some_and(|s| !s.running.load(Ordering::SeqCst))
+    {
+        ctx.connections.mqtt = None;
+    }
+    ctx.connections.mqtt.as_ref()
+}
+
+fn connect(ctx: &mut Ctx, broker: &str) -> Status {
+    let (host, port) = match broker.rsplit_once(':') {
+        Some((host, port)) => match port.parse() {
+            Ok(port) => (host, port),
+            Err(_) => return ctx.fail(format!("  {port} isn't a port")),
+        },
+        None => (broker, PORT),
+    };
+    // Brokers drop an old connection when a new one uses its id, so make
+    // each one different
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let id = format!("tui-template-{}-{nanos:x}", std::process::id());
+    let mut options = MqttOptions::new(id, host, port);
+    options.set_keep_alive(KEEP_ALIVE);
+    let (client, connection) = Client::new(options, 64);
+
+    let broker = format!("{host}:{port}");
+    let session = Session {
+        client: client.clone(),
+        broker: broker.clone(),
+        topics: Arc::default(),
+        running: Arc::new(AtomicBool::new(true)),
+    };
+    let (topics, running) = (session.topics.clone(), session.running.clone());
+    ctx.connections.mqtt = Some(session); // replacing one disconnects it
+    ctx.print(format!("  Connecting to {broker}…"));
+    ctx.spawner
+        .spawn_with_progress(format!("mqtt {broker}"), move |tx, progress| {
+            progress.unknown();
+            run(
+                &tx, &progress, &running, &broker, &client, connection, &topics,
+            );
+            running.store(false, Ordering::SeqCst);
+        });
+    Status::Success
+}
+
+// Until cancelled or disconnected
+fn run(
+    tx: &Sender<AppEvent>,
+    progress: &ProgressHandle,
+    running: &AtomicBool,
+    broker: &str,
+    client: &Client,
+    mut connection: Connection,
+    topics: &Mutex<BTreeSet<String>>,
+) {
+    let print = |line: String| {
+        let _ = tx.send(AppEvent::Print(line));
+    };
+    let mut before = false; // connected at some point
+    let mut complained = false; // about the connection failing, since
+    while !progress.cancelled() && running.load(Ordering::SeqCst) {
+        match connection.recv_timeout(POLL) {
+            Ok(Ok(Event::Incoming(Packet::ConnAck(_)))) => {
+                if before {
+                    print(format!("  [mqtt] Reconnected to {broker}"));
+                    // A clean session forgets subscriptions
+                    let topics = topics.lock().map(|t| t.clone()).unwrap_or_default();
+                    for topic in topics {
+                        let _ = client.try_subscribe(topic, QoS::AtMostOnce);
+                    }
+                } else {
+                    print(format!("  [mqtt] Connected to {broker}"));
+                }
+                before = true;
+                complained = false;
+            }
+            Ok(Ok(Event::Incoming(Packet::Publish(message)))) => {
+                print(format!(
+                    "  [{}] {}",
+                    message.topic,
+                    payload(&message.payload)
+                ));
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(err)) => {
+                if !complained {
+                    print(format!("  [mqtt] {broker}: {err} — retrying"));
+                    complained = true; // once, until it's back
+                }
+                thread::sleep(RETRY);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    let _ = client.try_disconnect();
+    print(format!("  [mqtt] Disconnected from {broker}"));
+}
+
+// Text as text, with control characters escaped; anything else as a size
+fn payload(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text
+            .chars()
+            .map(|c| match c {
+                c if c.is_control() => c.escape_default().to_string(),
+                c => c.to_string(),
+            })
+            .collect(),
+        Err(_) => format!("<{} bytes>", bytes.len()),
+    }
+}
@@ -0,0 +1,103 @@
+// =============================================================================
+// Multiplexer — tmux or GNU screen between the app and the terminal
+//
+//   multiplexer::to_terminal(&mut out, "\x1b]52;c;aGk=\x07")?;   OSC 52, OSC 9…
+//   Multiplexer::current()                   Some(Tmux) inside tmux
+//
+// A multiplexer draws the screen itself, so escapes meant for the terminal
+// it runs in — the clipboard's OSC 52 (clipboard.rs), the notifications'
+// OSC 9 (notifications.rs) — stop there unless they're wrapped in a DCS
+// "passthrough" that it hands on as is:
+//
+//   tmux     ESC P tmux; <the escape, each ESC doubled> ESC \
+//            (with `set -g allow-passthrough on`, tmux 3.3 and later). An
+//            OSC 52 also goes out unwrapped, which `set -g set-clipboard on`
+//            takes into tmux's own paste buffers and on to the terminal.
+//   screen   ESC P <the escape> ESC \, cut into pieces short enough for
+//            screen's limit on one
+//
+// Neither speaks the kitty keyboard protocol, so it isn't asked for (see
+// keymap.rs), and screen can't report focus, so that isn't either (tmux
+// does, with `set -g focus-events on`). Without focus reports the terminal
+// counts as always focused, so nothing notifies.
+//
+// It's tmux if $TMUX is set, or $TERM starts with "tmux" (over SSH from
+// inside it, where $TMUX isn't passed on); screen if $STY is set.
+// =============================================================================
+
+use std::{
+    env,
+    io::{self, Write},
+    sync::OnceLock,
+};
+
+const SCREEN_CHUNK: usize = 76; // bytes per passthrough; screen drops a long one
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    // The one the app is running inside, if any (looked up once)
+    pub fn current() -> Option<Self> {
+        static CURRENT: OnceLock<Option<Multiplexer>> = OnceLock::new();
+        *CURRENT.get_or_init(|| {
+            Self::from_env(
+                env::var_os("TMUX").is_some(),
+                env::var_os("STY").is_some(),
+                &env::var("TERM").unwrap_or_default(),
+            )
+        })
+    }
+
+    pub fn from_env(tmux: bool, sty: bool, term: &str) -> Option<Self> {
+        if tmux || term.starts_with("tmux") {
+            Some(Self::Tmux)
+        } else if sty {
+            Some(Self::Screen)
+        } else {
+            None
+        }
+    }
+
+    // `escape` wrapped so the multiplexer passes it on to the terminal
+    pub fn passthrough(self, escape: &str) -> String {
+        match self {
+            Self::Tmux => format!("\x1bPtmux;{}\x1b\\", escape.replace('\x1b', "\x1b\x1b")),
+            Self::Screen => {
+                // Cut between characters, so each piece is valid UTF-8
+                let mut wrapped = String::new();
+                let mut piece = String::new();
+                for c in escape.chars() {
+                    if piece.len() + c.len_utf8() > SCREEN_CHUNK {
+                        wrapped += &format!("\x1bP{piece}\x1b\\");
+                        piece.clear();
+                    }
+                    piece.push(c);
+                }
+                wrapped + &format!("\x1bP{piece}\x1b\\")
+            }
+        }
+    }
+
+    pub fn reports_focus(self) -> bool {
+        self == Self::Tmux
+    }
+}
+
+// Write `escape`, which is for the terminal itself, so that it gets there
+pub fn to_terminal(out: &mut impl Write, escape: &str) -> io::Result<()> {
+    match Multiplexer::current() {
+        None => out.write_all(escape.as_bytes())?,
+        Some(Multiplexer::Tmux) => {
+            out.write_all(escape.as_bytes())?; // for set-clipboard
+            out.write_all(Multiplexer::Tmux.passthrough(escape).as_bytes())?;
+        }
+        Some(Multiplexer::Screen) => {
+            out.write_all(Multiplexer::Screen.passthrough(escape).as_bytes())?
+        }
+    }
+    out.flush()
+}
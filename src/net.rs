@@ -0,0 +1,463 @@
+// =============================================================================
+// Net — `net connect tcp|udp <host:port>`: a raw socket, like netcat
+//
+//   > net connect tcp localhost:6379
+//   > net connect udp 10.0.0.7:5000
+//
+// What arrives is shown live in a pane: ← what came in, → what went out,
+// and · for the connection's own news. While the pane is open, whatever is
+// typed into the input bar is sent (`/close` still closes it).
+//
+// There are two encodings, switched with x (Tab to the pane first). Text
+// sends each typed line with a \n and shows what comes in as lines; hex
+// sends the bytes typed as hex digits ("de ad be ef", spaces optional) and
+// shows everything as a hex dump, for binary protocols. Switching redraws
+// what's already there.
+//
+// A background job owns the socket. Nothing reconnects: when the other end
+// hangs up, the pane says so and stays open to read. Closing the pane
+// closes the socket.
+//
+//   ↑/↓ PgUp/PgDn Home/End   scroll back (End follows new data again)
+// =============================================================================
+
+use std::{
+    cell::Cell,
+    io::{ErrorKind, Read, Write},
+    net::{TcpStream, ToSocketAddrs, UdpSocket},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver, Sender, TryRecvError},
+    },
+    time::Duration,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    ctx::{Ctx, Status},
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+};
+
+const POLL: Duration = Duration::from_millis(50); // how often the job checks for data to send
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_ENTRIES: usize = 5_000; // older ones are dropped
+const HEX_ROW: usize = 16; // bytes per hex dump line
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: net connect tcp|udp <host:port>";
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let ["connect", protocol, address] = words[..] else {
+        return ctx.fail(usage);
+    };
+    let protocol = match protocol {
+        "tcp" => Protocol::Tcp,
+        "udp" => Protocol::Udp,
+        _ => return ctx.fail(usage),
+    };
+    if !address.contains(':') {
+        return ctx.fail(format!("  {address} needs a port, e.g. {address}:80"));
+    }
+    let (view, outgoing) = NetView::new(protocol, address);
+    let log = view.log.clone();
+    let address = address.to_string();
+    ctx.show(view);
+    ctx.spawner.spawn_watcher(move |_| {
+        let why = match protocol {
+            Protocol::Tcp => run_tcp(&address, &log, &outgoing),
+            Protocol::Udp => run_udp(&address, &log, &outgoing),
+        };
+        if let Some(why) = why {
+            note(&log, |l| l.push(Direction::Note, why.into_bytes()));
+        }
+    });
+    Status::Success
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Tcp,
+    Udp,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    In,
+    Out,
+    Note, // connected, closed…
+}
+
+#[derive(Default)]
+struct Log {
+    entries: Vec<(Direction, Vec<u8>)>,
+    connected: bool,
+    changed: bool, // since the last render
+    closed: bool,  // the pane is gone
+}
+
+impl Log {
+    fn push(&mut self, direction: Direction, bytes: Vec<u8>) {
+        // TCP splits data wherever it likes; keep a line that arrived in
+        // pieces together
+        match self.entries.last_mut() {
+            Some((Direction::In, last)) if direction == Direction::In && !last.ends_with(b"\n") => {
+                last.extend(bytes);
+            }
+            _ => self.entries.push((direction, bytes)),
+        }
+        let excess = self.entries.len().saturating_sub(MAX_ENTRIES);
+        self.entries.drain(..excess);
+        self.changed = true;
+    }
+}
+
+type Shared = Arc<Mutex<Log>>;
+
+fn note(log: &Shared, f: impl FnOnce(&mut Log)) -> bool {
+    match log.lock() {
+        Ok(mut log) => {
+            f(&mut log);
+            !log.closed
+        }
+        Err(_) => false,
+    }
+}
+
+fn connected(log: &Shared, text: String) -> bool {
+    note(log, |l| {
+        l.connected = true;
+        l.push(Direction::Note, text.into_bytes());
+    })
+}
+
+fn timed_out(err: &std::io::Error) -> bool {
+    matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+}
+
+// Until the other end hangs up or it fails (Some(why)), or the pane closes
+fn run_tcp(address: &str, log: &Shared, outgoing: &Receiver<Vec<u8>>) -> Option<String> {
+    let addrs = address.to_socket_addrs().map_err(|err| err.to_string());
+    let addr = match addrs.map(|mut a| a.next()) {
+        Ok(Some(addr)) => addr,
+        Ok(None) => return Some(format!("{address} didn't resolve")),
+        Err(why) => return Some(why),
+    };
+    let mut stream = match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+        Ok(stream) => stream,
+        Err(err) => return Some(format!("can't connect: {err}")),
+    };
+    let _ = stream.set_read_timeout(Some(POLL));
+    if !connected(log, format!("connected to {addr}")) {
+        return None;
+    }
+    let mut buffer = [0; 8192];
+    loop {
+        loop {
+            match outgoing.try_recv() {
+                Ok(bytes) => {
+                    if let Err(err) = stream.write_all(&bytes) {
+                        return Some(err.to_string());
+                    }
+                    note(log, |l| l.push(Direction::Out, bytes));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return None,
+            }
+        }
+        match stream.read(&mut buffer) {
+            Ok(0) => {
+                note(log, |l| l.connected = false);
+                return Some("closed by the other end".to_string());
+            }
+            Ok(n) => {
+                if !note(log, |l| l.push(Direction::In, buffer[..n].to_vec())) {
+                    return None;
+                }
+            }
+            Err(err) if timed_out(&err) => {
+                if !note(log, |_| {}) {
+                    return None;
+                }
+            }
+            Err(err) => return Some(err.to_string()),
+        }
+    }
+}
+
+// No connection as such: each typed line is a datagram, and replies from
+// that address are shown
+fn run_udp(address: &str, log: &Shared, outgoing: &Receiver<Vec<u8>>) -> Option<String> {
+    let addrs = address.to_socket_addrs().map_err(|err| err.to_string());
+    let addr = match addrs.map(|mut a| a.next()) {
+        Ok(Some(addr)) => addr,
+        Ok(None) => return Some(format!("{address} didn't resolve")),
+        Err(why) => return Some(why),
+    };
+    let local = if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = match UdpSocket::bind(local).and_then(|s| s.connect(addr).map(|()| s)) {
+        Ok(socket) => socket,
+        Err(err) => return Some(format!("can't open a socket: {err}")),
+    };
+    let _ = socket.set_read_timeout(Some(POLL));
+    let from = socket
+        .local_addr()
+        .map_or(String::new(), |a| format!(" from {a}"));
+    if !connected(log, format!("sending to {addr}{from}")) {
+        return None;
+    }
+    let mut buffer = [0; 65536];
+    loop {
+        loop {
+            match outgoing.try_recv() {
+                Ok(bytes) => {
+                    if let Err(err) = socket.send(&bytes) {
+                        note(log, |l| {
+                            l.push(Direction::Note, err.to_string().into_bytes())
+                        });
+                        continue; // one datagram failing doesn't end the session
+                    }
+                    note(log, |l| l.push(Direction::Out, bytes));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return None,
+            }
+        }
+        match socket.recv(&mut buffer) {
+            Ok(n) => {
+                let mut datagram = buffer[..n].to_vec();
+                if !datagram.ends_with(b"\n") {
+                    datagram.push(b'\n'); // one per line, not run together
+                }
+                if !note(log, |l| l.push(Direction::In, datagram)) {
+                    return None;
+                }
+            }
+            Err(err) if timed_out(&err) => {
+                if !note(log, |_| {}) {
+                    return None;
+                }
+            }
+            // e.g. nothing listening there: the next datagram may do better
+            Err(err) => {
+                if !note(log, |l| {
+                    l.push(Direction::Note, err.to_string().into_bytes())
+                }) {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+// "de ad be ef", "deadbeef" or "0xde 0xad"
+pub fn parse_hex(typed: &str) -> Result<Vec<u8>, String> {
+    let digits: String = typed
+        .split_whitespace()
+        .map(|word| word.trim_start_matches("0x"))
+        .collect();
+    if let Some(bad) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("'{bad}' isn't hex"));
+    }
+    if digits.len() % 2 == 1 {
+        return Err("an odd number of hex digits".to_string());
+    }
+    // All ASCII now, so every pair of bytes is a pair of digits
+    (digits.as_bytes().chunks(2))
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).unwrap_or_default();
+            u8::from_str_radix(pair, 16).map_err(|_| format!("'{pair}' isn't hex"))
+        })
+        .collect()
+}
+
+// Control characters as escapes, so a stray \r or ESC can't upset the screen
+fn printable(line: &[u8]) -> String {
+    String::from_utf8_lossy(line)
+        .trim_end_matches(['\r', '\n'])
+        .chars()
+        .map(|c| match c {
+            '\t' => "    ".to_string(),
+            '\r' => "\\r".to_string(),
+            c if c.is_control() => format!("\\x{:02x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+// "48 65 6c 6c 6f 0a        |Hello.|"
+fn hex_line(chunk: &[u8]) -> String {
+    let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+    let ascii: String = chunk
+        .iter()
+        .map(|&b| match b {
+            0x20..=0x7e => b as char,
+            _ => '.',
+        })
+        .collect();
+    format!(
+        "{:<width$}  |{ascii}|",
+        hex.join(" "),
+        width = HEX_ROW * 3 - 1
+    )
+}
+
+pub struct NetView {
+    title: String,
+    log: Shared,
+    outgoing: Sender<Vec<u8>>,
+    hex: bool,             // the encoding, both ways
+    scroll: Option<usize>, // lines back from the newest; None = following
+    count: Cell<usize>,    // lines, as of the last render
+    height: Cell<usize>,   // rows that fit, as of the last render
+}
+
+impl NetView {
+    fn new(protocol: Protocol, address: &str) -> (Self, Receiver<Vec<u8>>) {
+        let (outgoing, receiver) = mpsc::channel();
+        let name = match protocol {
+            Protocol::Tcp => "TCP",
+            Protocol::Udp => "UDP",
+        };
+        let view = Self {
+            title: format!("{name}: {address}"),
+            log: Shared::default(),
+            outgoing,
+            hex: false,
+            scroll: None,
+            count: Cell::new(0),
+            height: Cell::new(1),
+        };
+        (view, receiver)
+    }
+
+    fn scroll_by(&mut self, step: isize) {
+        let last = self.count.get().saturating_sub(self.height.get());
+        let back = self
+            .scroll
+            .unwrap_or(0)
+            .saturating_add_signed(step)
+            .min(last);
+        self.scroll = (back > 0).then_some(back);
+    }
+
+    // Each entry as the lines the current encoding shows it as
+    fn lines<'a>(&self, log: &'a Log, theme: &Theme) -> Vec<Line<'a>> {
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let mut lines = Vec::new();
+        for (direction, bytes) in &log.entries {
+            let (mark, style) = match direction {
+                Direction::In => ("← ", Style::default().fg(theme.input_text)),
+                Direction::Out => ("→ ", Style::default().fg(Color::Cyan)),
+                Direction::Note => ("· ", dim),
+            };
+            let texts: Vec<String> = match direction {
+                Direction::Note => vec![printable(bytes)],
+                _ if self.hex => bytes.chunks(HEX_ROW).map(hex_line).collect(),
+                _ => bytes
+                    .split_inclusive(|&b| b == b'\n')
+                    .map(printable)
+                    .collect(),
+            };
+            for (i, text) in texts.into_iter().enumerate() {
+                let mark = if i == 0 { mark } else { "  " };
+                lines.push(Line::from(vec![
+                    Span::styled(mark, style),
+                    Span::styled(text, style),
+                ]));
+            }
+        }
+        lines
+    }
+}
+
+impl Drop for NetView {
+    fn drop(&mut self) {
+        if let Ok(mut log) = self.log.lock() {
+            log.closed = true;
+        }
+    }
+}
+
+impl Pane for NetView {
+    fn title(&self) -> String {
+        let connected = self.log.lock().is_ok_and(|l| l.connected);
+        let state = if connected { "open" } else { "not connected" };
+        let encoding = if self.hex { "hex" } else { "text" };
+        format!("{} ({state}, {encoding})", self.title)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Ok(mut log) = self.log.lock() else {
+            return;
+        };
+        log.changed = false;
+        let lines = self.lines(&log, theme);
+        let height = area.height as usize;
+        self.height.set(height);
+        self.count.set(lines.len());
+        let end = lines.len().saturating_sub(self.scroll.unwrap_or(0));
+        let start = end.saturating_sub(height);
+        let shown: Vec<Line> = lines.into_iter().take(end).skip(start).collect();
+        frame.render_widget(Paragraph::new(shown), area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get() as isize;
+        match key.code {
+            KeyCode::Up => self.scroll_by(1),
+            KeyCode::Down => self.scroll_by(-1),
+            KeyCode::PageUp => self.scroll_by(page),
+            KeyCode::PageDown => self.scroll_by(-page),
+            KeyCode::Home => self.scroll_by(isize::MAX),
+            KeyCode::End => self.scroll = None,
+            KeyCode::Char('x') => {
+                self.hex = !self.hex;
+                self.scroll = None;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.log.lock().is_ok_and(|l| l.changed)
+    }
+
+    fn hints(&self) -> Hints {
+        &[("↑↓", "scroll"), ("x", "text/hex"), ("Tab", "type to send")]
+    }
+
+    fn input_label(&self) -> Option<String> {
+        Some(if self.hex { "Send hex" } else { "Send" }.to_string())
+    }
+
+    fn submit(&mut self, line: &str) {
+        let bytes = if self.hex {
+            match parse_hex(line) {
+                Ok(bytes) => bytes,
+                Err(why) => {
+                    note(&self.log, |l| {
+                        l.push(Direction::Note, format!("not sent: {why}").into_bytes())
+                    });
+                    return;
+                }
+            }
+        } else {
+            format!("{line}\n").into_bytes()
+        };
+        let _ = self.outgoing.send(bytes);
+    }
+}
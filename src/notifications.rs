@@ -0,0 +1,137 @@
+// =============================================================================
+// Notifications — saying a job finished while you were in another window
+//
+//   [notifications]              # in config.toml
+//   method = "osc9"              # "osc9", "osc777", "desktop", "both" or "off"
+//   after_seconds = 10           # only for jobs that ran this long (0: any)
+//   commands = { ssh = "desktop", tail = "off" }   # by the job's first word
+//
+// When a background job (sleep, http, ssh, tail, hex, tree…) finishes
+// after running for after_seconds or more, Output gets a highlighted line
+// saying so ("Finished: sleep 60 (after 1m 00s)"), and if the terminal
+// isn't focused, it's announced:
+//
+//   osc9     an OSC 9 escape, which terminals such as iTerm2, WezTerm, kitty,
+//            foot and Windows Terminal turn into a system notification
+//   osc777   OSC 777's "notify", for those that have that one instead
+//            (urxvt, and GNOME Terminal and others built on VTE)
+//   desktop  a notification of the app's own: D-Bus on Linux and the BSDs,
+//            the native APIs on macOS and Windows (the `desktop` feature;
+//            built without it, this sends OSC 9 instead)
+//
+// Focus comes from the terminal's focus reporting, which most terminals
+// have. One without it counts as always focused, so it never notifies.
+// Inside tmux or screen, OSC 9 is wrapped for it to pass on (see
+// multiplexer.rs).
+// =============================================================================
+
+use std::{collections::BTreeMap, io, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::multiplexer;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Method {
+    #[default]
+    Osc9,
+    Osc777,
+    Desktop,
+    Both,
+    Off,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationConfig {
+    pub method: Method,
+    pub after_seconds: u64,
+    pub commands: BTreeMap<String, Method>, // overrides `method` for these
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            method: Method::Osc9,
+            after_seconds: 10,
+            commands: BTreeMap::new(),
+        }
+    }
+}
+
+impl NotificationConfig {
+    // How long a job has to run for its end to be worth a word
+    pub fn after(&self) -> Duration {
+        Duration::from_secs(self.after_seconds)
+    }
+
+    // A job's label starts with the command that started it ("ssh web1 df")
+    fn method(&self, label: &str) -> Method {
+        let command = label.split_whitespace().next().unwrap_or_default();
+        self.commands.get(command).copied().unwrap_or(self.method)
+    }
+}
+
+// "Finished: sleep 60 (after 1m 00s)", for Output and the notification
+pub fn summary(label: &str, took: Duration) -> String {
+    let secs = took.as_secs();
+    let after = if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, secs / 60 % 60)
+    };
+    format!("Finished: {label} (after {after})")
+}
+
+// The job called `label` is done; the caller knows the terminal isn't focused
+pub fn job_finished(config: &NotificationConfig, label: &str, took: Duration) {
+    let text = summary(label, took);
+    match config.method(label) {
+        Method::Osc9 => osc9(&text),
+        Method::Osc777 => osc777(&text),
+        Method::Desktop => desktop(&text),
+        Method::Both => {
+            osc9(&text);
+            #[cfg(feature = "desktop")]
+            desktop(&text);
+        }
+        Method::Off => {}
+    }
+}
+
+fn osc9(text: &str) {
+    // A control character would end the sequence early
+    let text: String = text.chars().filter(|c| !c.is_control()).collect();
+    let _ = multiplexer::to_terminal(&mut io::stdout(), &format!("\x1b]9;{text}\x07"));
+}
+
+// urxvt's: a title, then the body
+fn osc777(text: &str) {
+    let text: String = text.chars().filter(|c| !c.is_control()).collect();
+    let escape = format!("\x1b]777;notify;{};{text}\x07", env!("CARGO_PKG_NAME"));
+    let _ = multiplexer::to_terminal(&mut io::stdout(), &escape);
+}
+
+#[cfg(feature = "desktop")]
+fn desktop(text: &str) {
+    let text = text.to_string();
+    // The notification daemon can take a moment to answer; not on the UI thread
+    std::thread::spawn(move || {
+        let shown = notify_rust::Notification::new()
+            .appname(env!("CARGO_PKG_NAME"))
+            .summary(env!("CARGO_PKG_NAME"))
+            .body(&text)
+            .show();
+        if let Err(err) = shown {
+            tracing::warn!(%err, "desktop notification failed");
+        }
+    });
+}
+
+#[cfg(not(feature = "desktop"))]
+fn desktop(text: &str) {
+    osc9(text);
+}
@@ -0,0 +1,453 @@
+// =============================================================================
+// Output — the scrollback, and the lines it's drawn as
+//
+//   app.messages.push(line)            append (AppEvent::Print does this)
+//   app.messages.push_highlighted(line)   …drawn in reverse video, to stand out
+//   app.messages.clear()
+//   app.messages.get(i)                message i (0 is the oldest), as printed
+//   app.shown().rendered(top..end)     those rows of Output, ready to draw
+//
+// Messages are kept as they were printed. What gets drawn is each one made
+// safe for the screen first — a colored `ls` or a tab would otherwise throw
+// the grid off (see plain()). That's done once, as a message arrives, and
+// most need nothing: only the ones plain() changes keep a cleaned copy. A
+// frame then draws Lines that borrow those strings for the few rows it
+// shows, with nothing copied.
+//
+// With `wrap = true` in [layout], a message wider than Output carries on
+// onto the rows below, broken at the edge. Scrolling then counts rows
+// rather than messages, and to find them it keeps where each message starts
+// at the width Output last had; a new message adds one entry, and only a
+// different width (a resize, the minimap coming or going) counts them all
+// again.
+//
+// Only the newest HOT messages are kept as they are. Past that, the oldest
+// are compressed BLOCK at a time, so a day-long session holds a fraction of
+// what it printed. Reading one of those — scrolling far back, saving the
+// session — decompresses its block, and the last block read is kept for
+// the next. A Filter remembers what it has matched, so it reads each
+// message once rather than every frame.
+//
+// Each message is an Arc<str>, so whatever wants to keep some of them — a
+// session being saved, an export — clones the pointers, not the text.
+// =============================================================================
+
+use std::{
+    borrow::Cow,
+    cell::{Ref, RefCell},
+    collections::{BTreeSet, HashMap},
+    fmt,
+    io::{Read, Write},
+    iter,
+    ops::Range,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Line,
+};
+use regex::Regex;
+use unicode_width::UnicodeWidthChar;
+
+use crate::minimap::Summary;
+
+const HOT: usize = 10_000; // the newest messages, never compressed
+const BLOCK: usize = 1_000; // older ones are compressed this many together
+const ARC_COUNTS: usize = 2 * size_of::<usize>(); // beside each message's text
+
+const HIGHLIGHT: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+static IDS: AtomicU64 = AtomicU64::new(0);
+
+pub struct Scrollback {
+    id: u64,                         // a new one on clear(), so a Filter starts over
+    cold: Vec<Vec<u8>>,              // the oldest messages, BLOCK to a compressed block
+    hot: Vec<Arc<str>>,              // the rest, as printed
+    cleaned: HashMap<usize, String>, // plain() of the hot ones it changes, by index
+    summaries: Vec<Summary>,         // every message's, for the minimap
+    highlighted: BTreeSet<usize>,    // indexes of those push_highlighted() added
+    thawed: RefCell<Option<(usize, Vec<Arc<str>>)>>, // the cold block last read
+    wrap: RefCell<Wrap>,             // caught up when asked, at the width asked for
+}
+
+#[derive(Default)]
+struct Wrap {
+    width: u16,
+    starts: Vec<usize>, // the first row of each message so far, then one past the last
+}
+
+impl Default for Scrollback {
+    fn default() -> Self {
+        Self {
+            id: IDS.fetch_add(1, Ordering::Relaxed),
+            cold: Vec::new(),
+            hot: Vec::new(),
+            cleaned: HashMap::new(),
+            summaries: Vec::new(),
+            highlighted: BTreeSet::new(),
+            thawed: RefCell::default(),
+            wrap: RefCell::default(),
+        }
+    }
+}
+
+impl Scrollback {
+    pub fn push(&mut self, line: impl Into<Arc<str>>) {
+        let line = line.into();
+        self.summaries.push(Summary::of(&line));
+        if let Cow::Owned(text) = plain(&line) {
+            self.cleaned.insert(self.len(), text);
+        }
+        self.hot.push(line);
+        if self.hot.len() >= HOT + BLOCK {
+            self.freeze();
+        }
+    }
+
+    pub fn push_highlighted(&mut self, line: impl Into<Arc<str>>) {
+        self.highlighted.insert(self.len());
+        self.push(line);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn len(&self) -> usize {
+        self.frozen() + self.hot.len()
+    }
+
+    // Message `i`, as printed
+    pub fn get(&self, i: usize) -> Arc<str> {
+        let frozen = self.frozen();
+        if i >= frozen {
+            return self.hot[i - frozen].clone();
+        }
+        let (block, at) = (i / BLOCK, i % BLOCK);
+        let mut thawed = self.thawed.borrow_mut();
+        if let Some((last, lines)) = &*thawed
+            && *last == block
+        {
+            return lines[at].clone();
+        }
+        let lines = thaw(&self.cold[block]);
+        let line = lines[at].clone();
+        *thawed = Some((block, lines));
+        line
+    }
+
+    // Every message, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = Arc<str>> + '_ {
+        (0..self.len()).map(|i| self.get(i))
+    }
+
+    pub fn summaries(&self) -> &[Summary] {
+        &self.summaries
+    }
+
+    // Roughly what it takes in memory, for the debug overlay
+    pub fn bytes(&self) -> usize {
+        let hot: usize = self.hot.iter().map(|line| line.len() + ARC_COUNTS).sum();
+        let cold: usize = self.cold.iter().map(Vec::capacity).sum();
+        let cleaned: usize = self.cleaned.values().map(String::capacity).sum();
+        let wrap = self.wrap.borrow().starts.capacity() * size_of::<usize>();
+        let slots = self.hot.capacity() * size_of::<Arc<str>>()
+            + self.summaries.capacity() * size_of::<Summary>();
+        hot + cold + cleaned + wrap + slots
+    }
+
+    // Messages in compressed blocks
+    fn frozen(&self) -> usize {
+        self.cold.len() * BLOCK
+    }
+
+    // Message `i` as drawn
+    fn drawn(&self, i: usize) -> Cow<'_, str> {
+        match i.checked_sub(self.frozen()) {
+            Some(hot) => Cow::Borrowed(self.cleaned.get(&i).map_or(&self.hot[hot], String::as_str)),
+            None => Cow::Owned(plain(&self.get(i)).into_owned()),
+        }
+    }
+
+    // Compress the oldest BLOCK of the hot messages
+    fn freeze(&mut self) {
+        let frozen = self.frozen();
+        let lines: Vec<_> = self.hot.drain(..BLOCK).collect();
+        self.cleaned.retain(|&i, _| i >= frozen + BLOCK);
+        self.cold.push(freeze(&lines));
+    }
+
+    // Where each message starts, wrapped at `width`
+    fn starts(&self, width: u16) -> Ref<'_, [usize]> {
+        {
+            let mut wrap = self.wrap.borrow_mut();
+            if wrap.width != width || wrap.starts.is_empty() {
+                *wrap = Wrap {
+                    width,
+                    starts: vec![0],
+                };
+            }
+            for i in wrap.starts.len() - 1..self.len() {
+                let next = wrap.starts[i] + wrapped(&self.drawn(i), width).count();
+                wrap.starts.push(next);
+            }
+        }
+        Ref::map(self.wrap.borrow(), |wrap| &wrap.starts[..])
+    }
+}
+
+impl From<Vec<Arc<str>>> for Scrollback {
+    fn from(lines: Vec<Arc<str>>) -> Self {
+        let mut scrollback = Self::default();
+        lines.into_iter().for_each(|line| scrollback.push(line));
+        scrollback
+    }
+}
+
+// `lines` as one compressed block: each one's length, then its text
+fn freeze(lines: &[Arc<str>]) -> Vec<u8> {
+    let mut block = DeflateEncoder::new(Vec::new(), Compression::fast());
+    for line in lines {
+        // Into a Vec, which can't fail
+        let _ = block.write_all(&(line.len() as u32).to_le_bytes());
+        let _ = block.write_all(line.as_bytes());
+    }
+    let mut block = block.finish().unwrap_or_default();
+    block.shrink_to_fit();
+    block
+}
+
+// A block's lines again; always BLOCK of them
+fn thaw(block: &[u8]) -> Vec<Arc<str>> {
+    let mut bytes = Vec::new();
+    let _ = DeflateDecoder::new(block).read_to_end(&mut bytes);
+    let mut lines = Vec::with_capacity(BLOCK);
+    let mut rest = &bytes[..];
+    while let Some((len, after)) = rest.split_first_chunk::<4>() {
+        let (text, after) = after.split_at((u32::from_le_bytes(*len) as usize).min(after.len()));
+        lines.push(String::from_utf8_lossy(text).into());
+        rest = after;
+    }
+    lines.resize(BLOCK, Arc::from(""));
+    lines
+}
+
+// The `filter` command's pattern, and what it has matched so far
+pub struct Filter {
+    regex: Regex,
+    matches: RefCell<Matches>,
+}
+
+#[derive(Default)]
+struct Matches {
+    id: u64,        // of the Scrollback these are from
+    checked: usize, // its messages seen so far
+    found: Vec<usize>,
+}
+
+impl Filter {
+    pub fn new(regex: Regex) -> Self {
+        Self {
+            regex,
+            matches: RefCell::default(),
+        }
+    }
+
+    // The messages that match, checking just the ones new since last time
+    fn matching(&self, messages: &Scrollback) -> Vec<usize> {
+        let mut matches = self.matches.borrow_mut();
+        if matches.id != messages.id {
+            *matches = Matches {
+                id: messages.id,
+                ..Matches::default()
+            };
+        }
+        for i in matches.checked..messages.len() {
+            if self.regex.is_match(&messages.get(i)) {
+                matches.found.push(i);
+            }
+        }
+        matches.checked = messages.len();
+        matches.found.clone()
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.regex.fmt(f)
+    }
+}
+
+// What Output shows — every message, or those a filter lets through — and
+// whether they wrap, at how many columns
+pub struct Shown<'a> {
+    messages: &'a Scrollback,
+    only: Option<Vec<usize>>, // indices into messages
+    wrap: Option<u16>,
+}
+
+impl<'a> Shown<'a> {
+    pub fn new(messages: &'a Scrollback, filter: Option<&Filter>, wrap: Option<u16>) -> Self {
+        Self {
+            messages,
+            only: filter.map(|filter| filter.matching(messages)),
+            wrap,
+        }
+    }
+
+    // Messages shown
+    pub fn len(&self) -> usize {
+        self.only.as_ref().map_or(self.messages.len(), Vec::len)
+    }
+
+    // Theirs, for the minimap
+    pub fn summaries(&self) -> Cow<'a, [Summary]> {
+        let summaries = self.messages.summaries();
+        match &self.only {
+            None => Cow::Borrowed(summaries),
+            Some(only) => only.iter().map(|&i| summaries[i]).collect(),
+        }
+    }
+
+    // Rows they take: one each, unless wrapping
+    pub fn rows(&self) -> usize {
+        self.row_of(self.len())
+    }
+
+    // The first row of the `n`th message shown
+    pub fn row_of(&self, n: usize) -> usize {
+        let Some(width) = self.wrap else {
+            return n;
+        };
+        let starts = self.messages.starts(width);
+        match &self.only {
+            None => starts[n.min(self.len())],
+            Some(only) => only[..n.min(only.len())]
+                .iter()
+                .map(|&i| starts[i + 1] - starts[i])
+                .sum(),
+        }
+    }
+
+    // The message shown on `row` (len() if past the end), and how many of
+    // its rows come before it
+    pub fn at_row(&self, row: usize) -> (usize, usize) {
+        let Some(width) = self.wrap else {
+            return (row.min(self.len()), 0);
+        };
+        let starts = self.messages.starts(width);
+        match &self.only {
+            None => {
+                let n = starts.partition_point(|&start| start <= row) - 1;
+                (n, row - starts[n])
+            }
+            Some(only) => {
+                let mut row = row;
+                for (n, &i) in only.iter().enumerate() {
+                    let rows = starts[i + 1] - starts[i];
+                    if row < rows {
+                        return (n, row);
+                    }
+                    row -= rows;
+                }
+                (only.len(), 0)
+            }
+        }
+    }
+
+    // Rows `range`, as drawn (as many of them as there are)
+    pub fn rendered(&self, range: Range<usize>) -> Vec<Line<'a>> {
+        let (mut n, mut skip) = self.at_row(range.start);
+        let mut lines = Vec::with_capacity(range.len());
+        while lines.len() < range.len() && n < self.len() {
+            let i = self.index(n);
+            let text = self.messages.drawn(i);
+            let (from, room) = (lines.len(), range.len() - lines.len());
+            match (self.wrap, text) {
+                (None, text) => lines.push(Line::raw(text)),
+                (Some(width), Cow::Borrowed(text)) => {
+                    lines.extend(wrapped(text, width).skip(skip).take(room).map(Line::raw));
+                }
+                (Some(width), Cow::Owned(text)) => {
+                    let rows = wrapped(&text, width).skip(skip).take(room);
+                    lines.extend(rows.map(|row| Line::raw(row.to_owned())));
+                }
+            }
+            if self.messages.highlighted.contains(&i) {
+                lines[from..]
+                    .iter_mut()
+                    .for_each(|line| line.style = HIGHLIGHT);
+            }
+            n += 1;
+            skip = 0;
+        }
+        lines
+    }
+
+    fn index(&self, n: usize) -> usize {
+        self.only.as_ref().map_or(n, |only| only[n])
+    }
+}
+
+// `text` as the rows it wraps onto, each at most `width` columns (a
+// character wider than that gets a row of its own); "" is one empty row
+fn wrapped(text: &str, width: u16) -> impl Iterator<Item = &str> {
+    let width = usize::from(width.max(1));
+    let mut rest = Some(text);
+    iter::from_fn(move || {
+        let text = rest?;
+        let mut columns = 0;
+        for (at, c) in text.char_indices() {
+            let w = c.width().unwrap_or(0);
+            if columns + w > width && at > 0 {
+                rest = Some(&text[at..]);
+                return Some(&text[..at]);
+            }
+            columns += w;
+        }
+        rest = None;
+        Some(text)
+    })
+}
+
+// `line` without escape sequences; tabs become spaces, other controls go.
+// Borrowed if there's nothing to take out.
+pub fn plain(line: &str) -> Cow<'_, str> {
+    if !line.chars().any(char::is_control) {
+        return Cow::Borrowed(line);
+    }
+    let mut text = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI (colors, cursor moves): up to a final byte @ … ~
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC (titles, links): up to BEL or ESC \
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1b' && chars.next() == Some('\\')) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\t' => text.push_str("    "),
+            c if c.is_control() => {}
+            c => text.push(c),
+        }
+    }
+    Cow::Owned(text)
+}
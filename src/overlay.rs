@@ -0,0 +1,162 @@
+// =============================================================================
+// Overlays — popups drawn over the panels that take the keyboard until closed
+//
+// A command opens one through its Ctx and says what to do with the answer:
+//
+//   ctx.open(FilePicker::new(ctx.effects.files.clone(), dir), |ctx, path| {
+//       ctx.print(format!("  You picked {}", path.display()));
+//   });
+//
+// Overlays stack: the newest is drawn on top and gets every key press. When
+// it finishes with a choice it's popped and the callback runs with a fresh
+// Ctx, so it can print, send events or open the next overlay. Cancelling
+// (usually Esc) pops it without calling back.
+//
+// Commands can't block waiting for an answer — the main loop has to keep
+// running to draw the popup — hence the callback.
+//
+// To add an overlay, implement Overlay: render() draws it (popup() does the
+// frame and placement), handle_key() says whether it's still open, and
+// Answer is the type the callback receives. hints() lists its keys for the
+// hint line (hints.rs) if they're more than Enter and Esc.
+// =============================================================================
+
+use std::{fmt, time::Instant};
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Clear},
+};
+
+use crate::{clock, ctx::Ctx, hints::Hints, theme::Theme};
+
+// What a key press did to the overlay
+pub enum Outcome<T> {
+    Open,    // still going
+    Cancel,  // close without an answer
+    Done(T), // close and hand this to the callback
+}
+
+pub trait Overlay: Send {
+    type Answer: Send + 'static; // what the callback receives
+
+    fn render(&self, frame: &mut Frame, theme: &Theme);
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<Self::Answer>;
+
+    fn hints(&self) -> Hints {
+        &[("Enter", "choose"), ("Esc", "cancel")]
+    }
+}
+
+// The callback with the answer already bound, ready to run against a Ctx
+pub type Finish = Box<dyn FnOnce(&mut Ctx) + Send>;
+
+// An overlay on the stack, together with whoever is waiting for its answer.
+// Boxed behind Pending so overlays with different Answer types can share
+// the stack.
+pub struct Modal {
+    pending: Box<dyn Pending>,
+    opened: Instant, // for the opening animation (animation.rs)
+}
+
+impl Modal {
+    pub fn new<O, F>(overlay: O, on_done: F) -> Self
+    where
+        O: Overlay + 'static,
+        F: FnOnce(&mut Ctx, O::Answer) + Send + 'static,
+    {
+        Self {
+            pending: Box::new(Waiting {
+                overlay,
+                on_done: Some(on_done),
+            }),
+            opened: clock::now(),
+        }
+    }
+
+    pub fn opened(&self) -> Instant {
+        self.opened
+    }
+
+    pub fn render(&self, frame: &mut Frame, theme: &Theme) {
+        self.pending.render(frame, theme);
+    }
+
+    // Done carries the callback to run once the modal is off the stack
+    pub fn handle_key(&mut self, key: KeyEvent) -> Outcome<Finish> {
+        self.pending.handle_key(key)
+    }
+
+    pub fn hints(&self) -> Hints {
+        self.pending.hints()
+    }
+}
+
+// AppEvent is Debug; a boxed closure has nothing useful to show
+impl fmt::Debug for Modal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Modal")
+    }
+}
+
+trait Pending: Send {
+    fn render(&self, frame: &mut Frame, theme: &Theme);
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<Finish>;
+    fn hints(&self) -> Hints;
+}
+
+struct Waiting<O, F> {
+    overlay: O,
+    on_done: Option<F>, // taken when the answer arrives
+}
+
+impl<O, F> Pending for Waiting<O, F>
+where
+    O: Overlay,
+    F: FnOnce(&mut Ctx, O::Answer) + Send + 'static,
+{
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        self.overlay.render(frame, theme);
+    }
+
+    fn hints(&self) -> Hints {
+        self.overlay.hints()
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<Finish> {
+        match self.overlay.handle_key(key) {
+            Outcome::Open => Outcome::Open,
+            Outcome::Cancel => Outcome::Cancel,
+            Outcome::Done(answer) => match self.on_done.take() {
+                Some(on_done) => Outcome::Done(Box::new(move |ctx| on_done(ctx, answer))),
+                None => Outcome::Cancel,
+            },
+        }
+    }
+}
+
+// Clear a centered width×height box (clamped to the screen), draw a bordered
+// frame titled `title` and return the area inside it
+pub fn popup(frame: &mut Frame, width: u16, height: u16, title: &str, theme: &Theme) -> Rect {
+    let screen = frame.area();
+    let width = width.min(screen.width);
+    let height = height.min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {title} "))
+        .border_style(Style::default().fg(theme.input_border));
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    inner
+}
@@ -0,0 +1,310 @@
+// =============================================================================
+// Panes — views a command can put on screen next to (or instead of) Output
+//
+//   ctx.show(TableView::new("Bindings", headers).rows(rows));
+//
+// There is one pane slot. If the layout has a side panel the pane is drawn
+// there; otherwise it takes over the Output panel until it's closed (the
+// `close` command). Showing a pane gives it the keyboard: Tab
+// (focus-next in the keymap) moves focus between the pane and the input
+// bar, and Esc returns to the input bar.
+//
+// A focused pane sees keys before the keymap; whatever it doesn't use falls
+// through, except plain characters, which are never typed into the input
+// bar while the pane has focus.
+//
+// Split puts two panes in the slot side by side, e.g. an editor and its
+// preview; the left one gets keys first.
+//
+// To add a pane, implement Pane: render() draws inside the border App
+// provides, and handle_key() returns true for keys it used. A pane whose
+// content changes on its own (a live chart) also implements tick(), called
+// every tick, returning true when it needs redrawing. hints() lists its
+// keys for the hint line (hints.rs).
+//
+// A pane can also take over the input bar (a chat, a socket): input_label()
+// names what Enter does instead of running a command, and submit() gets
+// each line. A line starting with "/" still runs as a command, so `/close`
+// gets out.
+//
+// A pane whose content takes a while (a big file, a network call) is shown
+// straight away as a Loading placeholder and built by a job; see below.
+// =============================================================================
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::{hints::Hints, theme::Theme};
+
+pub trait Pane: Send {
+    fn title(&self) -> String;
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme);
+    fn handle_key(&mut self, key: KeyEvent) -> bool;
+
+    fn tick(&mut self) -> bool {
+        false
+    }
+
+    fn hints(&self) -> Hints {
+        &[]
+    }
+
+    fn input_label(&self) -> Option<String> {
+        None
+    }
+
+    fn submit(&mut self, _line: &str) {}
+}
+
+// AppEvent is Debug
+impl fmt::Debug for dyn Pane {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Pane({})", self.title())
+    }
+}
+
+// Draw `pane` in a bordered block, highlighted while it has focus
+pub fn render(frame: &mut Frame, pane: &dyn Pane, area: Rect, theme: &Theme, focused: bool) {
+    let color = if focused {
+        theme.input_border
+    } else {
+        theme.output_border
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", pane.title()))
+        .border_style(Style::default().fg(color));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    pane.render(frame, inner, theme);
+}
+
+pub struct Split {
+    left: Box<dyn Pane>,
+    right: Box<dyn Pane>,
+}
+
+impl Split {
+    pub fn new(left: impl Pane + 'static, right: impl Pane + 'static) -> Self {
+        Self {
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+}
+
+impl Pane for Split {
+    fn title(&self) -> String {
+        self.left.title()
+    }
+
+    // The right pane gets its own title, on a divider down the middle
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let [left, right] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        let divider = Block::default()
+            .borders(Borders::LEFT | Borders::TOP)
+            .title(format!(" {} ", self.right.title()))
+            .border_style(Style::default().fg(theme.output_border));
+        self.right.render(frame, divider.inner(right), theme);
+        frame.render_widget(divider, right);
+        // Last, so a cursor it sets (an editor's) wins
+        self.left.render(frame, left, theme);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.left.handle_key(key) || self.right.handle_key(key)
+    }
+
+    fn tick(&mut self) -> bool {
+        let left = self.left.tick();
+        self.right.tick() || left
+    }
+
+    fn hints(&self) -> Hints {
+        self.left.hints()
+    }
+
+    fn input_label(&self) -> Option<String> {
+        self.left.input_label()
+    }
+
+    fn submit(&mut self, line: &str) {
+        self.left.submit(line);
+    }
+}
+
+// =============================================================================
+// Loading — a placeholder pane until a job has built the real one
+//
+//   let (loading, slot) = Loading::new("Table: big.csv");
+//   ctx.show(loading);
+//   ctx.spawner.spawn(move |_| match parse(path) {
+//       Ok(rows) => slot.ready(TableView::new("Table: big.csv", headers).rows(rows)),
+//       Err(err) => slot.failed(format!("Can't read big.csv: {err}")),
+//   });
+//
+// Until then it draws a spinner over grey skeleton lines; on the next tick
+// after ready() it becomes that pane (title, keys, hints and all). failed()
+// leaves the reason in its place. If the placeholder is closed first,
+// whatever the job sends is dropped.
+// =============================================================================
+
+const SPINNER: [&str; 8] = ["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
+
+enum Stage {
+    Waiting,
+    Ready(Box<dyn Pane>),
+    Failed(String),
+}
+
+#[derive(Default)]
+struct Delivery {
+    stage: Option<Stage>, // what the job sent, not yet picked up
+    closed: bool,         // the placeholder is gone
+}
+
+// The job's half: hand over the finished pane, or say why there isn't one
+pub struct LoadingSlot {
+    delivery: Arc<Mutex<Delivery>>,
+}
+
+impl LoadingSlot {
+    pub fn ready(self, pane: impl Pane + 'static) {
+        self.deliver(Stage::Ready(Box::new(pane)));
+    }
+
+    pub fn failed(self, why: impl Into<String>) {
+        self.deliver(Stage::Failed(why.into()));
+    }
+
+    fn deliver(self, stage: Stage) {
+        if let Ok(mut delivery) = self.delivery.lock()
+            && !delivery.closed
+        {
+            delivery.stage = Some(stage);
+        }
+    }
+}
+
+pub struct Loading {
+    title: String,
+    spins: usize, // ticks so far, for the spinner
+    stage: Stage,
+    delivery: Arc<Mutex<Delivery>>,
+}
+
+impl Loading {
+    pub fn new(title: impl Into<String>) -> (Self, LoadingSlot) {
+        let delivery = Arc::<Mutex<Delivery>>::default();
+        let loading = Self {
+            title: title.into(),
+            spins: 0,
+            stage: Stage::Waiting,
+            delivery: delivery.clone(),
+        };
+        (loading, LoadingSlot { delivery })
+    }
+}
+
+impl Drop for Loading {
+    fn drop(&mut self) {
+        if let Ok(mut delivery) = self.delivery.lock() {
+            delivery.closed = true;
+        }
+    }
+}
+
+impl Pane for Loading {
+    fn title(&self) -> String {
+        match &self.stage {
+            Stage::Ready(pane) => pane.title(),
+            _ => self.title.clone(),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        match &self.stage {
+            Stage::Ready(pane) => pane.render(frame, area, theme),
+            Stage::Failed(why) => {
+                let text = Paragraph::new(format!("  {why}")).style(dim);
+                frame.render_widget(text, area);
+            }
+            Stage::Waiting => {
+                let spinner = SPINNER[self.spins % SPINNER.len()];
+                let mut lines = vec![
+                    Line::styled(format!("  {spinner} Loading…"), dim),
+                    Line::default(),
+                ];
+                // Rows of varying length, like the text that's coming
+                let width = area.width.saturating_sub(4) as usize;
+                let skeleton = (0..area.height.saturating_sub(2)).map(|row| {
+                    let len = width * [9, 6, 8, 4, 7][row as usize % 5] / 10;
+                    Line::styled(
+                        format!("  {}", "░".repeat(len)),
+                        dim.fg(theme.output_border),
+                    )
+                });
+                lines.extend(skeleton);
+                frame.render_widget(Paragraph::new(lines), area);
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match &mut self.stage {
+            Stage::Ready(pane) => pane.handle_key(key),
+            _ => false,
+        }
+    }
+
+    fn tick(&mut self) -> bool {
+        if let Stage::Ready(pane) = &mut self.stage {
+            return pane.tick();
+        }
+        let delivered = self.delivery.lock().ok().and_then(|mut d| d.stage.take());
+        match delivered {
+            Some(stage) => {
+                self.stage = stage;
+                true
+            }
+            None if matches!(self.stage, Stage::Waiting) => {
+                self.spins += 1; // the spinner turns
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn hints(&self) -> Hints {
+        match &self.stage {
+            Stage::Ready(pane) => pane.hints(),
+            _ => &[],
+        }
+    }
+
+    fn input_label(&self) -> Option<String> {
+        match &self.stage {
+            Stage::Ready(pane) => pane.input_label(),
+            _ => None,
+        }
+    }
+
+    fn submit(&mut self, line: &str) {
+        if let Stage::Ready(pane) = &mut self.stage {
+            pane.submit(line);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// =============================================================================
+// Where files live — XDG base directories, one subdirectory per app
+//
+//   config — $XDG_CONFIG_HOME/<app>   (~/.config/<app>)       config.toml, themes
+//   data   — $XDG_DATA_HOME/<app>     (~/.local/share/<app>)  sessions, history
+//   state  — $XDG_STATE_HOME/<app>    (~/.local/state/<app>)  logs
+//
+// <app> is the package name, so a renamed clone gets its own directories.
+//
+// With a profile selected (`--profile work`, `profile switch work`), each of
+// these becomes <dir>/<app>/profiles/<name> instead, so profiles never share
+// config, history or sessions.
+// =============================================================================
+
+use std::{env, path::PathBuf, sync::RwLock};
+
+static PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+pub fn config_dir() -> Option<PathBuf> {
+    app_dir("XDG_CONFIG_HOME", ".config")
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    app_dir("XDG_DATA_HOME", ".local/share")
+}
+
+pub fn state_dir() -> Option<PathBuf> {
+    app_dir("XDG_STATE_HOME", ".local/state")
+}
+
+// None is the default profile
+pub fn set_profile(name: Option<&str>) {
+    *PROFILE.write().unwrap_or_else(|e| e.into_inner()) = name.map(str::to_string);
+}
+
+pub fn profile() -> Option<String> {
+    PROFILE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// Profiles that have a config or data directory, sorted
+pub fn profiles() -> Vec<String> {
+    let mut names: Vec<String> = [
+        ("XDG_CONFIG_HOME", ".config"),
+        ("XDG_DATA_HOME", ".local/share"),
+    ]
+    .into_iter()
+    .filter_map(|(var, fallback)| base_dir(var, fallback))
+    .filter_map(|dir| std::fs::read_dir(dir.join("profiles")).ok())
+    .flatten()
+    .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+    .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+// Profile and session names become directory and file names — keep them boring
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn app_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    let dir = base_dir(var, fallback)?;
+    Some(match profile() {
+        Some(name) => dir.join("profiles").join(name),
+        None => dir,
+    })
+}
+
+// $<var>/<app>, falling back to ~/<fallback>/<app> when the variable is unset
+// or empty (as the XDG spec asks)
+fn base_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    let base = env::var_os(var)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(fallback)))?;
+    Some(base.join(env!("CARGO_PKG_NAME")))
+}
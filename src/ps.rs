@@ -0,0 +1,172 @@
+// =============================================================================
+// Processes — `ps`: running processes in a table, kept current (the
+// `sysinfo` feature)
+//
+//   cargo run --features sysinfo
+//   > ps
+//
+// The table pane (widgets/table.rs), re-read every REFRESH keeping the sort,
+// the filter and the selected process. Busiest first, to start with.
+//
+//   c / m        sort by CPU / memory, busiest first
+//   1–6          sort by any column; again to reverse
+//   /            filter as you type: firefox, name=cargo, cpu>5
+//   k            kill the selected process (SIGTERM), after asking
+//
+// CPU is measured between two readings, so it's 0 until the first refresh.
+// =============================================================================
+
+use std::{
+    sync::mpsc::Sender,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{Frame, layout::Rect};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind};
+
+use crate::{
+    ctx::{AppEvent, Ctx, Status},
+    hints::Hints,
+    overlay::Modal,
+    pane::Pane,
+    theme::Theme,
+    widgets::{Choice, Confirm, TableView},
+};
+
+const REFRESH: Duration = Duration::from_secs(2);
+const HEADERS: [&str; 6] = ["PID", "Name", "CPU", "Mem MiB", "Status", "Command"];
+const CPU: usize = 2; // columns c and m sort by
+const MEMORY: usize = 3;
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    if !args.is_empty() {
+        return ctx.fail("  Usage: ps");
+    }
+    let mut view = PsView {
+        system: System::new(),
+        table: TableView::new("Processes", HEADERS),
+        refreshed: Instant::now(),
+        tx: ctx.sender(),
+    };
+    view.refresh();
+    view.table.sort_by(CPU, true);
+    ctx.show(view);
+    Status::Success
+}
+
+pub struct PsView {
+    system: System,
+    table: TableView,
+    refreshed: Instant,
+    tx: Sender<AppEvent>,
+}
+
+impl PsView {
+    fn refresh(&mut self) {
+        let what = ProcessRefreshKind::nothing()
+            .with_cpu()
+            .with_memory()
+            .with_cmd(UpdateKind::OnlyIfNotSet);
+        self.system
+            .refresh_processes_specifics(ProcessesToUpdate::All, true, what);
+        self.refreshed = Instant::now();
+        let rows = self.system.processes().iter().map(|(pid, process)| {
+            let command: Vec<_> = process.cmd().iter().map(|a| a.to_string_lossy()).collect();
+            vec![
+                pid.to_string(),
+                process.name().to_string_lossy().into_owned(),
+                format!("{:.1}", process.cpu_usage()),
+                format!("{:.1}", process.memory() as f64 / (1024.0 * 1024.0)),
+                process.status().to_string(),
+                command.join(" "),
+            ]
+        });
+        self.table.set_rows(rows);
+    }
+
+    // Ask first; the answer comes back through the overlay's callback
+    fn kill_selected(&self) {
+        let Some(row) = self.table.selected_row() else {
+            return;
+        };
+        let (Ok(pid), name) = (row[0].parse::<usize>(), row[1].clone()) else {
+            return;
+        };
+        if pid == std::process::id() as usize {
+            let line = "  That's this app — quit it instead".to_string();
+            let _ = self.tx.send(AppEvent::Print(line));
+            return;
+        }
+        let confirm = Confirm::new(format!("Send SIGTERM to {name} (pid {pid})?"))
+            .labels("Kill", "Keep")
+            .default(Choice::No);
+        let modal = Modal::new(confirm, move |ctx, kill| {
+            if kill {
+                kill_process(ctx, pid, &name);
+            }
+        });
+        let _ = self.tx.send(AppEvent::Open(modal));
+    }
+}
+
+fn kill_process(ctx: &mut Ctx, pid: usize, name: &str) {
+    let pid = Pid::from(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    let sent = match system.process(pid) {
+        // Still the same process? Its pid may since have been reused
+        Some(process) if process.name().to_string_lossy() == name => process
+            .kill_with(Signal::Term)
+            .unwrap_or_else(|| process.kill()),
+        _ => {
+            ctx.print(format!("  {name} (pid {pid}) has already exited"));
+            return;
+        }
+    };
+    if sent {
+        ctx.print(format!("  Sent SIGTERM to {name} (pid {pid})"));
+    } else {
+        ctx.print(format!("  Couldn't signal {name} (pid {pid}) — not yours?"));
+    }
+}
+
+impl Pane for PsView {
+    fn title(&self) -> String {
+        self.table.title()
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if !self.table.typing() {
+            match key.code {
+                KeyCode::Char('c') => self.table.sort_by(CPU, true),
+                KeyCode::Char('m') => self.table.sort_by(MEMORY, true),
+                KeyCode::Char('k') => self.kill_selected(),
+                _ => return self.table.handle_key(key),
+            }
+            return true;
+        }
+        self.table.handle_key(key)
+    }
+
+    fn tick(&mut self) -> bool {
+        if self.refreshed.elapsed() < REFRESH {
+            return false;
+        }
+        self.refresh();
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("↑↓", "select"),
+            ("c/m", "sort by cpu/memory"),
+            ("/", "filter"),
+            ("k", "kill"),
+        ]
+    }
+}
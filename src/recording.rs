@@ -0,0 +1,108 @@
+// =============================================================================
+// Recording — `--record` every input event to a file, `--replay` it later
+//
+//   tui-template --record bug.jsonl       do whatever goes wrong, then quit
+//   tui-template --replay bug.jsonl       watch it happen again
+//
+// One JSON object per line: the event as crossterm describes it, and when
+// it arrived, in milliseconds since the loop started:
+//
+//   {"ms":812,"event":{"Key":{"code":{"Char":"h"},"modifiers":"", …}}}
+//
+// A replay hands the events to the loop at the same moments, so debouncing,
+// ticks and jobs line up much as they did; the keyboard works alongside it,
+// and once it's through a toast says so. Resizes are replayed as events,
+// but the terminal keeps its own size. Both flags at once records the
+// replay — and anything typed during it — to a new file.
+//
+// In tests, testing::replay() feeds a recording's keys to the app directly,
+// with no waiting (see testing.rs).
+// =============================================================================
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::Event;
+use serde::{Deserialize, Serialize};
+
+use crate::effects::Files;
+
+#[derive(Deserialize, Serialize)]
+struct Recorded {
+    ms: u64,
+    event: Event,
+}
+
+pub struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            out: BufWriter::new(File::create(path)?),
+            started: Instant::now(),
+        })
+    }
+
+    pub fn record(&mut self, event: &Event) -> io::Result<()> {
+        let line = serde_json::to_string(&Recorded {
+            ms: self.started.elapsed().as_millis() as u64,
+            event: event.clone(),
+        })?;
+        writeln!(self.out, "{line}")?;
+        self.out.flush() // so a crash keeps everything up to it
+    }
+}
+
+pub struct Replay {
+    events: VecDeque<(Duration, Event)>,
+    started: Instant,
+}
+
+impl Replay {
+    pub fn load(files: &dyn Files, path: &Path) -> Result<Self, String> {
+        let text = files.read(path).map_err(|err| err.to_string())?;
+        Ok(Self {
+            events: parse(&text)?.into(),
+            started: Instant::now(),
+        })
+    }
+
+    // How long until the next event is due; None once they're all out
+    pub fn wait(&self) -> Option<Duration> {
+        let (at, _) = self.events.front()?;
+        Some(at.saturating_sub(self.started.elapsed()))
+    }
+
+    // The next event, if it's time for it
+    pub fn next(&mut self) -> Option<Event> {
+        match self.wait()? {
+            Duration::ZERO => self.events.pop_front().map(|(_, event)| event),
+            _ => None,
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+// A recording's events, each with when it arrived
+pub fn parse(text: &str) -> Result<Vec<(Duration, Event)>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let recorded: Recorded =
+                serde_json::from_str(line).map_err(|err| format!("line {}: {err}", i + 1))?;
+            Ok((Duration::from_millis(recorded.ms), recorded.event))
+        })
+        .collect()
+}
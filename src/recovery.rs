@@ -0,0 +1,118 @@
+// =============================================================================
+// Crash recovery — don't lose the half-typed command when things go wrong
+//
+// The main loop keeps a copy of the input line and of the commands that
+// haven't reached the history file yet (record()). If the app panics, or the
+// terminal goes away (SIGHUP when an SSH connection drops, SIGTERM), that
+// copy is written to <state dir>/recovery.json. On the next launch the app
+// offers to restore it with `recover` (or `recover discard`).
+// =============================================================================
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+use color_eyre::{Result, eyre::OptionExt};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Recovery {
+    pub input: String,
+    pub history: Vec<String>, // submitted but not yet saved to the history file
+}
+
+impl Recovery {
+    fn is_empty(&self) -> bool {
+        self.input.is_empty() && self.history.is_empty()
+    }
+}
+
+static SNAPSHOT: Mutex<Option<Recovery>> = Mutex::new(None);
+
+// Keep the snapshot current — call after anything that changes the input
+pub fn record(input: &str, unsaved_history: &[String]) {
+    let mut snapshot = SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner());
+    let snapshot = snapshot.get_or_insert_with(Recovery::default);
+    if snapshot.input != input {
+        snapshot.input = input.to_string();
+    }
+    if snapshot.history != unsaved_history {
+        snapshot.history = unsaved_history.to_vec();
+    }
+}
+
+// Write the latest snapshot to the recovery file (if there's anything in it)
+pub fn stash() {
+    let snapshot = SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let Some(snapshot) = snapshot.filter(|s| !s.is_empty()) else {
+        return;
+    };
+    let result = recovery_path().and_then(|path| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&snapshot)?)?;
+        Ok(path)
+    });
+    match result {
+        Ok(path) => tracing::warn!("stashed unsaved input to {}", path.display()),
+        Err(err) => tracing::error!("couldn't stash unsaved input: {err:#}"),
+    }
+}
+
+// What a previous run left behind, if anything
+pub fn pending() -> Option<Recovery> {
+    let text = fs::read_to_string(recovery_path().ok()?).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+pub fn discard() {
+    if let Ok(path) = recovery_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// Stash on panic, then hand over to the previous hook (color-eyre's report,
+// ratatui's terminal restore). Only the main thread counts: a panicking
+// background job doesn't take the app down.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("main") {
+            stash();
+        }
+        previous(info);
+    }));
+}
+
+// Becomes true when the terminal hangs up or we're asked to terminate; the
+// main loop checks it, stashes, and exits cleanly
+pub fn termination_flag() -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    for signal in [signal_hook::consts::SIGHUP, signal_hook::consts::SIGTERM] {
+        if let Err(err) = signal_hook::flag::register(signal, Arc::clone(&flag)) {
+            tracing::warn!("can't watch for signal {signal}: {err}");
+        }
+    }
+    flag
+}
+
+pub fn terminated(flag: &AtomicBool) -> bool {
+    flag.load(Ordering::Relaxed)
+}
+
+fn recovery_path() -> Result<PathBuf> {
+    Ok(paths::state_dir()
+        .ok_or_eyre("can't find a state directory (is $HOME set?)")?
+        .join("recovery.json"))
+}
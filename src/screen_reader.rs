@@ -0,0 +1,120 @@
+// =============================================================================
+// Screen reader mode — `screen_reader = true` in the config, or --screen-reader
+//
+//   Welcome! Type 'help' for commands.
+//   Hello, ada!
+//   > your typing here█
+//
+// A screen reader reads a terminal by what changes in it and where the
+// cursor is, and the panels get in its way: borders, spinners and gauges
+// are read out glyph by glyph, a scrolling Output panel changes every row
+// at once, and the cursor waits in the input bar at the bottom, well away
+// from the line that just came in. So in this mode:
+//
+//   - Output is a plain transcript taking the whole screen: no border,
+//     title, status or side panel, long lines wrapped rather than cut off
+//   - the "> " prompt comes straight after its last line, so the cursor is
+//     next to whatever came in last, until the screen is full and both sit
+//     at the bottom
+//   - toasts are lines in it like any other ("  info: Config reloaded")
+//     instead of notices that come and go in a corner
+//   - nothing animates, as with reduced_motion
+//   - what panes and popups still draw has its box drawing, block, shape
+//     and braille characters blanked (plain()), except for the pointers
+//     (▶, ›) that mark a selection, which become ">"
+// =============================================================================
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::{Frame, buffer::Buffer, layout::Rect, widgets::Paragraph};
+
+use crate::{App, input_line, pane};
+
+const PROMPT: &str = "> ";
+
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+// For --screen-reader, before the app starts
+pub fn force() {
+    FORCED.store(true, Ordering::Relaxed);
+}
+
+pub fn forced() -> bool {
+    FORCED.load(Ordering::Relaxed)
+}
+
+// Everything but popups, toasts and the debug overlay, which render() draws
+// over it as usual
+pub fn render(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    app.minimap_area.set(None);
+    let room = area.height.saturating_sub(1);
+
+    // A pane has the screen above the prompt; otherwise the transcript runs
+    // down to it
+    let used = match &app.pane {
+        Some(p) => {
+            let above = Rect {
+                height: room,
+                ..area
+            };
+            pane::render(
+                frame,
+                p.as_ref(),
+                above,
+                &app.config.theme,
+                app.pane_focused,
+            );
+            room
+        }
+        None => {
+            app.output_width.set(area.width);
+            app.output_rows.set(usize::from(room).max(1));
+            let lines = app.shown();
+            let last_top = lines.rows().saturating_sub(usize::from(room));
+            let top = app.output_top.unwrap_or(last_top).min(last_top);
+            let text = lines.rendered(top..top + usize::from(room));
+            let used = text.len() as u16;
+            frame.render_widget(
+                Paragraph::new(text),
+                Rect {
+                    height: used,
+                    ..area
+                },
+            );
+            used
+        }
+    };
+
+    let row = area.y + used;
+    let typing = app.input.to_string();
+    let width = area.width.saturating_sub(PROMPT.len() as u16);
+    let (shown, column) = input_line::visible(&typing, app.input.cursor(), width);
+    let prompt = Rect {
+        y: row,
+        height: 1,
+        ..area
+    };
+    frame.render_widget(Paragraph::new(format!("{PROMPT}{shown}")), prompt);
+    if !app.pane_focused {
+        frame.set_cursor_position((area.x + PROMPT.len() as u16 + column, row));
+    }
+}
+
+// Blank what's only decoration, for panes and popups that draw it anyway
+pub fn plain(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        let mut chars = cell.symbol().chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let plain = match c {
+            '▶' | '►' | '▸' | '›' => ">",
+            '◀' | '◄' | '◂' | '‹' => "<",
+            // Box drawing, block elements, geometric shapes; braille
+            '\u{2500}'..='\u{25ff}' | '\u{2800}'..='\u{28ff}' => " ",
+            _ => continue,
+        };
+        cell.set_symbol(plain);
+    }
+}
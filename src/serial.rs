@@ -0,0 +1,337 @@
+// =============================================================================
+// Serial — `serial open <port> [baud]`: a serial port monitor (the `serial`
+// feature)
+//
+//   cargo run --features serial
+//   > serial list
+//   > serial open /dev/ttyUSB0 115200
+//
+// What the device writes is shown live in a pane, a line at a time, and
+// whatever is typed into the input bar is written to it (`/close` still
+// closes the pane, and with it the port). The baud rate defaults to
+// 115200; the rest is 8N1 with no flow control, which is what boards
+// expect.
+//
+// Each typed line goes out with a line ending — \n to start with; e (Tab to
+// the pane first) switches to \r\n, \r or none, for devices that want
+// something else (AT modems want \r).
+//
+// A background job owns the port. If the device goes away (unplugged,
+// reset), the pane says so and stays open to read; `serial open` again
+// once it's back.
+//
+//   ↑/↓ PgUp/PgDn Home/End   scroll back (End follows new output again)
+// =============================================================================
+
+use std::{
+    cell::Cell,
+    io::{ErrorKind, Read, Write},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver, Sender, TryRecvError},
+    },
+    time::Duration,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use serialport::SerialPortType;
+
+use crate::{
+    ctx::{Ctx, Status},
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+};
+
+const BAUD: u32 = 115_200;
+const POLL: Duration = Duration::from_millis(50); // how often the job checks for lines to send
+const MAX_LINES: usize = 5_000; // older ones are dropped
+const ENDINGS: [(&str, &str); 4] = [
+    ("\n", "\\n"),
+    ("\r\n", "\\r\\n"),
+    ("\r", "\\r"),
+    ("", "none"),
+];
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: serial list | serial open <port> [baud]";
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let (port, baud) = match words[..] {
+        ["list"] => return list(ctx),
+        ["open", port] => (port, BAUD),
+        ["open", port, baud] => match baud.parse() {
+            Ok(baud) => (port, baud),
+            Err(_) => return ctx.fail(format!("  {baud} isn't a baud rate")),
+        },
+        _ => return ctx.fail(usage),
+    };
+    // Opened here, so a wrong path or a busy port fails the command
+    let device = match serialport::new(port, baud).timeout(POLL).open() {
+        Ok(device) => device,
+        Err(err) => return ctx.fail(format!("  Can't open {port}: {err}")),
+    };
+    let (outgoing, lines) = mpsc::channel();
+    let view = SerialView {
+        title: format!("Serial: {port} at {baud}"),
+        log: Shared::default(),
+        outgoing,
+        ending: 0,
+        scroll: None,
+        count: Cell::new(0),
+        height: Cell::new(1),
+    };
+    let log = view.log.clone();
+    ctx.show(view);
+    ctx.spawner.spawn_watcher(move |_| {
+        if let Some(why) = run(device, &log, &lines) {
+            note(&log, |l| {
+                l.open = false;
+                l.push(Direction::Note, why.into_bytes());
+            });
+        }
+    });
+    Status::Success
+}
+
+fn list(ctx: &mut Ctx) -> Status {
+    let ports = match serialport::available_ports() {
+        Ok(ports) => ports,
+        Err(err) => return ctx.fail(format!("  Can't list serial ports: {err}")),
+    };
+    if ports.is_empty() {
+        ctx.print("  No serial ports found");
+    }
+    for port in ports {
+        let kind = match port.port_type {
+            SerialPortType::UsbPort(usb) => {
+                let product = usb.product.unwrap_or_default();
+                format!("USB {:04x}:{:04x} {product}", usb.vid, usb.pid)
+            }
+            SerialPortType::BluetoothPort => "Bluetooth".to_string(),
+            SerialPortType::PciPort => "PCI".to_string(),
+            SerialPortType::Unknown => String::new(),
+        };
+        ctx.print(format!("  {:<20} {kind}", port.port_name));
+    }
+    Status::Success
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    In,
+    Out,
+    Note, // the port went away…
+}
+
+struct Log {
+    lines: Vec<(Direction, Vec<u8>)>,
+    open: bool,
+    changed: bool, // since the last render
+    closed: bool,  // the pane is gone
+}
+
+impl Default for Log {
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            open: true,
+            changed: false,
+            closed: false,
+        }
+    }
+}
+
+impl Log {
+    // Devices write a line in as many pieces as they like; put it back
+    // together, and split what arrives together into lines
+    fn push(&mut self, direction: Direction, bytes: Vec<u8>) {
+        if direction != Direction::In {
+            self.lines.push((direction, bytes));
+        } else {
+            for piece in bytes.split_inclusive(|&b| b == b'\n') {
+                match self.lines.last_mut() {
+                    Some((Direction::In, last)) if !last.ends_with(b"\n") => {
+                        last.extend_from_slice(piece);
+                    }
+                    _ => self.lines.push((Direction::In, piece.to_vec())),
+                }
+            }
+        }
+        let excess = self.lines.len().saturating_sub(MAX_LINES);
+        self.lines.drain(..excess);
+        self.changed = true;
+    }
+}
+
+type Shared = Arc<Mutex<Log>>;
+
+fn note(log: &Shared, f: impl FnOnce(&mut Log)) -> bool {
+    match log.lock() {
+        Ok(mut log) => {
+            f(&mut log);
+            !log.closed
+        }
+        Err(_) => false,
+    }
+}
+
+// Until the device goes away (Some(why)) or the pane closes
+fn run(
+    mut device: Box<dyn serialport::SerialPort>,
+    log: &Shared,
+    outgoing: &Receiver<Vec<u8>>,
+) -> Option<String> {
+    let mut buffer = [0; 4096];
+    loop {
+        loop {
+            match outgoing.try_recv() {
+                Ok(bytes) => {
+                    if let Err(err) = device.write_all(&bytes) {
+                        return Some(format!("can't write: {err}"));
+                    }
+                    note(log, |l| l.push(Direction::Out, bytes));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return None,
+            }
+        }
+        match device.read(&mut buffer) {
+            Ok(0) => return Some("the device went away".to_string()),
+            Ok(n) => {
+                if !note(log, |l| l.push(Direction::In, buffer[..n].to_vec())) {
+                    return None;
+                }
+            }
+            Err(err) if err.kind() == ErrorKind::TimedOut => {
+                if !note(log, |_| {}) {
+                    return None;
+                }
+            }
+            Err(err) => return Some(format!("the device went away: {err}")),
+        }
+    }
+}
+
+// Control characters as escapes, so a device's stray ESC can't upset the
+// screen; the line ending itself isn't shown
+fn printable(line: &[u8]) -> String {
+    String::from_utf8_lossy(line)
+        .trim_end_matches(['\r', '\n'])
+        .chars()
+        .map(|c| match c {
+            '\t' => "    ".to_string(),
+            '\r' => "\\r".to_string(),
+            c if c.is_control() => format!("\\x{:02x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+pub struct SerialView {
+    title: String,
+    log: Shared,
+    outgoing: Sender<Vec<u8>>,
+    ending: usize,         // into ENDINGS
+    scroll: Option<usize>, // lines back from the newest; None = following
+    count: Cell<usize>,    // lines, as of the last render
+    height: Cell<usize>,   // rows that fit, as of the last render
+}
+
+impl SerialView {
+    fn scroll_by(&mut self, step: isize) {
+        let last = self.count.get().saturating_sub(self.height.get());
+        let back = self
+            .scroll
+            .unwrap_or(0)
+            .saturating_add_signed(step)
+            .min(last);
+        self.scroll = (back > 0).then_some(back);
+    }
+}
+
+impl Drop for SerialView {
+    fn drop(&mut self) {
+        if let Ok(mut log) = self.log.lock() {
+            log.closed = true;
+        }
+    }
+}
+
+impl Pane for SerialView {
+    fn title(&self) -> String {
+        let open = self.log.lock().is_ok_and(|l| l.open);
+        let state = if open { "open" } else { "closed" };
+        format!("{} ({state}, sends {})", self.title, ENDINGS[self.ending].1)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Ok(mut log) = self.log.lock() else {
+            return;
+        };
+        log.changed = false;
+        let height = area.height as usize;
+        self.height.set(height);
+        self.count.set(log.lines.len());
+        let end = log.lines.len().saturating_sub(self.scroll.unwrap_or(0));
+        let start = end.saturating_sub(height);
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let lines: Vec<Line> = log.lines[start..end]
+            .iter()
+            .map(|(direction, bytes)| {
+                let (mark, style) = match direction {
+                    Direction::In => ("← ", Style::default().fg(theme.input_text)),
+                    Direction::Out => ("→ ", Style::default().fg(Color::Cyan)),
+                    Direction::Note => ("· ", dim),
+                };
+                Line::from(vec![
+                    Span::styled(mark, style),
+                    Span::styled(printable(bytes), style),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get() as isize;
+        match key.code {
+            KeyCode::Up => self.scroll_by(1),
+            KeyCode::Down => self.scroll_by(-1),
+            KeyCode::PageUp => self.scroll_by(page),
+            KeyCode::PageDown => self.scroll_by(-page),
+            KeyCode::Home => self.scroll_by(isize::MAX),
+            KeyCode::End => self.scroll = None,
+            KeyCode::Char('e') => self.ending = (self.ending + 1) % ENDINGS.len(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.log.lock().is_ok_and(|l| l.changed)
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("↑↓", "scroll"),
+            ("e", "line ending"),
+            ("Tab", "type a line"),
+        ]
+    }
+
+    fn input_label(&self) -> Option<String> {
+        Some("Send".to_string())
+    }
+
+    fn submit(&mut self, line: &str) {
+        let bytes = format!("{line}{}", ENDINGS[self.ending].0).into_bytes();
+        let _ = self.outgoing.send(bytes);
+    }
+}
@@ -0,0 +1,204 @@
+// =============================================================================
+// Sessions — save the working context to disk and pick it up again later
+//
+//   session save [name]   — write messages, input history and variables
+//   session load [name]   — restore them (no name = most recently saved)
+//   session list          — show saved sessions, newest first
+//
+// Sessions are JSON files in <data dir>/sessions/<name>.json (see paths.rs).
+// With `[session] autoload = true` in the config, the most recent one is
+// loaded at startup. Every `autosave_minutes` the current state is also
+// saved as the "autosave" session, so a crash or dropped SSH connection
+// loses at most a few minutes of context.
+//
+//   export <file.json>    — the same, plus the live settings, to any path
+//   import <file.json>    — restore an export (e.g. on another machine)
+// =============================================================================
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, WrapErr, bail},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    effects::Files,
+    paths,
+    validate::{self, Problem},
+};
+
+pub const DEFAULT_NAME: &str = "default";
+pub const AUTOSAVE_NAME: &str = "autosave"; // written every autosave_minutes
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub messages: Vec<Arc<str>>,
+    pub history: Vec<String>,
+    pub vars: BTreeMap<String, String>,
+}
+
+impl Session {
+    pub fn save(&self, files: &dyn Files, name: &str) -> Result<PathBuf> {
+        let path = session_path(name)?;
+        if let Some(dir) = path.parent() {
+            (files.create_dir_all(dir)).wrap_err_with(|| format!("creating {}", dir.display()))?;
+        }
+
+        // Write to a temporary file and rename it into place, so a crash
+        // mid-write can't leave a truncated session behind
+        let tmp = path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(self)?;
+        (files.write(&tmp, &json)).wrap_err_with(|| format!("writing {}", tmp.display()))?;
+        (files.rename(&tmp, &path)).wrap_err_with(|| format!("writing {}", path.display()))?;
+        Ok(path)
+    }
+
+    // Returns the session's name alongside it, since `None` means
+    // "whichever was saved last"
+    pub fn load(files: &dyn Files, name: Option<&str>) -> Result<(String, Session)> {
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => list(files)?
+                .into_iter()
+                .next()
+                .ok_or_eyre("no saved sessions yet")?,
+        };
+        let path = session_path(&name)?;
+        let text = (files.read(&path)).wrap_err_with(|| format!("reading {}", path.display()))?;
+        let session =
+            serde_json::from_str(&text).wrap_err_with(|| format!("parsing {}", path.display()))?;
+        Ok((name, session))
+    }
+}
+
+// =============================================================================
+// State export/import — a self-describing snapshot for bug reports and for
+// moving a session between machines
+// =============================================================================
+const EXPORT_FORMAT: u32 = 1; // bump when the layout changes incompatibly
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateExport {
+    pub format: u32,
+    pub app: String,      // "<name> <version>" of whoever wrote it
+    pub exported_at: u64, // seconds since the Unix epoch
+    #[serde(flatten)]
+    pub session: Session,
+    #[serde(default)]
+    pub settings: Config,
+}
+
+impl StateExport {
+    pub fn new(session: Session, settings: Config) -> Self {
+        Self {
+            format: EXPORT_FORMAT,
+            app: concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")).to_string(),
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            session,
+            settings,
+        }
+    }
+
+    pub fn write(&self, files: &dyn Files, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        (files.write(path, &json)).wrap_err_with(|| format!("writing {}", path.display()))
+    }
+
+    // The settings come from anywhere, so they're checked as the config
+    // file's are (see validate.rs): what doesn't check out is reported and
+    // left at its default
+    pub fn read(files: &dyn Files, path: &Path) -> Result<(Self, Vec<Problem>)> {
+        let text = (files.read(path)).wrap_err_with(|| format!("reading {}", path.display()))?;
+        let parsing = || format!("parsing {}", path.display());
+        let mut json: serde_json::Value = serde_json::from_str(&text).wrap_err_with(parsing)?;
+        let settings = json
+            .as_object_mut()
+            .and_then(|json| json.remove("settings"));
+        let mut export: Self = serde_json::from_value(json).wrap_err_with(parsing)?;
+        if export.format > EXPORT_FORMAT {
+            bail!(
+                "{} was written by a newer version ({}); this one reads format {EXPORT_FORMAT}",
+                path.display(),
+                export.app
+            );
+        }
+        let (settings, problems) =
+            validate::check_json(settings.unwrap_or_default()).wrap_err_with(parsing)?;
+        export.settings = settings;
+        Ok((export, problems))
+    }
+}
+
+// =============================================================================
+// Input history — one command per line in <data dir>/history, so Up/Down
+// remembers commands across restarts
+// =============================================================================
+pub fn load_history(files: &dyn Files) -> Vec<String> {
+    history_path()
+        .and_then(|path| files.read(&path).ok())
+        .map(|text| text.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+pub fn save_history(files: &dyn Files, history: &[String]) -> Result<()> {
+    let path = history_path().ok_or_eyre("can't find a data directory (is $HOME set?)")?;
+    if let Some(dir) = path.parent() {
+        (files.create_dir_all(dir)).wrap_err_with(|| format!("creating {}", dir.display()))?;
+    }
+    let mut text = history.join("\n");
+    text.push('\n');
+    (files.write(&path, &text)).wrap_err_with(|| format!("writing {}", path.display()))
+}
+
+fn history_path() -> Option<PathBuf> {
+    Some(paths::data_dir()?.join("history"))
+}
+
+// Saved session names, most recently saved first
+pub fn list(files: &dyn Files) -> Result<Vec<String>> {
+    let dir = sessions_dir()?;
+    if files.metadata(&dir).is_err() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions: Vec<(SystemTime, String)> = Vec::new();
+    for path in (files.read_dir(&dir)).wrap_err_with(|| format!("reading {}", dir.display()))? {
+        if path.extension().is_some_and(|ext| ext == "json")
+            && let Some(name) = path.file_stem().and_then(|s| s.to_str())
+        {
+            sessions.push((modified(files, &path), name.to_string()));
+        }
+    }
+    sessions.sort_by(|a, b| b.cmp(a));
+    Ok(sessions.into_iter().map(|(_, name)| name).collect())
+}
+
+fn sessions_dir() -> Result<PathBuf> {
+    Ok(paths::data_dir()
+        .ok_or_eyre("can't find a data directory (is $HOME set?)")?
+        .join("sessions"))
+}
+
+fn session_path(name: &str) -> Result<PathBuf> {
+    if !paths::is_valid_name(name) {
+        bail!("session names may only contain letters, digits, '-' and '_'");
+    }
+    Ok(sessions_dir()?.join(format!("{name}.json")))
+}
+
+fn modified(files: &dyn Files, path: &Path) -> SystemTime {
+    (files.metadata(path).ok())
+        .and_then(|meta| meta.modified)
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
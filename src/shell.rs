@@ -0,0 +1,106 @@
+// =============================================================================
+// Shell — run a command line through the system shell, output into Output
+//
+//   > !cargo check             the rest of the line, as a job that `cancel`
+//                              stops
+//
+// From code:
+//
+//   let processes = ctx.effects.processes.clone();
+//   ctx.spawner.spawn(move |tx| {
+//       let _ = shell::run(&*processes, &tx, "cargo check");
+//   });
+//
+// run() blocks until the command exits, so call it from a job. Its stdout
+// and stderr are printed as they come, a line at a time, then a closing
+// line with the exit status and how long it took. The command goes through
+// `sh -c` (`cmd /C` on Windows) so pipes, globs and && work as typed.
+//
+// run_command() does the same for a Command of your own (no shell in
+// between), shown as `shown`, and kills it as soon as `cancelled` says so:
+//
+//   shell::run_command(&*processes, &tx, "ssh web1 uptime", ssh, || progress.cancelled())
+//
+// Either way it's `processes` that runs it (see effects.rs).
+//
+// Either way the child gets the app's environment with `env set` / `env
+// unset` applied (see env.rs).
+// =============================================================================
+
+use std::{
+    io,
+    process::{Command, ExitStatus},
+    sync::mpsc::Sender,
+    time::Instant,
+};
+
+use crate::{
+    ctx::{AppEvent, Ctx, Status},
+    effects::Processes,
+    env,
+};
+
+// `!<command line>`
+pub fn command(ctx: &mut Ctx, line: &str) -> Status {
+    if line.is_empty() {
+        return ctx.fail("  Usage: !<shell command>");
+    }
+    let line = line.to_string();
+    let processes = ctx.effects.processes.clone();
+    ctx.spawner
+        .spawn_with_progress(format!("!{line}"), move |tx, progress| {
+            progress.unknown();
+            // Failures (can't start, exit codes) are printed by run_command()
+            let cancelled = || progress.cancelled();
+            let _ = run_command(&*processes, &tx, &line, shell(&line), &cancelled);
+        });
+    Status::Success
+}
+
+pub fn run(
+    processes: &dyn Processes,
+    tx: &Sender<AppEvent>,
+    command: &str,
+) -> io::Result<ExitStatus> {
+    run_command(processes, tx, command, shell(command), &|| false)
+}
+
+pub fn run_command(
+    processes: &dyn Processes,
+    tx: &Sender<AppEvent>,
+    shown: &str,
+    mut command: Command,
+    cancelled: &dyn Fn() -> bool,
+) -> io::Result<ExitStatus> {
+    let print = |line: String| {
+        let _ = tx.send(AppEvent::Print(line));
+    };
+    print(format!("  $ {shown}"));
+    env::apply(&mut command);
+    let started = Instant::now();
+    let status = processes
+        .run(tx, command, cancelled)
+        .inspect_err(|err| print(format!("  Can't run {shown}: {err}")))?;
+
+    let secs = started.elapsed().as_secs_f64();
+    match status.code() {
+        Some(0) => print(format!("  ✓ done in {secs:.1}s")),
+        Some(code) => print(format!("  ✗ exit code {code} after {secs:.1}s")),
+        None => print(format!("  ✗ killed after {secs:.1}s")),
+    }
+    Ok(status)
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
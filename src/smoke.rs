@@ -0,0 +1,69 @@
+// =============================================================================
+// Smoke test — `--smoke-test`: start up, draw, run `help`, and say if it worked
+//
+//   $ tui-template --smoke-test && echo ok
+//   smoke test passed: started, drew 80×24, ran `help`
+//   ok
+//
+// A cheap end-to-end check for CI and packaging scripts, which have no
+// terminal: the app starts as it would (config, history, profile), draws
+// a frame into a TestBackend instead of the screen, runs `help` and draws
+// again. Exits 0 if the greeting and help's output were both on screen,
+// else 1 with what was missing on stderr; a panic on the way fails too.
+// Nothing is saved.
+// =============================================================================
+
+use std::process::ExitCode;
+
+use color_eyre::Result;
+use ratatui::{Terminal, backend::TestBackend};
+
+use crate::{App, ctx::Status, render};
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+
+pub fn run() -> Result<ExitCode> {
+    let mut app = App::new();
+    app.greet();
+    app.drain_events();
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT))?;
+    let mut failures = Vec::new();
+
+    terminal.draw(|frame| render(frame, &app))?;
+    if !screen(&terminal).contains("Welcome!") {
+        failures.push("the first frame has no greeting".to_string());
+    }
+
+    let before = app.messages.len();
+    if app.submit("help") != Status::Success {
+        failures.push("`help` failed".to_string());
+    }
+    app.drain_events();
+    terminal.draw(|frame| render(frame, &app))?;
+    let mut printed = (before..app.messages.len()).map(|i| app.messages.get(i));
+    match printed.rfind(|line| !line.trim().is_empty()) {
+        None => failures.push("`help` printed nothing".to_string()),
+        Some(last) => {
+            let start: String = last.trim().chars().take(30).collect();
+            if !screen(&terminal).contains(&start) {
+                failures.push(format!("`help`'s last line isn't on screen: {start}"));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        println!("smoke test passed: started, drew {WIDTH}×{HEIGHT}, ran `help`");
+        return Ok(ExitCode::SUCCESS);
+    }
+    for failure in &failures {
+        eprintln!("smoke test failed: {failure}");
+    }
+    Ok(ExitCode::FAILURE)
+}
+
+// The cells' text, row after row
+fn screen(terminal: &Terminal<TestBackend>) -> String {
+    let buffer = terminal.backend().buffer();
+    buffer.content().iter().map(|cell| cell.symbol()).collect()
+}
@@ -0,0 +1,169 @@
+// =============================================================================
+// SQL — `sql open <db>`, then `sql <query>`: SQLite, results in a table pane
+//
+//   > sql open shop.db
+//   > sql tables
+//   > sql select name, total from orders order by total desc
+//   > set customer 42
+//   > sql select * from orders where customer_id = :customer
+//
+// `:name` in a query is a parameter bound to the variable of that name
+// (`set name value`), so values are never pasted into the SQL text — unlike
+// $name, which is. Values that look like numbers are bound as numbers.
+//
+// A query that returns rows opens them in the table pane (sort with 1–9,
+// filter with /); anything else says how many rows it changed. `sql close`
+// closes the database. At most MAX_ROWS rows are shown.
+// =============================================================================
+
+use std::path::Path;
+
+use rusqlite::{Connection, ToSql, types::ValueRef};
+
+use crate::{
+    ctx::{Ctx, Status},
+    widgets::TableView,
+};
+
+const MAX_ROWS: usize = 100_000;
+
+pub struct Database {
+    connection: Connection,
+    name: String, // file name, for titles
+}
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let (op, rest) = args.split_once(' ').unwrap_or((args, ""));
+    match op {
+        "" => ctx.fail("  Usage: sql open <db> | sql <query> | sql tables | sql close"),
+        "open" if !rest.trim().is_empty() => open(ctx, rest.trim()),
+        "close" => match ctx.connections.database.take() {
+            Some(db) => {
+                ctx.print(format!("  Closed {}", db.name));
+                Status::Success
+            }
+            None => ctx.fail("  No database is open"),
+        },
+        "tables" => query(
+            ctx,
+            "select name, type from sqlite_schema \
+             where type in ('table', 'view') and name not like 'sqlite_%' order by name",
+        ),
+        _ => query(ctx, args),
+    }
+}
+
+fn open(ctx: &mut Ctx, path: &str) -> Status {
+    let connection = match Connection::open(path) {
+        Ok(connection) => connection,
+        Err(err) => return ctx.fail(format!("  Can't open {path}: {err}")),
+    };
+    // Opening creates the file lazily; reading the schema finds non-databases
+    let tables: rusqlite::Result<i64> = connection.query_row(
+        "select count(*) from sqlite_schema where type = 'table'",
+        [],
+        |row| row.get(0),
+    );
+    let tables = match tables {
+        Ok(tables) => tables,
+        Err(err) => return ctx.fail(format!("  Can't open {path}: {err}")),
+    };
+    let name = Path::new(path)
+        .file_name()
+        .map_or(path.to_string(), |name| name.to_string_lossy().into_owned());
+    let s = if tables == 1 { "" } else { "s" };
+    ctx.print(format!("  Opened {name} ({tables} table{s})"));
+    ctx.connections.database = Some(Database { connection, name });
+    Status::Success
+}
+
+fn query(ctx: &mut Ctx, sql: &str) -> Status {
+    // Out of ctx while the query runs, so failing doesn't fight the borrow
+    let Some(db) = ctx.connections.database.take() else {
+        return ctx.fail("  No database is open — `sql open <path>` first");
+    };
+    let status = run(ctx, &db, sql);
+    ctx.connections.database = Some(db);
+    status
+}
+
+fn run(ctx: &mut Ctx, db: &Database, sql: &str) -> Status {
+    let mut statement = match db.connection.prepare(sql) {
+        Ok(statement) => statement,
+        Err(err) => return ctx.fail(format!("  {err}")),
+    };
+
+    // :name parameters come from variables
+    let mut values = Vec::new();
+    for i in 1..=statement.parameter_count() {
+        let name = statement.parameter_name(i).unwrap_or("?");
+        let Some(value) = name
+            .strip_prefix([':', '@', '$'])
+            .and_then(|var| ctx.vars.get(var))
+        else {
+            return ctx.fail(format!(
+                "  No value for {name} — set one with `set {} <value>`",
+                name.trim_start_matches([':', '@', '$', '?'])
+            ));
+        };
+        values.push(bindable(value));
+    }
+    let params: Vec<&dyn ToSql> = values.iter().map(|v| v.as_ref()).collect();
+
+    if statement.column_count() == 0 {
+        return match statement.execute(params.as_slice()) {
+            Ok(changed) => {
+                let s = if changed == 1 { "" } else { "s" };
+                ctx.print(format!("  {changed} row{s} changed"));
+                Status::Success
+            }
+            Err(err) => ctx.fail(format!("  {err}")),
+        };
+    }
+
+    let headers: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let columns = headers.len();
+    let mut rows = Vec::new();
+    let result = statement.query(params.as_slice()).and_then(|mut results| {
+        while rows.len() < MAX_ROWS
+            && let Some(row) = results.next()?
+        {
+            let cells = (0..columns)
+                .map(|i| row.get_ref(i).map(cell_text))
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+            rows.push(cells);
+        }
+        Ok(())
+    });
+    if let Err(err) = result {
+        return ctx.fail(format!("  {err}"));
+    }
+    let title = format!("SQL: {}", db.name);
+    ctx.show(TableView::new(title, headers).show_kinds().rows(rows));
+    Status::Success
+}
+
+// A variable's value as the most specific SQL type it reads as
+fn bindable(value: &str) -> Box<dyn ToSql> {
+    if let Ok(n) = value.parse::<i64>() {
+        Box::new(n)
+    } else if let Ok(x) = value.parse::<f64>() {
+        Box::new(x)
+    } else {
+        Box::new(value.to_string())
+    }
+}
+
+fn cell_text(value: ValueRef) -> String {
+    match value {
+        ValueRef::Null => String::new(),
+        ValueRef::Integer(n) => n.to_string(),
+        ValueRef::Real(x) => x.to_string(),
+        ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned(),
+        ValueRef::Blob(bytes) => format!("<{} bytes>", bytes.len()),
+    }
+}
@@ -0,0 +1,51 @@
+// =============================================================================
+// SSH — `ssh <[user@]host> <command>`: run a command on another machine
+//
+//   > ssh deploy@web1 systemctl status nginx
+//   > ssh ssh://admin@10.0.0.7:2222 df -h
+//   > jobs
+//   > cancel 3
+//
+// A job that shells out to the `ssh` on PATH, so hosts, keys, ports and
+// jump hosts come from ~/.ssh/config and the agent as usual. The remote
+// stdout and stderr are printed into Output as they arrive, then the exit
+// status, as for local commands (shell.rs). The job is listed by `jobs`,
+// and `cancel` stops it by hanging up.
+//
+// There's no one to answer a prompt, so ssh runs in batch mode: a host
+// needs key (or agent) authentication and to be in known_hosts already —
+// connect once from a terminal first. The command is passed to the remote
+// shell as typed; quote as you would there.
+// =============================================================================
+
+use std::process::Command;
+
+use crate::{
+    ctx::{Ctx, Status},
+    shell,
+};
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let Some((host, remote)) = args.split_once(' ') else {
+        return ctx.fail("  Usage: ssh <[user@]host> <command>");
+    };
+    // Anything starting with - would be taken as an option of ssh's own
+    if host.starts_with('-') {
+        return ctx.fail(format!("  {host} isn't a host"));
+    }
+    let (host, remote) = (host.to_string(), remote.trim().to_string());
+    let mut ssh = Command::new("ssh");
+    ssh.args(["-T", "-o", "BatchMode=yes", "--"])
+        .arg(&host)
+        .arg(&remote);
+    let label = format!("ssh {host} {remote}");
+    let processes = ctx.effects.processes.clone();
+    ctx.spawner
+        .spawn_with_progress(label.clone(), move |tx, progress| {
+            progress.unknown();
+            // Failures (can't connect, exit codes) are printed by run_command()
+            let cancelled = || progress.cancelled();
+            let _ = shell::run_command(&*processes, &tx, &label, ssh, &cancelled);
+        });
+    Status::Success
+}
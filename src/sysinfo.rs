@@ -0,0 +1,187 @@
+// =============================================================================
+// System — `sysinfo`: CPU, memory and network as live sparklines (the
+// `sysinfo` feature)
+//
+//   cargo run --features sysinfo
+//   > sysinfo
+//
+// A pane (in the side panel, if the layout has one) sampled on every tick,
+// though no more often than the sysinfo crate can measure CPU usage. CPU
+// and memory are drawn against their full scale — 100%, all of the RAM — and
+// network traffic against the busiest moment on screen. The last HISTORY
+// samples are kept; a narrow panel shows the most recent that fit.
+//
+// A monitoring app cloned from the template starts here: add a section to
+// render() for whatever else it watches.
+// =============================================================================
+
+use std::{collections::VecDeque, time::Instant};
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Paragraph, Sparkline},
+};
+use sysinfo::{
+    CpuRefreshKind, MINIMUM_CPU_UPDATE_INTERVAL, MemoryRefreshKind, Networks, RefreshKind, System,
+};
+
+use crate::{
+    ctx::{Ctx, Status},
+    pane::Pane,
+    theme::Theme,
+};
+
+// Samples kept per series — wider than any sensible side panel
+const HISTORY: usize = 200;
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    if !args.is_empty() {
+        return ctx.fail("  Usage: sysinfo");
+    }
+    ctx.show(SysView::new());
+    Status::Success
+}
+
+#[derive(Default)]
+struct Series(VecDeque<f64>);
+
+impl Series {
+    fn push(&mut self, value: f64) {
+        if self.0.len() == HISTORY {
+            self.0.pop_front();
+        }
+        self.0.push_back(value);
+    }
+
+    fn latest(&self) -> f64 {
+        self.0.back().copied().unwrap_or(0.0)
+    }
+
+    // The newest `width` samples as 0..=100 of `scale` (or of the largest
+    // of them, without one)
+    fn bars(&self, width: u16, scale: Option<f64>) -> Vec<u64> {
+        let shown = self.0.len().min(width as usize);
+        let window = self.0.range(self.0.len() - shown..);
+        let top = scale.unwrap_or_else(|| window.clone().fold(0.0, |a: f64, &b| a.max(b)));
+        window
+            .map(|&v| match top > 0.0 {
+                true => (v / top * 100.0).round().clamp(0.0, 100.0) as u64,
+                false => 0,
+            })
+            .collect()
+    }
+}
+
+pub struct SysView {
+    system: System,
+    networks: Networks,
+    sampled: Instant,
+    cpu: Series,      // % of all cores
+    memory: Series,   // bytes in use
+    received: Series, // bytes per second, all interfaces
+    sent: Series,
+}
+
+impl SysView {
+    fn new() -> Self {
+        let refresh = RefreshKind::nothing()
+            .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+            .with_memory(MemoryRefreshKind::nothing().with_ram());
+        Self {
+            system: System::new_with_specifics(refresh),
+            networks: Networks::new_with_refreshed_list(),
+            sampled: Instant::now(),
+            cpu: Series::default(),
+            memory: Series::default(),
+            received: Series::default(),
+            sent: Series::default(),
+        }
+    }
+
+    fn sample(&mut self) {
+        let secs = self.sampled.elapsed().as_secs_f64();
+        self.sampled = Instant::now();
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.networks.refresh(true);
+        let (received, sent) = self.networks.iter().fold((0, 0), |(r, s), (_, data)| {
+            (r + data.received(), s + data.transmitted())
+        });
+        self.cpu.push(f64::from(self.system.global_cpu_usage()));
+        self.memory.push(self.system.used_memory() as f64);
+        self.received.push(received as f64 / secs);
+        self.sent.push(sent as f64 / secs);
+    }
+}
+
+// 512 B, 3.4 KiB, 1.2 GiB
+fn size(bytes: f64) -> String {
+    let mut value = bytes;
+    for unit in ["B", "KiB", "MiB", "GiB"] {
+        if value < 1024.0 {
+            return match unit {
+                "B" => format!("{value:.0} {unit}"),
+                _ => format!("{value:.1} {unit}"),
+            };
+        }
+        value /= 1024.0;
+    }
+    format!("{value:.1} TiB")
+}
+
+impl Pane for SysView {
+    fn title(&self) -> String {
+        match self.system.cpus().len() {
+            1 => "System (1 CPU)".to_string(),
+            n => format!("System ({n} CPUs)"),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let total = self.system.total_memory() as f64;
+        let sections = [
+            (
+                format!("cpu  {:.0}%", self.cpu.latest()),
+                self.cpu.bars(area.width, Some(100.0)),
+            ),
+            (
+                format!("memory  {} / {}", size(self.memory.latest()), size(total)),
+                self.memory.bars(area.width, Some(total)),
+            ),
+            (
+                format!("net in  {}/s", size(self.received.latest())),
+                self.received.bars(area.width, None),
+            ),
+            (
+                format!("net out  {}/s", size(self.sent.latest())),
+                self.sent.bars(area.width, None),
+            ),
+        ];
+
+        let rows = Layout::vertical([Constraint::Fill(1); 4]).split(area);
+        let label_style = Style::default().add_modifier(Modifier::BOLD);
+        let chart_style = Style::default().fg(theme.input_border);
+        for ((label, bars), &row) in sections.iter().zip(rows.iter()) {
+            let [label_row, chart_row] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(row);
+            frame.render_widget(Paragraph::new(label.as_str()).style(label_style), label_row);
+            let sparkline = Sparkline::default().data(bars).max(100).style(chart_style);
+            frame.render_widget(sparkline, chart_row);
+        }
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent) -> bool {
+        false
+    }
+
+    fn tick(&mut self) -> bool {
+        if self.sampled.elapsed() < MINIMUM_CPU_UPDATE_INTERVAL {
+            return false;
+        }
+        self.sample();
+        true
+    }
+}
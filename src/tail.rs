@@ -0,0 +1,161 @@
+// =============================================================================
+// Tail — `tail [-f] [-n <lines>] <path>`, like tail(1)
+//
+// Prints the last lines of a file (10 unless -n says otherwise), each
+// tagged with the file's name so several tails can share Output:
+//
+//   [app.log] GET /health 200
+//
+// With -f it keeps going as a background job, printing lines as they're
+// appended. A file truncated in place (`> app.log`) is read again from the
+// start; one that's replaced (log rotation renames it and starts a new
+// one) is followed into the new file. Stop it with `cancel` (see `jobs`).
+// =============================================================================
+
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+    sync::mpsc::Sender,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    ctx::{AppEvent, Ctx, ProgressHandle, Status},
+    effects::Files,
+};
+
+const POLL: Duration = Duration::from_millis(250);
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: tail [-f] [-n <lines>] <path>";
+    let (mut follow, mut count, mut path) = (false, 10, None);
+    let mut words = args.split_whitespace();
+    while let Some(word) = words.next() {
+        match word {
+            "-f" => follow = true,
+            "-n" => match words.next().map(str::parse) {
+                Some(Ok(n)) => count = n,
+                _ => return ctx.fail(usage),
+            },
+            _ if path.is_none() => path = Some(word.to_string()),
+            _ => return ctx.fail(usage),
+        }
+    }
+    let Some(path) = path else {
+        return ctx.fail(usage);
+    };
+    let files = ctx.effects.files.clone();
+    // Which file this is, for noticing when it's swapped for another
+    let id = files
+        .metadata(Path::new(&path))
+        .map_or(0, |meta| meta.identity);
+    let file = match files.open(Path::new(&path)) {
+        Ok(file) => file,
+        Err(err) => return ctx.fail(format!("  Can't read {path}: {err}")),
+    };
+    let tag = Path::new(&path)
+        .file_name()
+        .map_or(path.clone(), |name| name.to_string_lossy().into_owned());
+
+    // The last `count` lines, remembering where they stopped
+    let mut reader = BufReader::new(file);
+    let mut last = VecDeque::with_capacity(count + 1);
+    let mut line = String::new();
+    let mut position = 0;
+    while let Ok(n @ 1..) = reader.read_line(&mut line) {
+        position += n as u64;
+        last.push_back(line.trim_end_matches(['\r', '\n']).to_string());
+        if last.len() > count {
+            last.pop_front();
+        }
+        line.clear();
+    }
+    for line in last {
+        ctx.print(format!("  [{tag}] {line}"));
+    }
+    if !follow {
+        return Status::Success;
+    }
+
+    ctx.spawner
+        .spawn_with_progress(format!("tail {tag}"), move |tx, progress| {
+            let at = Place {
+                reader,
+                position,
+                id,
+            };
+            follow_file(&tx, &progress, &*files, &path, &tag, at);
+        });
+    Status::Success
+}
+
+// How far into which file
+struct Place {
+    reader: BufReader<Box<dyn Read + Send>>,
+    position: u64, // bytes read so far
+    id: u64,       // Metadata::identity, to tell a replaced file
+}
+
+fn follow_file(
+    tx: &Sender<AppEvent>,
+    progress: &ProgressHandle,
+    files: &dyn Files,
+    path: &str,
+    tag: &str,
+    mut at: Place,
+) {
+    let print = |line: String| {
+        let _ = tx.send(AppEvent::Print(line));
+    };
+    progress.unknown();
+    let mut line = String::new();
+    while !progress.cancelled() {
+        // Whatever has been appended; a partial last line waits for its \n
+        match at.reader.read_line(&mut line) {
+            Ok(n) if n > 0 && line.ends_with('\n') => {
+                at.position += line.len() as u64;
+                print(format!("  [{tag}] {}", line.trim_end_matches(['\r', '\n'])));
+                line.clear();
+                continue;
+            }
+            Ok(_) => {}
+            Err(err) => return print(format!("  [{tag}] Stopped following: {err}")),
+        }
+        thread::sleep(POLL);
+
+        let Ok(meta) = files.metadata(Path::new(path)) else {
+            continue; // mid-rotation: the new file isn't there yet
+        };
+        if meta.identity != at.id {
+            match files.open(Path::new(path)) {
+                Ok(file) => {
+                    // What was written to the old one before it was moved
+                    while at.reader.read_line(&mut line).is_ok_and(|n| n > 0) {
+                        print(format!("  [{tag}] {}", line.trim_end_matches(['\r', '\n'])));
+                        line.clear();
+                    }
+                    print(format!("  [{tag}] (replaced — following the new file)"));
+                    at = Place {
+                        reader: BufReader::new(file),
+                        position: 0,
+                        id: meta.identity,
+                    };
+                    line.clear();
+                }
+                Err(_) => continue,
+            }
+        } else if meta.len < at.position + line.len() as u64 {
+            // Read it again from the start
+            let Ok(file) = files.open(Path::new(path)) else {
+                continue;
+            };
+            print(format!("  [{tag}] (truncated — reading from the start)"));
+            at.reader = BufReader::new(file);
+            at.position = 0;
+            line.clear();
+        }
+    }
+    print(format!("  Stopped following {path}"));
+}
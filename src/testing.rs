@@ -0,0 +1,735 @@
+// =============================================================================
+// Testing — draw the app into ratatui's TestBackend and look at the result
+//
+//   let mut app = testing::app();
+//   testing::type_text(&mut app, "hello ada");
+//   testing::press(&mut app, "enter");
+//   insta::assert_snapshot!(testing::screen(&app, 60, 12));
+//
+// app() is the app as it starts — greeting and all — but with the default
+// config whatever the user's config file says (and no watcher on it), no
+// history, and no animations, so the same keys always draw the same screen. press() takes
+// key names as the keymap writes them ("ctrl-l", "f10", "x"), and applies
+// what they caused before returning. screen() is one line of text per row
+// (as text() makes of any buffer).
+// replay() plays the keys of a `--record` file (see recording.rs) the same
+// way, ignoring their timing — handy for turning a bug report into a test.
+// manual_clock() stops time on the test's thread until it's advanced, for
+// anything that happens after a while (see clock.rs):
+//
+//   let clock = testing::manual_clock();
+//   clock.advance(Duration::from_secs(10));
+//   app.on_tick();
+//
+// Commands that run programs, touch files or make requests do it through
+// app.effects; give it a Fake to keep the test in memory and see what was
+// asked (see effects.rs). settle() waits for the jobs commands started, and
+// applies what they sent:
+//
+//   let fake = Arc::new(Fake::default());
+//   app.effects = Effects::fake(&fake);
+//   … `!ls`, enter …
+//   testing::settle(&mut app);
+//   assert_eq!(fake.calls(), ["run sh -c ls"]);
+//
+// The snapshots live in src/snapshots/. After a deliberate UI change, review
+// and accept the new ones with `cargo insta review` (cargo install
+// cargo-insta), or `INSTA_UPDATE=always cargo test` to take them all.
+// =============================================================================
+
+use std::{rc::Rc, sync::Arc, thread, time::Duration};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Terminal,
+    backend::TestBackend,
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier},
+};
+
+use crate::{
+    App, Input,
+    clock::{self, ManualClock},
+    config::Config,
+    effects::{Effects, Fake},
+    keymap::{KeyBinding, Keymap},
+    net, notifications, recording, render,
+    theme::{Depth, Theme},
+    toast::Level,
+    validate::{self, Problem},
+};
+
+pub fn app() -> App {
+    let mut app = App::unloaded(); // none of the user's config or history
+    app.config = Config {
+        reduced_motion: true,
+        ..Config::default()
+    };
+    app.monochrome = false; // even with NO_COLOR set
+    app.greet();
+    app
+}
+
+pub fn manual_clock() -> ManualClock {
+    let clock = ManualClock::new();
+    clock::set(Rc::new(clock.clone()));
+    clock
+}
+
+pub fn press(app: &mut App, key: &str) {
+    let key: KeyBinding = key.parse().expect("a key name");
+    app.handle(Input::Key(key));
+}
+
+pub fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        app.handle(Input::Key(key.into()));
+    }
+}
+
+pub fn settle(app: &mut App) {
+    while app.spawner.active() > 0 {
+        app.drain_events();
+        thread::sleep(Duration::from_millis(1));
+    }
+    app.drain_events();
+}
+
+pub fn replay(app: &mut App, recording: &str) {
+    for (_, event) in recording::parse(recording).expect("a recording") {
+        if let Some(input @ Input::Key(_)) = Input::from_event(&event) {
+            app.handle(input);
+        }
+    }
+}
+
+// The screen a width × height terminal would show, trailing spaces trimmed
+pub fn screen(app: &App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("a test terminal");
+    terminal
+        .draw(|frame| render(frame, app))
+        .expect("drawing into memory");
+    text(terminal.backend().buffer())
+}
+
+// The cells' symbols in order, for finding where text is
+fn text_of(buffer: &Buffer) -> String {
+    buffer.content.iter().map(|cell| cell.symbol()).collect()
+}
+
+pub fn text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let row: String = (area.left()..area.right())
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        text += row.trim_end();
+        text.push('\n');
+    }
+    text
+}
+
+#[test]
+fn starts_with_the_greeting() {
+    let app = app();
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn typing_fills_the_input_bar() {
+    let mut app = app();
+    type_text(&mut app, "hello ada");
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn edits_in_the_middle_of_the_line() {
+    let mut app = app();
+    type_text(&mut app, "hello ada");
+    press(&mut app, "left");
+    press(&mut app, "left");
+    press(&mut app, "left");
+    type_text(&mut app, "dear ");
+    press(&mut app, "home");
+    press(&mut app, "delete");
+    type_text(&mut app, "j");
+    press(&mut app, "end");
+    press(&mut app, "backspace");
+    assert_eq!(app.input(), "jello dear ad");
+}
+
+#[test]
+fn the_cursor_follows_wide_characters() {
+    let mut app = app();
+    let cursor = |app: &App| {
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).expect("a test terminal");
+        terminal
+            .draw(|frame| render(frame, app))
+            .expect("drawing into memory");
+        let screen = text(terminal.backend().buffer());
+        (terminal.get_cursor_position().expect("a cursor"), screen)
+    };
+    type_text(&mut app, "字字字");
+    press(&mut app, "left");
+    assert_eq!(cursor(&app).0, (5, 10).into()); // the border, then two columns each
+    // Longer than the bar, it scrolls to keep the cursor in view
+    press(&mut app, "end");
+    type_text(&mut app, &"字".repeat(30));
+    let (at, screen) = cursor(&app);
+    assert_eq!(at, (37, 10).into());
+    let row = screen.lines().nth(10).expect("the input row");
+    assert_eq!(row, format!("│{}  │", "字 ".repeat(18))); // a wide cell's second half is " "
+}
+
+#[test]
+fn enter_runs_the_command() {
+    let mut app = app();
+    type_text(&mut app, "hello ada");
+    press(&mut app, "enter");
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn up_recalls_the_last_command() {
+    let mut app = app();
+    type_text(&mut app, "hello ada");
+    press(&mut app, "enter");
+    press(&mut app, "up");
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn replays_a_recording() {
+    let mut app = app();
+    replay(
+        &mut app,
+        r#"
+        {"ms":0,"event":{"Key":{"code":{"Char":"h"},"modifiers":"","kind":"Press","state":""}}}
+        {"ms":90,"event":{"Key":{"code":{"Char":"i"},"modifiers":"","kind":"Press","state":""}}}
+        {"ms":200,"event":{"Key":{"code":"Enter","modifiers":"","kind":"Press","state":""}}}
+        "#,
+    );
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn toasts_go_after_a_while() {
+    let clock = manual_clock();
+    let mut app = app();
+    type_text(&mut app, "toast warn disk nearly full");
+    press(&mut app, "enter");
+    let shown = screen(&app, 60, 12);
+    clock.advance(Duration::from_secs(2));
+    app.on_tick();
+    assert_eq!(screen(&app, 60, 12), shown, "still there after 2s");
+    clock.advance(Duration::from_secs(60));
+    app.on_tick();
+    insta::assert_snapshot!(shown);
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn a_screen_reader_gets_a_plain_transcript() {
+    let mut app = app();
+    app.config.screen_reader = true;
+    type_text(&mut app, "hello ada");
+    press(&mut app, "enter");
+    type_text(&mut app, "toast warn disk nearly full");
+    press(&mut app, "enter");
+    type_text(&mut app, "hi");
+    let mut terminal = Terminal::new(TestBackend::new(60, 12)).expect("a test terminal");
+    terminal
+        .draw(|frame| render(frame, &app))
+        .expect("drawing into memory");
+    let drawn = text(terminal.backend().buffer());
+    // The prompt, and the cursor, come right after the newest line
+    let prompt = drawn
+        .lines()
+        .position(|row| row == "> hi")
+        .expect("the prompt");
+    assert_eq!(
+        terminal.get_cursor_position().expect("a cursor"),
+        (4, prompt as u16).into()
+    );
+    insta::assert_snapshot!(drawn);
+
+    // Popups keep their text, without the box
+    press(&mut app, "f10");
+    let menu = screen(&app, 60, 12);
+    assert!(
+        !menu.contains(|c| ('\u{2500}'..='\u{25ff}').contains(&c)),
+        "{menu}"
+    );
+}
+
+#[test]
+fn long_jobs_end_with_a_highlighted_line() {
+    let _clock = manual_clock(); // so `sleep 0` takes no time at all
+    for (after_seconds, said) in [(10, false), (0, true)] {
+        let mut app = app();
+        app.config.notifications.after_seconds = after_seconds;
+        type_text(&mut app, "sleep 0");
+        press(&mut app, "enter");
+        settle(&mut app);
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).expect("a test terminal");
+        terminal
+            .draw(|frame| render(frame, &app))
+            .expect("drawing into memory");
+        let buffer = terminal.backend().buffer();
+        let row = text(buffer)
+            .lines()
+            .position(|row| row.contains("Finished: sleep 0 (after 0s)"));
+        assert_eq!(row.is_some(), said, "after_seconds = {after_seconds}");
+        if let Some(row) = row {
+            let reversed = (0..60).any(|x| {
+                let cell = &buffer[(x, row as u16)];
+                cell.modifier.contains(Modifier::REVERSED)
+            });
+            assert!(reversed, "highlighted");
+        }
+    }
+    // Jobs without a gauge are timed too, and named after their command
+    let mut app = app();
+    app.config.notifications.after_seconds = 0;
+    let fake = Arc::new(Fake::default().with_file("notes.md", "hi"));
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "hex notes.md");
+    press(&mut app, "enter");
+    settle(&mut app);
+    let finished = "  Finished: hex notes.md (after 0s)";
+    assert!(app.messages.iter().any(|line| &*line == finished));
+    let took = |secs| notifications::summary("x", Duration::from_secs(secs));
+    assert_eq!(took(90), "Finished: x (after 1m 30s)");
+    assert_eq!(took(3720), "Finished: x (after 1h 02m)");
+}
+
+#[test]
+fn ticks_slow_down_when_idle() {
+    let mut app = app();
+    let rate = app.config.tick_rate();
+    assert_eq!(app.tick_rate(Duration::ZERO), rate);
+    assert_eq!(app.tick_rate(Duration::from_secs(10)), rate * 4);
+    app.toasts.push(Level::Info, "hi".into());
+    assert_eq!(app.tick_rate(Duration::from_secs(10)), rate);
+}
+
+#[test]
+fn f10_opens_the_menu() {
+    let mut app = app();
+    press(&mut app, "f10");
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn f12_shows_the_debug_overlay() {
+    let mut app = app();
+    press(&mut app, "f12");
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn escapes_for_the_terminal_pass_through_multiplexers() {
+    use crate::multiplexer::Multiplexer::{self, Screen, Tmux};
+    assert_eq!(
+        Multiplexer::from_env(true, false, "screen-256color"),
+        Some(Tmux)
+    );
+    assert_eq!(
+        Multiplexer::from_env(false, false, "tmux-256color"),
+        Some(Tmux)
+    ); // over SSH
+    assert_eq!(Multiplexer::from_env(false, true, "screen"), Some(Screen));
+    assert_eq!(Multiplexer::from_env(false, false, "xterm-256color"), None);
+    assert_eq!(
+        Tmux.passthrough("\x1b]9;hi\x07"),
+        "\x1bPtmux;\x1b\x1b]9;hi\x07\x1b\\"
+    );
+    let long = format!("\x1b]52;c;{}\x07", "A".repeat(100));
+    let wrapped = Screen.passthrough(&long);
+    assert_eq!(wrapped.matches("\x1bP").count(), 2); // two pieces
+    assert_eq!(wrapped.replace("\x1bP", "").replace("\x1b\\", ""), long);
+}
+
+#[test]
+fn kitty_keys_have_bindings_of_their_own() {
+    let mut app = app();
+    app.config.keymap = toml::from_str(
+        r#"
+        "shift-enter" = "hello shift"
+        "release-f5" = "hello release"
+        "#,
+    )
+    .expect("a keymap");
+    let said = |app: &App, text: &str| app.messages.iter().any(|line| &*line == text);
+    type_text(&mut app, "typed");
+    press(&mut app, "shift-enter");
+    assert_eq!(app.input(), "typed"); // not submitted
+    assert!(said(&app, "  Hello, shift!"));
+    press(&mut app, "f5");
+    assert!(!said(&app, "  Hello, release!"));
+    press(&mut app, "release-f5");
+    assert!(said(&app, "  Hello, release!"));
+    press(&mut app, "release-x"); // unbound, so neither typed nor anything else
+    assert_eq!(app.input(), "typed");
+}
+
+// One mistake of each kind, each on a line of its own
+const CONFIG_WITH_PROBLEMS: &str = r#"history_size = 50
+histroy_size = 10
+
+[theme]
+input_border = "oragne"
+status_bg = "blue"
+
+[keymap]
+ctlr-l = "clear"
+ctrl-k = "clear-input"
+"#;
+
+fn problem(key: &str) -> Problem {
+    let (_, problems) = validate::check(CONFIG_WITH_PROBLEMS).expect("valid TOML");
+    assert_eq!(problems.len(), 3, "{problems:?}");
+    let found = problems.into_iter().find(|p| p.key == key);
+    found.unwrap_or_else(|| panic!("no problem with {key}"))
+}
+
+#[test]
+fn an_unknown_setting_is_reported_and_the_rest_apply() {
+    let problem = problem("histroy_size");
+    assert_eq!(problem.line, Some(2));
+    assert!(problem.message.starts_with("unknown field `histroy_size`"));
+    let (config, _) = validate::check(CONFIG_WITH_PROBLEMS).unwrap();
+    assert_eq!(config.history_size, 50);
+}
+
+#[test]
+fn a_bad_color_falls_back_alone() {
+    let problem = problem("theme.input_border");
+    assert_eq!(problem.line, Some(5));
+    assert!(problem.message.contains("'oragne' is not a color"));
+    let (config, _) = validate::check(CONFIG_WITH_PROBLEMS).unwrap();
+    assert_eq!(config.theme.input_border, Theme::default().input_border);
+    assert_eq!(config.theme.status_bg, Color::Blue);
+}
+
+#[test]
+fn a_bad_key_name_falls_back_alone() {
+    let problem = problem("keymap.ctlr-l");
+    assert_eq!(problem.line, Some(9));
+    assert!(problem.message.contains("unknown modifier 'ctlr'"));
+    let (config, _) = validate::check(CONFIG_WITH_PROBLEMS).unwrap();
+    let action = |keymap: &Keymap, key: &str| {
+        let key: KeyBinding = key.parse().expect("a key name");
+        keymap.get_binding(key).map(ToString::to_string)
+    };
+    assert_eq!(
+        action(&config.keymap, "ctrl-k").as_deref(),
+        Some("clear-input")
+    );
+    let default = Keymap::default();
+    assert_eq!(action(&config.keymap, "ctrl-l"), action(&default, "ctrl-l"));
+}
+
+#[test]
+fn hex_mode_refuses_what_isnt_hex() {
+    assert_eq!(
+        net::parse_hex("de ad 0xbe EF"),
+        Ok(vec![0xde, 0xad, 0xbe, 0xef])
+    );
+    assert_eq!(net::parse_hex("aé1"), Err("'é' isn't hex".to_string()));
+    assert_eq!(net::parse_hex("éé"), Err("'é' isn't hex".to_string()));
+    assert_eq!(
+        net::parse_hex("abc"),
+        Err("an odd number of hex digits".to_string())
+    );
+}
+
+#[test]
+fn output_draws_escapes_and_tabs_clean() {
+    let mut app = app();
+    app.print("\x1b[31mred\x1b[0m\tdone");
+    assert!(screen(&app, 60, 12).contains("red    done"));
+}
+
+#[test]
+fn wraps_long_lines_and_scrolls_by_row() {
+    let mut app = app();
+    app.config.layout.wrap = true;
+    app.print(format!("  {}", "abcdefghij".repeat(10)));
+    app.print("  字".repeat(12));
+    for n in 1..=4 {
+        app.print(format!("  line {n}"));
+    }
+    screen(&app, 40, 12); // to learn Output's size
+    press(&mut app, "pageup");
+    insta::assert_snapshot!(screen(&app, 40, 12));
+}
+
+#[test]
+fn a_tiny_terminal_asks_for_room() {
+    insta::assert_snapshot!(screen(&app(), 30, 8));
+}
+
+#[test]
+fn resizing_keeps_the_line_scrolled_back_to() {
+    let mut app = app();
+    app.config.layout.wrap = true;
+    for n in 0..40 {
+        app.print(format!("  line {n} {}", "x".repeat(50)));
+    }
+    screen(&app, 80, 24);
+    press(&mut app, "pageup");
+    let top = |app: &App| app.shown().at_row(app.output_top.unwrap()).0;
+    let before = top(&app);
+    app.resized(Rect::new(0, 0, 40, 24)); // each line now wraps onto two rows
+    assert_eq!(top(&app), before);
+}
+
+#[test]
+fn themes_fit_the_terminals_colors() {
+    let theme = Theme {
+        input_border: Color::Rgb(0xff, 0x88, 0x00),
+        status_bg: Color::Indexed(236),
+        ..Theme::default()
+    };
+    let fit = |depth| {
+        let theme = theme.clone().for_depth(depth);
+        (theme.input_border, theme.status_bg, theme.output_border)
+    };
+    assert_eq!(
+        fit(Depth::TrueColor),
+        (
+            Color::Rgb(0xff, 0x88, 0x00),
+            Color::Indexed(236),
+            Color::DarkGray
+        )
+    );
+    assert_eq!(
+        fit(Depth::Palette),
+        (Color::Indexed(208), Color::Indexed(236), Color::DarkGray)
+    );
+    assert_eq!(
+        fit(Depth::Basic),
+        (Color::Yellow, Color::Black, Color::DarkGray)
+    );
+}
+
+#[test]
+fn no_color_draws_in_bold_and_reverse() {
+    let mut app = app();
+    app.monochrome = true;
+    app.config.layout = toml::from_str(
+        r#"
+        panels = [
+            { kind = "output", size = "fill" },
+            { kind = "status", size = { length = 1 } },
+            { kind = "input", size = { length = 3 } },
+        ]
+        "#,
+    )
+    .expect("a layout");
+    let mut terminal = Terminal::new(TestBackend::new(60, 12)).expect("a test terminal");
+    terminal
+        .draw(|frame| render(frame, &app))
+        .expect("drawing into memory");
+    let buffer = terminal.backend().buffer();
+    let cells = &buffer.content;
+    assert!(
+        cells
+            .iter()
+            .all(|c| c.fg == Color::Reset && c.bg == Color::Reset)
+    );
+    let at = |text: &str| {
+        let screen = text_of(buffer);
+        let i = screen.find(text).expect("on screen");
+        &cells[screen[..i].chars().count()]
+    };
+    assert!(at("messages").modifier.contains(Modifier::REVERSED)); // the status bar
+    assert!(at("Command").modifier.contains(Modifier::BOLD)); // the cyan input border
+    assert!(at("Output").modifier.is_empty()); // gray
+}
+
+#[test]
+fn old_output_is_compressed_and_read_back() {
+    let mut app = app();
+    let before = app.messages.len();
+    for n in 0..25_000 {
+        app.print(format!("  line {n}"));
+    }
+    assert_eq!(&*app.messages.get(before), "  line 0");
+    assert_eq!(&*app.messages.get(before + 12_345), "  line 12345");
+    assert_eq!(app.messages.iter().count(), before + 25_000);
+    type_text(&mut app, "filter ^  line 42$");
+    press(&mut app, "enter");
+    insta::assert_snapshot!(screen(&app, 60, 12));
+}
+
+#[test]
+fn every_panel() {
+    let mut app = app();
+    app.config.layout = toml::from_str(
+        r#"
+        panels = [
+            { kind = "title", size = { length = 1 } },
+            { kind = "menu", size = { length = 1 } },
+            { kind = "output", size = "fill" },
+            { kind = "status", size = { length = 1 } },
+            { kind = "input", size = { length = 3 } },
+            { kind = "hints", size = { length = 1 } },
+        ]
+        side_panel = { position = "right", width = { percent = 30 } }
+        "#,
+    )
+    .expect("a layout");
+    insta::assert_snapshot!(screen(&app, 80, 16));
+}
+
+#[test]
+fn narrow_terminals_stack_and_drop_panels() {
+    let mut app = app();
+    app.config.layout = toml::from_str(
+        r#"
+        panels = [
+            { kind = "title", size = { length = 1 } },
+            { kind = "output", size = "fill" },
+            { kind = "status", size = { length = 1 } },
+            { kind = "input", size = { length = 3 } },
+        ]
+        side_panel = { position = "right", width = { percent = 30 } }
+        "#,
+    )
+    .expect("a layout");
+    // The side panel under Output, then gone with the bars
+    insta::assert_snapshot!(screen(&app, 70, 16));
+    insta::assert_snapshot!(screen(&app, 45, 16));
+}
+
+#[cfg(unix)]
+#[test]
+fn shell_commands_run_through_effects() {
+    let mut app = app();
+    let fake = Arc::new(Fake::default().printing(&["Cargo.toml", "src"]).exiting(1));
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "!ls -a");
+    press(&mut app, "enter");
+    settle(&mut app);
+    assert_eq!(fake.calls(), ["run sh -c ls -a"]);
+    assert!(app.messages.iter().any(|line| &*line == "  $ ls -a"));
+    assert!(app.messages.iter().any(|line| &*line == "  Cargo.toml"));
+    assert!(
+        app.messages
+            .iter()
+            .any(|line| line.starts_with("  ✗ exit code 1"))
+    );
+}
+
+#[test]
+fn the_editor_reads_and_saves_through_effects() {
+    let mut app = app();
+    let fake = Arc::new(Fake::default().with_file("notes.md", "hello\n"));
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "edit notes.md");
+    press(&mut app, "enter");
+    type_text(&mut app, "oh, ");
+    press(&mut app, "ctrl-s");
+    assert_eq!(fake.calls(), ["read notes.md", "write notes.md"]);
+    assert_eq!(fake.file("notes.md").as_deref(), Some("oh, hello\n"));
+}
+
+#[test]
+fn json_reads_through_effects() {
+    let mut app = app();
+    let fake = Arc::new(Fake::default().with_file("a.json", r#"{"name": "ada"}"#));
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "json a.json");
+    press(&mut app, "enter");
+    press(&mut app, "esc"); // out of its pane, back to the input bar
+    type_text(&mut app, "json missing.json");
+    press(&mut app, "enter");
+    assert_eq!(fake.calls(), ["read a.json", "read missing.json"]);
+    let failed = |path: &str| {
+        let start = format!("  Can't read {path}");
+        app.messages.iter().any(|line| line.starts_with(&start))
+    };
+    assert!(!failed("a.json") && failed("missing.json"));
+}
+
+#[test]
+fn tree_and_hex_read_through_effects() {
+    let mut app = app();
+    let fake = Arc::new(
+        Fake::default()
+            .with_file("src/main.rs", "fn main() {}")
+            .with_file("notes.md", "hi"),
+    );
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "tree .");
+    press(&mut app, "enter");
+    settle(&mut app);
+    let title = app.pane.as_ref().map(|pane| pane.title());
+    assert_eq!(title.as_deref(), Some("Tree: ."));
+    press(&mut app, "esc");
+    type_text(&mut app, "hex notes.md");
+    press(&mut app, "enter");
+    settle(&mut app);
+    assert_eq!(fake.calls(), ["list .", "list .", "read notes.md"]);
+    let title = app.pane.as_ref().map(|pane| pane.title());
+    assert_eq!(title.as_deref(), Some("Hex: notes.md"));
+}
+
+#[test]
+fn export_and_import_go_through_effects() {
+    let mut app = app();
+    let fake = Arc::new(Fake::default());
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "set who ada");
+    press(&mut app, "enter");
+    type_text(&mut app, "export state.json");
+    press(&mut app, "enter");
+    let exported = fake.file("state.json").expect("an export");
+    assert!(exported.contains(r#""who": "ada""#), "{exported}");
+    app.vars.clear();
+    type_text(&mut app, "import state.json");
+    press(&mut app, "enter");
+    assert_eq!(fake.calls(), ["write state.json", "read state.json"]);
+    assert_eq!(app.vars.get("who").map(String::as_str), Some("ada"));
+    assert!(!app.messages.iter().any(|line| line.contains("problem")));
+}
+
+#[test]
+fn imported_settings_are_checked() {
+    let mut app = app();
+    let export = r#"{"format": 1, "app": "x", "exported_at": 0, "settings": {
+        "tick_rate_ms": 0, "history_size": 50, "startup": ["!rm -rf ~"]
+    }}"#;
+    let fake = Arc::new(Fake::default().with_file("state.json", export));
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "import state.json");
+    press(&mut app, "enter");
+    assert_eq!(app.config.tick_rate_ms, Config::default().tick_rate_ms);
+    assert_eq!(app.config.history_size, 50);
+    assert!(app.config.startup.is_empty());
+    let said = |text: &str| app.messages.iter().any(|line| &*line == text);
+    assert!(said("    tick_rate_ms: must be at least 1"));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn http_requests_go_through_effects() {
+    let mut app = app();
+    let fake = Arc::new(Fake::default().responding("pong\n"));
+    app.effects = Effects::fake(&fake);
+    type_text(&mut app, "http post https://example.com/ping {}");
+    press(&mut app, "enter");
+    settle(&mut app);
+    assert_eq!(fake.calls(), ["POST https://example.com/ping"]);
+    assert!(
+        app.messages
+            .iter()
+            .any(|line| &*line == "  HTTP/1.1 200 OK")
+    );
+    assert!(app.messages.iter().any(|line| &*line == "  pong"));
+}
@@ -0,0 +1,368 @@
+// =============================================================================
+// Theme — the colors render() uses
+//
+// Colors are written as names ("cyan", "darkgray"), 256-color indexes ("208"),
+// or hex RGB ("#ff8800"). A theme is either inline in the config:
+//
+//   [theme]
+//   input_border = "#ff8800"
+//   status_bg = "236"
+//
+// or the name of a file in <config dir>/themes/ (same keys, no [theme] header):
+//
+//   theme = "dracula"            # loads ~/.config/<app>/themes/dracula.toml
+//
+// Theme files are TOML only; there's no YAML support. `theme list` shows
+// the available files and `theme <name>` switches live.
+// `theme set <key>` picks a new color for one key of the current theme.
+//
+// Hex colors need a truecolor terminal, and indexes above 15 one with 256
+// colors. Elsewhere they're mapped to the nearest color the terminal has —
+// an entry of the 256-color palette, or one of the 16 basic colors — so
+// themes still look roughly right instead of wrong. What the terminal has
+// is worked out once, from $COLORTERM and then terminfo's max_colors for
+// $TERM (see Depth::detect).
+//
+// With NO_COLOR set (https://no-color.org) or --no-color there are no colors
+// at all: render() turns each frame monochrome, with reverse video where
+// there was a background and bold for colored text.
+// =============================================================================
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::{
+        OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, WrapErr, bail},
+};
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    effects::{Files, System},
+    paths,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    #[serde(deserialize_with = "color")]
+    pub output_border: Color, // also used for the side panel
+    #[serde(deserialize_with = "color")]
+    pub input_border: Color,
+    #[serde(deserialize_with = "color")]
+    pub input_text: Color,
+    #[serde(deserialize_with = "color")]
+    pub status_fg: Color,
+    #[serde(deserialize_with = "color")]
+    pub status_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            output_border: Color::DarkGray,
+            input_border: Color::Cyan,
+            input_text: Color::White,
+            status_fg: Color::Black,
+            status_bg: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    // Load <config dir>/themes/<name>.toml
+    pub fn load(files: &dyn Files, name: &str) -> Result<Self> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            bail!("'{name}' is not a theme name");
+        }
+        let path = themes_dir()?.join(format!("{name}.toml"));
+        let text = (files.read(&path)).wrap_err_with(|| format!("reading {}", path.display()))?;
+        let theme: Theme =
+            toml::from_str(&text).wrap_err_with(|| format!("parsing {}", path.display()))?;
+        Ok(theme.for_terminal())
+    }
+
+    // The theme's keys, for `theme set`
+    pub const KEYS: [&str; 5] = [
+        "output_border",
+        "input_border",
+        "input_text",
+        "status_fg",
+        "status_bg",
+    ];
+
+    pub fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        match key {
+            "output_border" => Some(&mut self.output_border),
+            "input_border" => Some(&mut self.input_border),
+            "input_text" => Some(&mut self.input_text),
+            "status_fg" => Some(&mut self.status_fg),
+            "status_bg" => Some(&mut self.status_bg),
+            _ => None,
+        }
+    }
+
+    // The same theme with every color representable on this terminal
+    pub fn for_terminal(self) -> Self {
+        self.for_depth(Depth::detect())
+    }
+
+    pub fn for_depth(self, depth: Depth) -> Self {
+        let down = |color| depth.fit(color);
+        Self {
+            output_border: down(self.output_border),
+            input_border: down(self.input_border),
+            input_text: down(self.input_text),
+            status_fg: down(self.status_fg),
+            status_bg: down(self.status_bg),
+        }
+    }
+}
+
+// Theme names available to `theme <name>`, sorted
+pub fn list(files: &dyn Files) -> Result<Vec<String>> {
+    let dir = themes_dir()?;
+    if files.metadata(&dir).is_err() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    for path in (files.read_dir(&dir)).wrap_err_with(|| format!("reading {}", dir.display()))? {
+        if path.extension().is_some_and(|ext| ext == "toml")
+            && let Some(name) = path.file_stem().and_then(|s| s.to_str())
+        {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+pub fn themes_dir() -> Result<PathBuf> {
+    Ok(paths::config_dir()
+        .ok_or_eyre("can't find a config directory (is $HOME set?)")?
+        .join("themes"))
+}
+
+// For the config's `theme` key: a table is an inline theme, a string names a
+// theme file. Either way the result is adjusted to the terminal. The config
+// file itself comes straight from disk, so a theme it names does too.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Theme, D::Error> {
+    use serde::de::Error;
+
+    match toml::Value::deserialize(deserializer)? {
+        toml::Value::String(name) => {
+            Theme::load(&System, &name).map_err(|err| D::Error::custom(format!("{err:#}")))
+        }
+        table => Theme::deserialize(table)
+            .map(Theme::for_terminal)
+            .map_err(D::Error::custom),
+    }
+}
+
+// Like Color's own Deserialize, but says what was wrong and what would work
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    use serde::de::Error;
+
+    let text = String::deserialize(deserializer)?;
+    text.parse().map_err(|_| {
+        D::Error::custom(format!(
+            "'{text}' is not a color (try a name like \"cyan\", an index like \"208\" or hex like \"#ff8800\")"
+        ))
+    })
+}
+
+// =============================================================================
+// Color depth — what the terminal can show, and fitting colors to it
+// =============================================================================
+
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+// For --no-color, before anything draws
+pub fn set_no_color() {
+    NO_COLOR.store(true, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Depth {
+    Mono,      // bold and reverse only
+    Basic,     // the 16 named colors
+    Palette,   // the xterm 256-color palette
+    TrueColor, // any RGB
+}
+
+impl Depth {
+    // Terminals that can show 24-bit color advertise it in $COLORTERM; for
+    // the rest, terminfo's entry for $TERM says how many colors. With no
+    // entry to go by, 256, which nearly every terminal emulator has.
+    pub fn detect() -> Self {
+        static DEPTH: OnceLock<Depth> = OnceLock::new();
+        if NO_COLOR.load(Ordering::Relaxed) {
+            return Self::Mono;
+        }
+        *DEPTH.get_or_init(|| {
+            if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+                return Self::Mono;
+            }
+            if env::var("COLORTERM").is_ok_and(|v| v == "truecolor" || v == "24bit") {
+                return Self::TrueColor;
+            }
+            let term = env::var("TERM").unwrap_or_default();
+            match max_colors(&term) {
+                Some(n) if n >= 1 << 24 => Self::TrueColor, // the *-direct entries
+                Some(n) if n >= 256 => Self::Palette,
+                Some(_) => Self::Basic,
+                None if term.contains("256color") => Self::Palette,
+                None if term == "linux" || term.starts_with("vt") => Self::Basic,
+                None => Self::Palette,
+            }
+        })
+    }
+
+    // The nearest color this depth can show; Mono's are dropped as each
+    // frame is drawn (see monochrome() in lib.rs)
+    pub fn fit(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::TrueColor | Self::Mono, _) => color,
+            (Self::Palette, Color::Rgb(..)) => to_256(color),
+            (Self::Basic, Color::Rgb(r, g, b)) => to_16((r, g, b)),
+            (Self::Basic, Color::Indexed(i)) => to_16(palette_rgb(i)),
+            _ => color,
+        }
+    }
+}
+
+// terminfo's max_colors for `term`, from its compiled entry (see term(5))
+fn max_colors(term: &str) -> Option<i32> {
+    const MAX_COLORS: usize = 13; // its place among the numbers
+    let first = term.chars().next()?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(dir.into());
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(Path::new(&home).join(".terminfo"));
+    }
+    if let Some(list) = env::var_os("TERMINFO_DIRS") {
+        dirs.extend(env::split_paths(&list));
+    }
+    dirs.extend(
+        [
+            "/etc/terminfo",
+            "/lib/terminfo",
+            "/usr/share/terminfo",
+            "/usr/lib/terminfo",
+        ]
+        .map(PathBuf::from),
+    );
+    let entry = dirs.iter().find_map(|dir| {
+        // By first letter on Linux, by its hex code on macOS
+        [first.to_string(), format!("{:x}", first as u32)]
+            .iter()
+            .find_map(|sub| fs::read(dir.join(sub).join(term)).ok())
+    })?;
+
+    let short = |at: usize| Some(i16::from_le_bytes(entry.get(at..at + 2)?.try_into().ok()?));
+    let wide = match short(0)? {
+        0o432 => false,
+        0o1036 => true, // 32-bit numbers
+        _ => return None,
+    };
+    let (names, bools, numbers) = (short(2)?, short(4)?, short(6)?);
+    if numbers <= MAX_COLORS as i16 {
+        return None;
+    }
+    let start = 12 + names as usize + bools as usize;
+    let start = start + start % 2; // numbers start on an even byte
+    let n = match wide {
+        true => {
+            let at = start + 4 * MAX_COLORS;
+            i32::from_le_bytes(entry.get(at..at + 4)?.try_into().ok()?)
+        }
+        false => short(start + 2 * MAX_COLORS)?.into(),
+    };
+    (n > 0).then_some(n)
+}
+
+// Nearest xterm-256 palette entry: either a cell of the 6×6×6 color cube
+// (16–231) or a step on the grayscale ramp (232–255), whichever is closer
+fn to_256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest_level = |c: u8| (0..6).min_by_key(|&i| LEVELS[i].abs_diff(c)).unwrap_or(0);
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let avg = (r as usize + g as usize + b as usize) / 3;
+    let gray_step = (avg.saturating_sub(8) / 10).min(23);
+    let gray = (8 + 10 * gray_step) as u8;
+    let gray_index = 232 + gray_step;
+
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(cr, r) + d(cg, g) + d(cb, b)
+    };
+    if distance((gray, gray, gray)) < distance(cube) {
+        Color::Indexed(gray_index as u8)
+    } else {
+        Color::Indexed(cube_index as u8)
+    }
+}
+
+// The basic colors as xterm shows them, in index order
+const BASIC: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+// What a 256-color palette entry looks like
+fn palette_rgb(i: u8) -> (u8, u8, u8) {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match i {
+        0..16 => BASIC[i as usize].1,
+        16..232 => {
+            let i = (i - 16) as usize;
+            (LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6])
+        }
+        _ => {
+            let gray = 8 + 10 * (i - 232);
+            (gray, gray, gray)
+        }
+    }
+}
+
+// Nearest basic color
+fn to_16((r, g, b): (u8, u8, u8)) -> Color {
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(cr, r) + d(cg, g) + d(cb, b)
+    };
+    BASIC
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map_or(Color::Reset, |&(color, _)| color)
+}
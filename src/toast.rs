@@ -0,0 +1,138 @@
+// =============================================================================
+// Toasts — short-lived notices in the top-right corner
+//
+//   ctx.toast(Level::Info, "Config reloaded");
+//   let _ = tx.send(AppEvent::Toast(Level::Error, "Upload failed".into()));
+//
+// For things worth seeing but not worth keeping: they stack newest on top
+// and each disappears on its own after its level's timeout (see
+// Level::timeout), checked every tick. Anything the user may need to scroll
+// back to belongs in the Output panel instead. Headless modes print toasts
+// as ordinary lines. New toasts slide in from the right (animation.rs).
+// =============================================================================
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    animation::{self, Reveal},
+    clock,
+    theme::Theme,
+};
+
+const MAX_SHOWN: usize = 5; // older ones are dropped early
+const WIDTH: u16 = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    // Errors stay up longest: they're the ones most likely to need reading
+    fn timeout(self) -> Duration {
+        match self {
+            Level::Info => Duration::from_secs(3),
+            Level::Warn => Duration::from_secs(5),
+            Level::Error => Duration::from_secs(8),
+        }
+    }
+
+    fn color(self, theme: &Theme) -> Color {
+        match self {
+            Level::Info => theme.input_border,
+            Level::Warn => Color::Yellow,
+            Level::Error => Color::Red,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Level::Info => "info",
+            Level::Warn => "warning",
+            Level::Error => "error",
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Toasts {
+    shown: VecDeque<(Level, String, Instant)>, // newest first, with when it expires
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: Level, text: String) {
+        self.shown
+            .push_front((level, text, clock::now() + level.timeout()));
+        self.shown.truncate(MAX_SHOWN);
+    }
+
+    // Drop expired toasts; true if any went
+    pub fn expire(&mut self) -> bool {
+        let before = self.shown.len();
+        let now = clock::now();
+        self.shown.retain(|(_, _, until)| *until > now);
+        self.shown.len() != before
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty()
+    }
+
+    // True while a new toast is still sliding in
+    pub fn animating(&self) -> bool {
+        self.shown
+            .iter()
+            .any(|(level, _, until)| animation::running(*until - level.timeout()))
+    }
+
+    // Stacked down from the top-right corner of `area`, as many as fit
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme, animate: bool) {
+        let width = WIDTH.min(area.width);
+        let inner = width.saturating_sub(2).max(1) as usize;
+        let mut y = area.y;
+        for (level, text, until) in &self.shown {
+            // Enough rows for the wrapped text, plus the border
+            let lines: usize = text
+                .lines()
+                .map(|l| l.chars().count().div_ceil(inner).max(1))
+                .sum();
+            let height = lines as u16 + 2;
+            if y + height > area.bottom() {
+                break;
+            }
+            let rect = Rect::new(area.right() - width, y, width, height);
+            let color = level.color(theme);
+            let toast = Paragraph::new(text.as_str())
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(" {level} "))
+                        .border_style(Style::default().fg(color)),
+                );
+            let shown = *until - level.timeout();
+            let before = (animate && animation::running(shown)).then(|| frame.buffer_mut().clone());
+            frame.render_widget(Clear, rect);
+            frame.render_widget(toast, rect);
+            if let Some(before) = before {
+                animation::reveal(frame, &before, animation::progress(shown), Reveal::Left);
+            }
+            y += height;
+        }
+    }
+}
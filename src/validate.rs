@@ -0,0 +1,227 @@
+// =============================================================================
+// Config validation — find every problem in a config file, not just the first
+//
+// A plain `toml::from_str::<Config>` stops at the first mistake and throws
+// the whole file away. Instead, each setting is checked on its own (and each
+// entry of [session], [theme], [keymap], [layout], [notifications],
+// [alerts] and [cursor] inside that), so:
+//
+//   - a typo'd key, a bad key name or an invalid color is reported with the
+//     line it's on, and
+//   - only that one setting falls back to its default; the rest still apply.
+//
+// The report looks like:
+//
+//   3 problems in ~/.config/<app>/config.toml (using defaults for these):
+//     line 2: histroy_size: unknown field `histroy_size`, expected one of …
+//     line 7: keymap.ctlr-l: unknown modifier 'ctlr' in key 'ctlr-l'
+//     line 9: theme.input_border: 'oragne' is not a color (try a name …
+//
+// TOML syntax errors are still fatal — there's nothing sensible to recover —
+// but toml's own message already points at the line and column.
+//
+// An import's settings (see session.rs) get the same checks through
+// check_json(), without line numbers.
+// =============================================================================
+
+use std::{collections::HashMap, fmt};
+
+use color_eyre::{Result, eyre::WrapErr};
+use serde::{Deserialize, de::DeserializeOwned};
+use toml::{Table, Value, de::DeTable};
+
+use crate::{
+    alerts::AlertConfig,
+    config::{Config, SessionConfig},
+    cursor::CursorConfig,
+    keymap::Keymap,
+    layout::LayoutConfig,
+    notifications::NotificationConfig,
+    theme::Theme,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: Option<usize>, // 1-based; None if the key couldn't be located
+    pub key: String,         // dotted path, e.g. "theme.input_border"
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+// Parse `text`, keeping every setting that's valid and reporting the rest
+pub fn check(text: &str) -> Result<(Config, Vec<Problem>)> {
+    let table: Table = toml::from_str(text)?;
+    check_table(table, &KeyLines::new(text))
+}
+
+// The same for settings written as JSON
+pub fn check_json(settings: serde_json::Value) -> Result<(Config, Vec<Problem>)> {
+    let table = match toml_of(settings) {
+        Some(Value::Table(table)) => table,
+        _ => Table::new(), // not an object: nothing to keep
+    };
+    check_table(table, &KeyLines::default())
+}
+
+fn check_table(table: Table, lines: &KeyLines) -> Result<(Config, Vec<Problem>)> {
+    let mut problems = Vec::new();
+
+    let mut kept = Table::new();
+    for (key, value) in table {
+        // Sections are checked entry by entry first, so one bad entry
+        // doesn't take the rest of the section down with it
+        let value = match (key.as_str(), value) {
+            ("session", Value::Table(t)) => {
+                Value::Table(fields::<SessionConfig>(&key, t, lines, &mut problems))
+            }
+            ("theme", Value::Table(t)) => {
+                Value::Table(fields::<Theme>(&key, t, lines, &mut problems))
+            }
+            ("keymap", Value::Table(t)) => {
+                Value::Table(fields::<Keymap>(&key, t, lines, &mut problems))
+            }
+            ("layout", Value::Table(t)) => {
+                Value::Table(fields::<LayoutConfig>(&key, t, lines, &mut problems))
+            }
+            ("notifications", Value::Table(t)) => {
+                Value::Table(fields::<NotificationConfig>(&key, t, lines, &mut problems))
+            }
+            ("alerts", Value::Table(t)) => {
+                Value::Table(fields::<AlertConfig>(&key, t, lines, &mut problems))
+            }
+            ("cursor", Value::Table(t)) => {
+                Value::Table(fields::<CursorConfig>(&key, t, lines, &mut problems))
+            }
+            (_, value) => value,
+        };
+        if let Some(value) = field::<Config>(&[&key], &key, value, lines, &mut problems) {
+            kept.insert(key, value);
+        }
+    }
+
+    let mut config =
+        Config::deserialize(Value::Table(kept)).wrap_err("applying the valid settings")?;
+    if config.tick_rate_ms == 0 {
+        problems.push(Problem {
+            line: lines.find(&["tick_rate_ms"]),
+            key: "tick_rate_ms".into(),
+            message: "must be at least 1".into(),
+        });
+        config.tick_rate_ms = Config::default().tick_rate_ms;
+    }
+    problems.sort_by_key(|p| p.line);
+    Ok((config, problems))
+}
+
+// Keep the entries of `section` that `T` accepts on their own
+fn fields<T: DeserializeOwned>(
+    section: &str,
+    table: Table,
+    lines: &KeyLines,
+    problems: &mut Vec<Problem>,
+) -> Table {
+    table
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let path = format!("{section}.{key}");
+            field::<T>(&[section, &key], &path, value, lines, problems).map(|v| (key, v))
+        })
+        .collect()
+}
+
+// Deserialize a table holding only `path`'s last key into `T`. Every config
+// type uses #[serde(default)], so this checks just that one setting (and
+// deny_unknown_fields catches typos).
+fn field<T: DeserializeOwned>(
+    path: &[&str],
+    dotted: &str,
+    value: Value,
+    lines: &KeyLines,
+    problems: &mut Vec<Problem>,
+) -> Option<Value> {
+    let key = path.last()?.to_string();
+    let single = Value::Table(Table::from_iter([(key, value)]));
+    match T::deserialize(single.clone()) {
+        Ok(_) => match single {
+            Value::Table(t) => t.into_iter().next().map(|(_, v)| v),
+            _ => None,
+        },
+        Err(err) => {
+            problems.push(Problem {
+                line: lines.find(path),
+                key: dotted.to_string(),
+                message: err.message().trim_end().to_string(),
+            });
+            None
+        }
+    }
+}
+
+// TOML has no null: a JSON null is left out, as if it weren't set
+fn toml_of(json: serde_json::Value) -> Option<Value> {
+    use serde_json::Value as Json;
+
+    Some(match json {
+        Json::Null => return None,
+        Json::Bool(b) => Value::Boolean(b),
+        Json::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Float(n.as_f64()?),
+        },
+        Json::String(text) => Value::String(text),
+        Json::Array(items) => Value::Array(items.into_iter().filter_map(toml_of).collect()),
+        Json::Object(entries) => Value::Table(
+            entries
+                .into_iter()
+                .filter_map(|(key, value)| Some((key, toml_of(value)?)))
+                .collect(),
+        ),
+    })
+}
+
+// =============================================================================
+// Line numbers — toml::Table forgets where things were, so keep a separate
+// index of key path → line from the span-preserving parser
+// =============================================================================
+#[derive(Default)]
+struct KeyLines(HashMap<Vec<String>, usize>);
+
+impl KeyLines {
+    fn new(text: &str) -> Self {
+        let mut index = HashMap::new();
+        if let Ok(table) = DeTable::parse(text) {
+            let line_of = |offset: usize| text[..offset].matches('\n').count() + 1;
+            collect(table.get_ref(), &mut Vec::new(), &line_of, &mut index);
+        }
+        Self(index)
+    }
+
+    fn find(&self, path: &[&str]) -> Option<usize> {
+        let path: Vec<String> = path.iter().map(|s| s.to_string()).collect();
+        self.0.get(&path).copied()
+    }
+}
+
+fn collect(
+    table: &DeTable,
+    prefix: &mut Vec<String>,
+    line_of: &dyn Fn(usize) -> usize,
+    index: &mut HashMap<Vec<String>, usize>,
+) {
+    for (key, value) in table {
+        prefix.push(key.get_ref().to_string());
+        index.insert(prefix.clone(), line_of(key.span().start));
+        if let Some(inner) = value.get_ref().as_table() {
+            collect(inner, prefix, line_of, index);
+        }
+        prefix.pop();
+    }
+}
@@ -0,0 +1,122 @@
+// =============================================================================
+// Watch — `watch <path>… -- <shell command>`: re-run a command on changes
+//
+//   > watch src/ Cargo.toml -- cargo check
+//
+// Each rule is a background job: it waits for files under the paths to
+// change, then runs the command (see shell.rs) and prints its output. A
+// burst of changes — a save that writes several files, a branch switch —
+// runs it once, DEBOUNCE after the last of them. Changes made while the
+// command runs count as a new burst. Changes under .git/ and target/ are
+// ignored, so watching a whole project doesn't retrigger on builds.
+//
+// Rules show up in `jobs`; `cancel <id>` stops one.
+// =============================================================================
+
+use std::{
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    time::{Duration, Instant},
+};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::{
+    ctx::{AppEvent, Ctx, Status},
+    shell,
+};
+
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const POLL: Duration = Duration::from_millis(250); // how often cancel is checked
+const IGNORED: [&str; 2] = [".git", "target"];
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let usage = "  Usage: watch <path>… -- <shell command>";
+    let Some((paths, command)) = args.split_once("--") else {
+        return ctx.fail(usage);
+    };
+    let paths: Vec<String> = paths.split_whitespace().map(String::from).collect();
+    let command = command.trim().to_string();
+    if paths.is_empty() || command.is_empty() {
+        return ctx.fail(usage);
+    }
+    if let Some(missing) = paths.iter().find(|p| !Path::new(p).exists()) {
+        return ctx.fail(format!(
+            "  Can't watch {missing}: no such file or directory"
+        ));
+    }
+
+    let (events, changes) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(events) {
+        Ok(watcher) => watcher,
+        Err(err) => return ctx.fail(format!("  Can't watch files: {err}")),
+    };
+    for path in &paths {
+        if let Err(err) = watcher.watch(Path::new(path), RecursiveMode::Recursive) {
+            return ctx.fail(format!("  Can't watch {path}: {err}"));
+        }
+    }
+    let label = format!("watch {}", paths.join(" "));
+    ctx.print(format!(
+        "  Watching {} — `{command}` runs on changes",
+        paths.join(", ")
+    ));
+    let processes = ctx.effects.processes.clone();
+    ctx.spawner.spawn_with_progress(label, move |tx, progress| {
+        let _watcher = watcher; // watching stops when it's dropped
+        progress.unknown();
+        let mut last_change: Option<(Instant, String)> = None; // not yet acted on
+        while !progress.cancelled() {
+            // Wake up in time to end a burst, or to notice `cancel`
+            let wait = last_change
+                .as_ref()
+                .map_or(POLL, |(at, _)| DEBOUNCE.saturating_sub(at.elapsed()))
+                .min(POLL);
+            match changes.recv_timeout(wait) {
+                Ok(Ok(event)) => {
+                    if let Some(path) = changed_path(&event) {
+                        last_change = Some((Instant::now(), path));
+                    }
+                }
+                Ok(Err(err)) => print(&tx, format!("  Watch error: {err}")),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if let Some((at, path)) = &last_change
+                && at.elapsed() >= DEBOUNCE
+            {
+                print(&tx, format!("  {path} changed"));
+                last_change = None;
+                let _ = shell::run(&*processes, &tx, &command); // failures are printed by run()
+            }
+        }
+        print(&tx, format!("  Stopped watching for `{command}`"));
+    });
+    Status::Success
+}
+
+fn print(tx: &Sender<AppEvent>, line: String) {
+    let _ = tx.send(AppEvent::Print(line));
+}
+
+// The file an event is about, unless it's one to ignore
+fn changed_path(event: &Event) -> Option<String> {
+    if matches!(event.kind, EventKind::Access(_) | EventKind::Other) {
+        return None; // reads don't count
+    }
+    event
+        .paths
+        .iter()
+        .find(|path| {
+            !path
+                .components()
+                .any(|c| IGNORED.iter().any(|ignored| c.as_os_str() == *ignored))
+        })
+        .map(|path| {
+            let cwd = std::env::current_dir().unwrap_or_default();
+            path.strip_prefix(&cwd)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+}
@@ -0,0 +1,309 @@
+// =============================================================================
+// WebSocket — `ws connect <url>` (the `websocket` feature)
+//
+//   cargo run --features websocket
+//   > ws connect wss://echo.websocket.org
+//
+// Frames are shown live in a pane: ← what came in, → what went out, and ·
+// for the connection's own news. While the pane is open, whatever is typed
+// into the input bar is sent as a text frame (`/close` still closes it).
+//
+// A background job owns the socket. When the connection drops it tries
+// again after 1s, then 2s, 4s… up to MAX_BACKOFF; lines typed meanwhile are
+// sent once it's back. Closing the pane stops the job.
+//
+//   ↑/↓ PgUp/PgDn Home/End   scroll back (End follows new frames again)
+// =============================================================================
+
+use std::{
+    cell::Cell,
+    io::ErrorKind,
+    net::TcpStream,
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver, Sender, TryRecvError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use tungstenite::{Error, Message, WebSocket, stream::MaybeTlsStream};
+
+use crate::{
+    ctx::{Ctx, Status},
+    hints::Hints,
+    pane::Pane,
+    theme::Theme,
+};
+
+const POLL: Duration = Duration::from_millis(50); // how often the job checks for lines to send
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_FRAMES: usize = 5_000; // older ones are dropped
+
+pub fn command(ctx: &mut Ctx, args: &str) -> Status {
+    let Some(url) = args.strip_prefix("connect ").map(str::trim) else {
+        return ctx.fail("  Usage: ws connect <ws://… or wss://…>");
+    };
+    if !url.starts_with("ws://") && !url.starts_with("wss://") {
+        return ctx.fail(format!("  Not a ws(s) URL: {url}"));
+    }
+    let (view, outgoing) = WsView::new(url);
+    let log = view.log.clone();
+    let url = url.to_string();
+    ctx.show(view);
+    ctx.spawner
+        .spawn_watcher(move |_| run(&url, &log, &outgoing));
+    Status::Success
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    In,
+    Out,
+    Note, // connected, dropped, retrying…
+}
+
+#[derive(Default)]
+struct Log {
+    frames: Vec<(Instant, Direction, String)>,
+    connected: bool,
+    changed: bool, // since the last render
+    closed: bool,  // the pane is gone
+}
+
+impl Log {
+    fn push(&mut self, direction: Direction, text: impl Into<String>) {
+        self.frames.push((Instant::now(), direction, text.into()));
+        let excess = self.frames.len().saturating_sub(MAX_FRAMES);
+        self.frames.drain(..excess);
+        self.changed = true;
+    }
+}
+
+type Shared = Arc<Mutex<Log>>;
+
+fn note(log: &Shared, f: impl FnOnce(&mut Log)) -> bool {
+    match log.lock() {
+        Ok(mut log) => {
+            f(&mut log);
+            !log.closed
+        }
+        Err(_) => false,
+    }
+}
+
+// Connect, pump frames both ways, and reconnect until the pane closes
+fn run(url: &str, log: &Shared, outgoing: &Receiver<String>) {
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        let why = match tungstenite::connect(url) {
+            Ok((mut socket, _)) => {
+                backoff = Duration::from_secs(1);
+                if !note(log, |l| {
+                    l.connected = true;
+                    l.push(Direction::Note, "connected");
+                }) {
+                    return;
+                }
+                match pump(&mut socket, log, outgoing) {
+                    Some(why) => why,
+                    None => {
+                        let _ = socket.close(None);
+                        return;
+                    }
+                }
+            }
+            Err(err) => err.to_string(),
+        };
+        let retry = format!("{why}; trying again in {}s", backoff.as_secs());
+        if !note(log, |l| {
+            l.connected = false;
+            l.push(Direction::Note, retry);
+        }) {
+            return;
+        }
+        // Sleep in steps, so closing the pane stops the wait
+        let until = Instant::now() + backoff;
+        while Instant::now() < until {
+            if !note(log, |_| {}) {
+                return;
+            }
+            thread::sleep(POLL);
+        }
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+// Until the connection drops (Some(why)) or the pane closes (None)
+fn pump(
+    socket: &mut WebSocket<MaybeTlsStream<TcpStream>>,
+    log: &Shared,
+    outgoing: &Receiver<String>,
+) -> Option<String> {
+    // A read timeout lets one thread both wait for frames and send lines
+    let stream = match socket.get_mut() {
+        MaybeTlsStream::Plain(stream) => Some(&*stream),
+        MaybeTlsStream::Rustls(tls) => Some(&tls.sock),
+        _ => None,
+    };
+    if let Some(stream) = stream {
+        let _ = stream.set_read_timeout(Some(POLL));
+    }
+    loop {
+        loop {
+            match outgoing.try_recv() {
+                Ok(line) => {
+                    if let Err(err) = socket.send(Message::text(line.clone())) {
+                        return Some(err.to_string());
+                    }
+                    note(log, |l| l.push(Direction::Out, line));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return None,
+            }
+        }
+        let frame = match socket.read() {
+            Ok(Message::Text(text)) => text.to_string(),
+            Ok(Message::Binary(bytes)) => format!("<{} bytes of binary>", bytes.len()),
+            Ok(Message::Close(frame)) => {
+                return Some(match frame {
+                    Some(frame) if !frame.reason.is_empty() => {
+                        format!("closed by the server: {}", frame.reason)
+                    }
+                    _ => "closed by the server".to_string(),
+                });
+            }
+            Ok(_) => continue, // pings are answered by tungstenite itself
+            Err(Error::Io(err))
+                if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                if !note(log, |_| {}) {
+                    return None;
+                }
+                continue;
+            }
+            Err(err) => return Some(err.to_string()),
+        };
+        if !note(log, |l| l.push(Direction::In, frame)) {
+            return None;
+        }
+    }
+}
+
+pub struct WsView {
+    url: String,
+    log: Shared,
+    outgoing: Sender<String>,
+    started: Instant,
+    scroll: Option<usize>, // frames back from the newest; None = following
+    height: Cell<usize>,   // rows that fit, as of the last render
+}
+
+impl WsView {
+    fn new(url: &str) -> (Self, Receiver<String>) {
+        let (outgoing, receiver) = mpsc::channel();
+        let view = Self {
+            url: url.to_string(),
+            log: Shared::default(),
+            outgoing,
+            started: Instant::now(),
+            scroll: None,
+            height: Cell::new(1),
+        };
+        (view, receiver)
+    }
+
+    fn scroll_by(&mut self, step: isize) {
+        let count = self.log.lock().map_or(0, |l| l.frames.len());
+        let back = self.scroll.unwrap_or(0).saturating_add_signed(step);
+        let back = back.min(count.saturating_sub(self.height.get()));
+        self.scroll = (back > 0).then_some(back);
+    }
+}
+
+impl Drop for WsView {
+    fn drop(&mut self) {
+        if let Ok(mut log) = self.log.lock() {
+            log.closed = true;
+        }
+    }
+}
+
+impl Pane for WsView {
+    fn title(&self) -> String {
+        let connected = self.log.lock().is_ok_and(|l| l.connected);
+        let state = if connected {
+            "connected"
+        } else {
+            "connecting…"
+        };
+        format!("WebSocket: {} ({state})", self.url)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Ok(mut log) = self.log.lock() else {
+            return;
+        };
+        log.changed = false;
+        let height = area.height as usize;
+        self.height.set(height);
+        let end = log.frames.len().saturating_sub(self.scroll.unwrap_or(0));
+        let start = end.saturating_sub(height);
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let lines: Vec<Line> = log.frames[start..end]
+            .iter()
+            .map(|(at, direction, text)| {
+                let secs = at.duration_since(self.started).as_secs_f64();
+                let (mark, style) = match direction {
+                    Direction::In => ("←", Style::default().fg(theme.input_text)),
+                    Direction::Out => ("→", Style::default().fg(Color::Cyan)),
+                    Direction::Note => ("·", dim),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{secs:>7.1}s "), dim),
+                    Span::styled(format!("{mark} "), style),
+                    Span::styled(text.as_str(), style),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get() as isize;
+        match key.code {
+            KeyCode::Up => self.scroll_by(1),
+            KeyCode::Down => self.scroll_by(-1),
+            KeyCode::PageUp => self.scroll_by(page),
+            KeyCode::PageDown => self.scroll_by(-page),
+            KeyCode::Home => self.scroll_by(isize::MAX),
+            KeyCode::End => self.scroll = None,
+            _ => return false,
+        }
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.log.lock().is_ok_and(|l| l.changed)
+    }
+
+    fn hints(&self) -> Hints {
+        &[("↑↓", "scroll"), ("End", "follow"), ("Tab", "type a frame")]
+    }
+
+    fn input_label(&self) -> Option<String> {
+        Some("Send".to_string())
+    }
+
+    fn submit(&mut self, line: &str) {
+        let _ = self.outgoing.send(line.to_string());
+    }
+}
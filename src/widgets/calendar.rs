@@ -0,0 +1,263 @@
+// =============================================================================
+// Calendar — pick a date from a month view
+//
+//   ctx.open(Calendar::new("Due date").initial(due), |ctx, date| { … });
+//
+//   ←/→  ↑/↓         a day / a week
+//   PgUp/PgDn        a month
+//   [ / ]            a year
+//   Home/End         first / last of the month
+//   t                today
+//   Enter / Esc      choose / cancel
+//
+// Weeks start on Monday. Dates are plain proleptic Gregorian days with no
+// time zone; "today" is today in UTC. Date parses and prints as YYYY-MM-DD.
+// =============================================================================
+
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    hints::Hints,
+    overlay::{self, Outcome, Overlay},
+    theme::Theme,
+};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32, // 1–12
+    pub day: u32,   // 1–31
+}
+
+impl Date {
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_days((secs / 86_400) as i64)
+    }
+
+    // "2026-10-14"; None unless it's a real date
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.trim().splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        let valid = (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month);
+        valid.then_some(Self { year, month, day })
+    }
+
+    // Days since 1970-01-01 and back (Howard Hinnant's civil-date algorithm)
+    fn days(self) -> i64 {
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = i64::from(self.month);
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    // 0 = Monday … 6 = Sunday
+    fn weekday(self) -> usize {
+        (self.days() + 3).rem_euclid(7) as usize // 1970-01-01 was a Thursday
+    }
+
+    fn add_days(self, n: i64) -> Self {
+        Self::from_days(self.days() + n)
+    }
+
+    // Same day in another month, or that month's last day if it's shorter
+    fn add_months(self, n: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + n;
+        let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+        let day = self.day.min(days_in_month(year, month));
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+pub struct Calendar {
+    title: String,
+    cursor: Date,
+    today: Date,
+}
+
+impl Calendar {
+    pub fn new(title: impl Into<String>) -> Self {
+        let today = Date::today();
+        Self {
+            title: title.into(),
+            cursor: today,
+            today,
+        }
+    }
+
+    // Start on this date instead of today
+    pub fn initial(mut self, date: Date) -> Self {
+        self.cursor = date;
+        self
+    }
+}
+
+impl Overlay for Calendar {
+    type Answer = Date;
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let area = overlay::popup(frame, 26, 11, &self.title, theme);
+        let [month_row, weekdays_row, grid, hint_row] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(6),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let heading = Style::default()
+            .fg(theme.input_border)
+            .add_modifier(Modifier::BOLD);
+        let month = format!(
+            "{} {}",
+            MONTHS[self.cursor.month as usize - 1],
+            self.cursor.year
+        );
+        frame.render_widget(
+            Paragraph::new(month)
+                .style(heading)
+                .alignment(Alignment::Center),
+            month_row,
+        );
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        frame.render_widget(
+            Paragraph::new(" Mo Tu We Th Fr Sa Su").style(dim),
+            weekdays_row,
+        );
+
+        // Six weeks from the Monday on or before the 1st, so every month fits
+        let first = Date {
+            day: 1,
+            ..self.cursor
+        };
+        let start = first.add_days(-(first.weekday() as i64));
+        let weeks: Vec<Line> = (0..6)
+            .map(|week| {
+                let days = (0..7).map(|d| {
+                    let date = start.add_days(week * 7 + d);
+                    let mut style = Style::default();
+                    if date.month != self.cursor.month {
+                        style = style.add_modifier(Modifier::DIM);
+                    }
+                    if date == self.today {
+                        style = style.fg(theme.input_border).add_modifier(Modifier::BOLD);
+                    }
+                    if date == self.cursor {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    [
+                        Span::raw(" "),
+                        Span::styled(format!("{:>2}", date.day), style),
+                    ]
+                });
+                Line::from(days.flatten().collect::<Vec<_>>())
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(weeks), grid);
+
+        frame.render_widget(
+            Paragraph::new(" t today  Enter choose").style(dim),
+            hint_row,
+        );
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<Date> {
+        let cursor = self.cursor;
+        self.cursor = match key.code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            KeyCode::Enter => return Outcome::Done(cursor),
+            KeyCode::Left => cursor.add_days(-1),
+            KeyCode::Right => cursor.add_days(1),
+            KeyCode::Up => cursor.add_days(-7),
+            KeyCode::Down => cursor.add_days(7),
+            KeyCode::PageUp => cursor.add_months(-1),
+            KeyCode::PageDown => cursor.add_months(1),
+            KeyCode::Char('[') => cursor.add_months(-12),
+            KeyCode::Char(']') => cursor.add_months(12),
+            KeyCode::Home => Date { day: 1, ..cursor },
+            KeyCode::End => Date {
+                day: days_in_month(cursor.year, cursor.month),
+                ..cursor
+            },
+            KeyCode::Char('t') => self.today,
+            _ => cursor,
+        };
+        Outcome::Open
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("←→↑↓", "day / week"),
+            ("PgUp/PgDn", "month"),
+            ("t", "today"),
+            ("Enter", "choose"),
+            ("Esc", "cancel"),
+        ]
+    }
+}
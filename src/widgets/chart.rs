@@ -0,0 +1,247 @@
+// =============================================================================
+// Chart — values over time, one line per series, shown as a pane
+//
+//   let (chart, feed) = ChartView::new("Latency");
+//   ctx.show(chart);
+//   ctx.spawner.spawn_watcher(move |_| {
+//       while !feed.closed() {
+//           feed.record("p50", measure());
+//           …
+//       }
+//   });
+//
+// feed.record(series, value) can be called from any thread (clone the feed
+// for several jobs); the first value for a name adds that series to the
+// legend. The chart shows the last `window` of time (60s unless
+// .window() says otherwise) and follows the newest samples:
+//
+//   ←/→        look back / forward in time (stops following)
+//   End or f   follow again
+//   +/-        zoom the time window in / out
+//
+// The y axis fits whatever is visible. Samples older than HISTORY are
+// dropped.
+// =============================================================================
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Chart, Dataset, GraphType, LegendPosition},
+};
+
+use crate::{hints::Hints, pane::Pane, theme::Theme};
+
+const HISTORY: Duration = Duration::from_secs(60 * 60);
+const MIN_WINDOW: Duration = Duration::from_secs(5);
+
+// Series colors, in the order series first appear
+const PALETTE: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Green,
+    Color::Red,
+    Color::Blue,
+];
+
+#[derive(Default)]
+struct Data {
+    series: Vec<(String, Vec<(f64, f64)>)>, // (name, (seconds since start, value))
+    changed: bool,                          // since the last render
+    closed: bool,                           // the view is gone
+}
+
+// The sending half: cheap to clone, safe to move into a job
+#[derive(Clone)]
+pub struct ChartFeed {
+    data: Arc<Mutex<Data>>,
+    start: Instant,
+}
+
+impl ChartFeed {
+    pub fn record(&self, series: &str, value: f64) {
+        let now = self.start.elapsed().as_secs_f64();
+        let Ok(mut data) = self.data.lock() else {
+            return;
+        };
+        let points = match data.series.iter().position(|(name, _)| name == series) {
+            Some(i) => &mut data.series[i].1,
+            None => {
+                data.series.push((series.to_string(), Vec::new()));
+                &mut data.series.last_mut().expect("just pushed").1
+            }
+        };
+        points.push((now, value));
+        let cutoff = now - HISTORY.as_secs_f64();
+        if points.first().is_some_and(|&(t, _)| t < cutoff) {
+            points.retain(|&(t, _)| t >= cutoff);
+        }
+        data.changed = true;
+    }
+
+    // The pane was closed (or replaced): a job feeding it can stop
+    pub fn closed(&self) -> bool {
+        self.data.lock().map_or(true, |d| d.closed)
+    }
+}
+
+pub struct ChartView {
+    title: String,
+    feed: ChartFeed,
+    window: Duration,
+    end: Option<f64>, // right edge in seconds since start; None = now (following)
+}
+
+impl ChartView {
+    pub fn new(title: impl Into<String>) -> (Self, ChartFeed) {
+        let feed = ChartFeed {
+            data: Arc::default(),
+            start: Instant::now(),
+        };
+        let view = Self {
+            title: title.into(),
+            feed: feed.clone(),
+            window: Duration::from_secs(60),
+            end: None,
+        };
+        (view, feed)
+    }
+
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window.max(MIN_WINDOW);
+        self
+    }
+
+    fn now(&self) -> f64 {
+        self.feed.start.elapsed().as_secs_f64()
+    }
+
+    // Move the right edge by a quarter window; reaching now follows again
+    fn pan(&mut self, quarters: f64) {
+        let now = self.now();
+        let end = self.end.unwrap_or(now) + quarters * self.window.as_secs_f64() / 4.0;
+        self.end = (end < now).then_some(end.max(0.0));
+    }
+}
+
+impl Pane for ChartView {
+    fn title(&self) -> String {
+        match self.end {
+            None => self.title.clone(),
+            Some(_) => format!("{} (paused — End to follow)", self.title),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Ok(mut data) = self.feed.data.lock() else {
+            return;
+        };
+        data.changed = false;
+
+        let now = self.now();
+        let end = self.end.unwrap_or(now);
+        let start = end - self.window.as_secs_f64();
+
+        // Only the visible points, so the y axis fits what's on screen
+        let visible: Vec<(&str, Vec<(f64, f64)>)> = data
+            .series
+            .iter()
+            .map(|(name, points)| {
+                let shown = points
+                    .iter()
+                    .filter(|&&(t, _)| t >= start && t <= end)
+                    .copied()
+                    .collect();
+                (name.as_str(), shown)
+            })
+            .collect();
+
+        let (mut low, mut high) = visible
+            .iter()
+            .flat_map(|(_, points)| points.iter().map(|&(_, v)| v))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v), hi.max(v))
+            });
+        if !low.is_finite() {
+            (low, high) = (0.0, 1.0); // nothing visible yet
+        }
+        let pad = match (high - low) * 0.1 {
+            0.0 => 1.0, // a flat line still gets some room
+            pad => pad,
+        };
+        let (low, high) = (low - pad, high + pad);
+
+        let datasets = visible
+            .iter()
+            .enumerate()
+            .map(|(i, (name, points))| {
+                Dataset::default()
+                    .name(name.to_string())
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(PALETTE[i % PALETTE.len()]))
+                    .data(points)
+            })
+            .collect();
+
+        let axis_style = Style::default().fg(theme.output_border);
+        let ago = |t: f64| match now - t {
+            s if s < 0.5 => "now".to_string(),
+            s if s < 120.0 => format!("-{s:.0}s"),
+            s => format!("-{:.0}m", s / 60.0),
+        };
+        let x_axis = Axis::default()
+            .style(axis_style)
+            .bounds([start, end])
+            .labels([ago(start), ago((start + end) / 2.0), ago(end)].map(Span::raw));
+        let y_axis = Axis::default()
+            .style(axis_style)
+            .bounds([low, high])
+            .labels([low, (low + high) / 2.0, high].map(|v| Span::raw(format!("{v:.1}"))));
+
+        let chart = Chart::new(datasets)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .legend_position(Some(LegendPosition::TopLeft))
+            .hidden_legend_constraints((Constraint::Percentage(50), Constraint::Percentage(50)));
+        frame.render_widget(chart, area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Left => self.pan(-1.0),
+            KeyCode::Right => self.pan(1.0),
+            KeyCode::End | KeyCode::Char('f') => self.end = None,
+            KeyCode::Char('+') => self.window = (self.window / 2).max(MIN_WINDOW),
+            KeyCode::Char('-') => self.window = (self.window * 2).min(HISTORY),
+            _ => return false,
+        }
+        true
+    }
+
+    // Following the clock moves the chart even without new samples
+    fn tick(&mut self) -> bool {
+        self.end.is_none() || self.feed.data.lock().is_ok_and(|d| d.changed)
+    }
+
+    fn hints(&self) -> Hints {
+        &[("←→", "pan"), ("+/-", "zoom"), ("f", "follow")]
+    }
+}
+
+impl Drop for ChartView {
+    fn drop(&mut self) {
+        if let Ok(mut data) = self.feed.data.lock() {
+            data.closed = true;
+        }
+    }
+}
@@ -0,0 +1,312 @@
+// =============================================================================
+// Color picker — choose a palette color or type an RGB hex value
+//
+//   ctx.open(ColorPicker::new("Border color").initial(theme.input_border), |ctx, color| {
+//       …
+//   });
+//
+//   arrows          move around the palette
+//   Tab             switch between the palette and the hex field
+//   0-9 a-f  #      type a hex value (in the hex field)
+//   Enter / Esc     choose / cancel
+//
+// The palette is the xterm 256: the 16 terminal colors, the 6×6×6 cube and
+// the grayscale ramp. The first 16 come back as named colors (Color::Cyan),
+// the rest as Color::Indexed, a hex value as Color::Rgb. The swatch shows
+// whichever is current, so a hex value can be checked before choosing it.
+// name() gives the spelling themes and the config accept.
+// =============================================================================
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Margin},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    hints::Hints,
+    overlay::{self, Outcome, Overlay},
+    theme::Theme,
+};
+
+const NAMED: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+// xterm's defaults for the 16; terminals vary, this is only for the swatch
+// text and for picking a readable cursor
+const NAMED_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// The palette on screen: the 16, six rows of the cube, the grays
+const ROWS: usize = 8;
+
+// How a color is written in a theme: "cyan", "208", "#ff8800"
+pub fn name(color: Color) -> String {
+    color.to_string().to_lowercase()
+}
+
+pub struct ColorPicker {
+    title: String,
+    index: u8,             // the palette cursor
+    hex: Option<String>,   // Some while the hex field has focus
+    error: Option<String>, // why the last Enter was refused
+}
+
+impl ColorPicker {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            index: 0,
+            hex: None,
+            error: None,
+        }
+    }
+
+    // Start on this color: its palette entry, or the hex field for RGB
+    pub fn initial(mut self, color: Color) -> Self {
+        match color {
+            Color::Indexed(i) => self.index = i,
+            Color::Rgb(r, g, b) => self.hex = Some(format!("#{r:02x}{g:02x}{b:02x}")),
+            named => {
+                if let Some(i) = NAMED.iter().position(|&c| c == named) {
+                    self.index = i as u8;
+                }
+            }
+        }
+        self
+    }
+
+    // What Enter would choose right now
+    fn current(&self) -> Result<Color, String> {
+        match &self.hex {
+            Some(text) => parse_hex(text).ok_or_else(|| "  not a color: use #rrggbb".into()),
+            None => Ok(palette(self.index)),
+        }
+    }
+
+    fn step(&mut self, rows: isize, cols: isize) {
+        let (row, col) = position(self.index);
+        let row = row.saturating_add_signed(rows).min(ROWS - 1);
+        let col = col.saturating_add_signed(cols);
+        self.index = at(row, col);
+    }
+}
+
+fn palette(index: u8) -> Color {
+    match NAMED.get(index as usize) {
+        Some(&named) => named,
+        None => Color::Indexed(index),
+    }
+}
+
+fn rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..16 => NAMED_RGB[index as usize],
+        16..232 => {
+            let i = (index - 16) as usize;
+            (CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+fn parse_hex(text: &str) -> Option<Color> {
+    let digits = text.trim().trim_start_matches('#');
+    if digits.len() != 6 {
+        return None;
+    }
+    let value = u32::from_str_radix(digits, 16).ok()?;
+    Some(Color::Rgb(
+        (value >> 16) as u8,
+        (value >> 8) as u8,
+        value as u8,
+    ))
+}
+
+// Palette index ↔ (row, column) on screen
+fn position(index: u8) -> (usize, usize) {
+    let i = index as usize;
+    match i {
+        0..16 => (0, i),
+        16..232 => (1 + (i - 16) / 36, (i - 16) % 36),
+        _ => (7, i - 232),
+    }
+}
+
+fn at(row: usize, col: usize) -> u8 {
+    let i = match row {
+        0 => col.min(15),
+        1..=6 => 16 + (row - 1) * 36 + col.min(35),
+        _ => 232 + col.min(23),
+    };
+    i as u8
+}
+
+impl Overlay for ColorPicker {
+    type Answer = Color;
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let area = overlay::popup(frame, 76, 16, &self.title, theme);
+        let area = area.inner(Margin::new(1, 0));
+        let [grid, _, swatch, hint] = Layout::vertical([
+            Constraint::Length(10),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        // Each color is two cells wide; the cursor marks its cell with <>
+        let cell = |index: u8| {
+            let selected = self.hex.is_none() && index == self.index;
+            let (r, g, b) = rgb(index);
+            let light = r as u32 * 299 + g as u32 * 587 + b as u32 * 114 > 128_000;
+            let style = Style::default().bg(Color::Indexed(index)).fg(if light {
+                Color::Black
+            } else {
+                Color::White
+            });
+            Span::styled(if selected { "<>" } else { "  " }, style)
+        };
+        let row = |range: std::ops::Range<u16>| {
+            Line::from(range.map(|i| cell(i as u8)).collect::<Vec<_>>())
+        };
+        let mut lines = vec![row(0..16), Line::default()];
+        lines.extend((0..6).map(|r| row(16 + r * 36..52 + r * 36)));
+        lines.extend([Line::default(), row(232..256)]);
+        frame.render_widget(Paragraph::new(lines), grid);
+
+        let [sample, details] =
+            Layout::horizontal([Constraint::Length(12), Constraint::Fill(1)]).areas(swatch);
+        let current = self.current();
+        if let Ok(color) = current {
+            frame.render_widget(Paragraph::new("").style(Style::default().bg(color)), sample);
+        }
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let field = match &self.hex {
+            Some(text) => Line::from(vec![
+                Span::raw("  Hex: "),
+                Span::styled(text.as_str(), Style::default().fg(theme.input_text)),
+            ]),
+            None => Line::from(Span::styled("  Hex: (Tab to type one)", dim)),
+        };
+        let value = match (&current, &self.error) {
+            (Ok(color), _) if self.hex.is_some() => Line::from(format!("  {}", name(*color))),
+            (Ok(color), _) => {
+                let (r, g, b) = rgb(self.index);
+                Line::from(format!("  {}  ≈ #{r:02x}{g:02x}{b:02x}", name(*color)))
+            }
+            (Err(_), Some(why)) => {
+                Line::styled(why.as_str(), Style::default().add_modifier(Modifier::BOLD))
+            }
+            (Err(_), None) => Line::styled("  #rrggbb", dim),
+        };
+        frame.render_widget(Paragraph::new(vec![field, value]), details);
+        if let Some(text) = &self.hex {
+            let x = details.x + 7 + text.chars().count() as u16;
+            frame.set_cursor_position((x.min(details.right().saturating_sub(1)), details.y));
+        }
+
+        frame.render_widget(
+            Paragraph::new("Tab palette/hex  Enter choose  Esc cancel").style(dim),
+            hint,
+        );
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<Color> {
+        match key.code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            KeyCode::Enter => match self.current() {
+                Ok(color) => return Outcome::Done(color),
+                Err(why) => self.error = Some(why),
+            },
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.error = None;
+                self.hex = match self.hex {
+                    Some(_) => None,
+                    None => {
+                        let (r, g, b) = rgb(self.index);
+                        Some(format!("#{r:02x}{g:02x}{b:02x}"))
+                    }
+                };
+            }
+            _ => match &mut self.hex {
+                Some(text) => match key.code {
+                    KeyCode::Backspace => {
+                        text.pop();
+                        self.error = None;
+                    }
+                    KeyCode::Char(c) if c.is_ascii_hexdigit() || c == '#' => {
+                        if text.len() < 7 {
+                            text.push(c.to_ascii_lowercase());
+                        }
+                        self.error = None;
+                    }
+                    _ => {}
+                },
+                None => match key.code {
+                    KeyCode::Left => self.step(0, -1),
+                    KeyCode::Right => self.step(0, 1),
+                    KeyCode::Up => self.step(-1, 0),
+                    KeyCode::Down => self.step(1, 0),
+                    KeyCode::Home => self.index = at(position(self.index).0, 0),
+                    KeyCode::End => self.index = at(position(self.index).0, usize::MAX),
+                    _ => {}
+                },
+            },
+        }
+        Outcome::Open
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("arrows", "move"),
+            ("Tab", "palette / hex"),
+            ("Enter", "choose"),
+            ("Esc", "cancel"),
+        ]
+    }
+}
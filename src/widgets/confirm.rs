@@ -0,0 +1,163 @@
+// =============================================================================
+// Confirmation dialog — a yes/no question with a way out
+//
+//   ctx.open(Confirm::new("Overwrite notes.txt?").default(Choice::No), |ctx, yes| {
+//       if yes { … }
+//   });
+//
+// Buttons are Yes, No and Cancel, each with a shortcut (the first letter of
+// its label, so .labels("Overwrite", "Keep") gives o and k). ←/→/Tab move
+// between them, Enter presses the highlighted one — the default, to start
+// with. Esc is always Cancel, which closes the dialog without an answer.
+// =============================================================================
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::{
+    hints::Hints,
+    overlay::{self, Outcome, Overlay},
+    theme::Theme,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    Yes,
+    No,
+    Cancel,
+}
+
+const CHOICES: [Choice; 3] = [Choice::Yes, Choice::No, Choice::Cancel];
+
+pub struct Confirm {
+    question: String,
+    labels: [String; 3], // in CHOICES order
+    selected: Choice,
+}
+
+impl Confirm {
+    pub fn new(question: impl Into<String>) -> Self {
+        Self {
+            question: question.into(),
+            labels: ["Yes".into(), "No".into(), "Cancel".into()],
+            selected: Choice::Yes,
+        }
+    }
+
+    // The button Enter presses until the user moves
+    pub fn default(mut self, choice: Choice) -> Self {
+        self.selected = choice;
+        self
+    }
+
+    // Rename Yes and No; their first letters become the shortcuts
+    pub fn labels(mut self, yes: impl Into<String>, no: impl Into<String>) -> Self {
+        self.labels[0] = yes.into();
+        self.labels[1] = no.into();
+        self
+    }
+
+    fn label(&self, choice: Choice) -> &str {
+        &self.labels[index(choice)]
+    }
+
+    // The button whose label starts with `c`, ignoring case
+    fn by_shortcut(&self, c: char) -> Option<Choice> {
+        let c = c.to_ascii_lowercase();
+        CHOICES.into_iter().find(|&choice| {
+            let first = self.label(choice).chars().next();
+            first.map(|f| f.to_ascii_lowercase()) == Some(c)
+        })
+    }
+
+    fn press(&self, choice: Choice) -> Outcome<bool> {
+        match choice {
+            Choice::Yes => Outcome::Done(true),
+            Choice::No => Outcome::Done(false),
+            Choice::Cancel => Outcome::Cancel,
+        }
+    }
+
+    fn move_by(&mut self, step: isize) {
+        let i = (index(self.selected) as isize + step).rem_euclid(CHOICES.len() as isize);
+        self.selected = CHOICES[i as usize];
+    }
+}
+
+fn index(choice: Choice) -> usize {
+    CHOICES.iter().position(|&c| c == choice).unwrap_or(0)
+}
+
+impl Overlay for Confirm {
+    type Answer = bool; // true for Yes
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let width = (self.question.chars().count() as u16 + 4).clamp(36, 60);
+        let area = overlay::popup(frame, width, 7, "Confirm", theme);
+        let [question_area, buttons_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        frame.render_widget(
+            Paragraph::new(self.question.as_str()).wrap(Wrap { trim: true }),
+            question_area,
+        );
+
+        // [ Yes ]  [ No ]  [ Cancel ], shortcut letters underlined
+        let mut spans = Vec::new();
+        for choice in CHOICES {
+            let style = if choice == self.selected {
+                Style::default()
+                    .fg(theme.status_fg)
+                    .bg(theme.input_border)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let label = self.label(choice);
+            let split = label.chars().next().map_or(0, char::len_utf8);
+            let (first, rest) = label.split_at(split);
+            spans.push(Span::styled("[ ", style));
+            spans.push(Span::styled(
+                first,
+                style.add_modifier(Modifier::UNDERLINED),
+            ));
+            spans.push(Span::styled(format!("{rest} ]"), style));
+            spans.push(Span::raw("  "));
+        }
+        spans.pop();
+        let line = Line::from(spans);
+        let [centered] = Layout::horizontal([Constraint::Length(line.width() as u16)])
+            .flex(Flex::Center)
+            .areas(buttons_area);
+        frame.render_widget(line, centered);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<bool> {
+        match key.code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            KeyCode::Enter => return self.press(self.selected),
+            KeyCode::Left | KeyCode::BackTab => self.move_by(-1),
+            KeyCode::Right | KeyCode::Tab => self.move_by(1),
+            KeyCode::Char(c) => {
+                if let Some(choice) = self.by_shortcut(c) {
+                    return self.press(choice);
+                }
+            }
+            _ => {}
+        }
+        Outcome::Open
+    }
+
+    fn hints(&self) -> Hints {
+        &[("←→", "move"), ("Enter", "press"), ("Esc", "cancel")]
+    }
+}
@@ -0,0 +1,249 @@
+// =============================================================================
+// Diff — two texts side by side, changes highlighted, shown as a pane
+//
+//   ctx.show(DiffView::new("config.toml", &before, &after).labels("saved", "edited"));
+//
+//   ↑/↓ PgUp/PgDn Home/End   scroll
+//   n / p                    next / previous change
+//
+// Lines are matched up first (removed lines on the left, added on the
+// right, unchanged on both). Where a line was changed rather than added or
+// removed, the characters that differ are highlighted within it.
+// =============================================================================
+
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use similar::{Algorithm, DiffTag, capture_diff_slices};
+
+use crate::{hints::Hints, pane::Pane, theme::Theme};
+
+const REMOVED: Color = Color::Red;
+const ADDED: Color = Color::Green;
+
+pub struct DiffView {
+    title: String,
+    labels: (String, String),
+    rows: Vec<Row>,
+    added: usize,
+    removed: usize,
+    top: usize,          // first row on screen
+    height: Cell<usize>, // rows that fit, as of the last render
+}
+
+// One screen row: a line from either side, or both
+struct Row {
+    left: Option<Half>,
+    right: Option<Half>,
+    changed: bool,
+}
+
+struct Half {
+    number: usize,                 // 1-based line number
+    segments: Vec<(String, bool)>, // text, and whether it differs from the other side
+}
+
+impl Half {
+    // A line with no counterpart (or an identical one) — nothing to pick out
+    fn whole(number: usize, text: &str) -> Self {
+        Self {
+            number,
+            segments: vec![(text.to_string(), false)],
+        }
+    }
+}
+
+impl DiffView {
+    pub fn new(title: impl Into<String>, old: &str, new: &str) -> Self {
+        let old: Vec<&str> = old.lines().collect();
+        let new: Vec<&str> = new.lines().collect();
+        let mut view = Self {
+            title: title.into(),
+            labels: ("old".into(), "new".into()),
+            rows: Vec::new(),
+            added: 0,
+            removed: 0,
+            top: 0,
+            height: Cell::new(1),
+        };
+
+        for op in capture_diff_slices(Algorithm::Myers, &old, &new) {
+            let (tag, old_range, new_range) = op.as_tag_tuple();
+            match tag {
+                DiffTag::Equal => {
+                    for (o, n) in old_range.zip(new_range) {
+                        view.rows.push(Row {
+                            left: Some(Half::whole(o + 1, old[o])),
+                            right: Some(Half::whole(n + 1, new[n])),
+                            changed: false,
+                        });
+                    }
+                }
+                // A replaced block pairs its lines up; any extra lines on
+                // one side are plain removals or additions
+                _ => {
+                    view.removed += old_range.len();
+                    view.added += new_range.len();
+                    let pairs = old_range.len().max(new_range.len());
+                    for i in 0..pairs {
+                        let o = old_range.clone().nth(i);
+                        let n = new_range.clone().nth(i);
+                        let (left, right) = match (o, n) {
+                            (Some(o), Some(n)) => inline_diff(old[o], new[n], o + 1, n + 1),
+                            (Some(o), None) => (Some(Half::whole(o + 1, old[o])), None),
+                            (None, Some(n)) => (None, Some(Half::whole(n + 1, new[n]))),
+                            (None, None) => (None, None),
+                        };
+                        view.rows.push(Row {
+                            left,
+                            right,
+                            changed: true,
+                        });
+                    }
+                }
+            }
+        }
+        view
+    }
+
+    pub fn labels(mut self, left: impl Into<String>, right: impl Into<String>) -> Self {
+        self.labels = (left.into(), right.into());
+        self
+    }
+
+    fn scroll_by(&mut self, step: isize) {
+        let last = self.rows.len().saturating_sub(self.height.get());
+        self.top = self.top.saturating_add_signed(step).min(last);
+    }
+
+    // Jump to the start of the next (or previous) run of changed rows
+    fn jump(&mut self, forward: bool) {
+        let starts = (1..self.rows.len())
+            .filter(|&i| self.rows[i].changed && !self.rows[i - 1].changed)
+            .chain(self.rows.first().is_some_and(|r| r.changed).then_some(0));
+        let target = match forward {
+            true => starts.filter(|&i| i > self.top).min(),
+            false => starts.filter(|&i| i < self.top).max(),
+        };
+        if let Some(row) = target {
+            self.top = row;
+            self.scroll_by(0); // clamp
+        }
+    }
+}
+
+// The changed characters of a line pair, for highlighting both halves
+fn inline_diff(old: &str, new: &str, old_no: usize, new_no: usize) -> (Option<Half>, Option<Half>) {
+    let a: Vec<char> = old.chars().collect();
+    let b: Vec<char> = new.chars().collect();
+    let mut left = Half {
+        number: old_no,
+        segments: Vec::new(),
+    };
+    let mut right = Half {
+        number: new_no,
+        segments: Vec::new(),
+    };
+    for op in capture_diff_slices(Algorithm::Myers, &a, &b) {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        let changed = tag != DiffTag::Equal;
+        if !old_range.is_empty() {
+            left.segments.push((a[old_range].iter().collect(), changed));
+        }
+        if !new_range.is_empty() {
+            right
+                .segments
+                .push((b[new_range].iter().collect(), changed));
+        }
+    }
+    (Some(left), Some(right))
+}
+
+impl Pane for DiffView {
+    fn title(&self) -> String {
+        format!("{} (+{} −{})", self.title, self.added, self.removed)
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let [header, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+        let halves = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).spacing(1);
+        let [left_head, right_head] = halves.areas(header);
+        let [left_area, right_area] = halves.areas(body);
+
+        let head = Style::default()
+            .fg(theme.input_border)
+            .add_modifier(Modifier::BOLD);
+        frame.render_widget(
+            Paragraph::new(self.labels.0.as_str()).style(head),
+            left_head,
+        );
+        frame.render_widget(
+            Paragraph::new(self.labels.1.as_str()).style(head),
+            right_head,
+        );
+
+        self.height.set((body.height as usize).max(1));
+        let width = self.rows.len().max(1).to_string().len(); // for line numbers
+        let shown = &self.rows[self.top.min(self.rows.len())..];
+        let lines = |pick: fn(&Row) -> Option<&Half>, color: Color| -> Vec<Line> {
+            shown
+                .iter()
+                .take(body.height as usize)
+                .map(|row| {
+                    let Some(half) = pick(row) else {
+                        return Line::from(""); // the other side has a line here
+                    };
+                    let gutter = Style::default().fg(theme.output_border);
+                    let mut spans = vec![Span::styled(format!("{:>width$} ", half.number), gutter)];
+                    for (text, changed) in &half.segments {
+                        let style = match (row.changed, changed) {
+                            (false, _) => Style::default(),
+                            (true, false) => Style::default().fg(color),
+                            (true, true) => {
+                                Style::default().fg(color).add_modifier(Modifier::REVERSED)
+                            }
+                        };
+                        spans.push(Span::styled(text.as_str(), style));
+                    }
+                    Line::from(spans)
+                })
+                .collect()
+        };
+        frame.render_widget(
+            Paragraph::new(lines(|r| r.left.as_ref(), REMOVED)),
+            left_area,
+        );
+        frame.render_widget(
+            Paragraph::new(lines(|r| r.right.as_ref(), ADDED)),
+            right_area,
+        );
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get() as isize;
+        match key.code {
+            KeyCode::Up => self.scroll_by(-1),
+            KeyCode::Down => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home => self.top = 0,
+            KeyCode::End => self.scroll_by(isize::MAX),
+            KeyCode::Char('n') => self.jump(true),
+            KeyCode::Char('p') => self.jump(false),
+            _ => return false,
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[("↑↓", "scroll"), ("n/p", "next / previous change")]
+    }
+}
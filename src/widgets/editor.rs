@@ -0,0 +1,344 @@
+// =============================================================================
+// Editor — a multi-line text area, shown as a pane
+//
+//   ctx.show(EditorView::open(ctx.effects.files.clone(), "notes.md")); // a file; ctrl-s saves it
+//   ctx.show(EditorView::new("Draft", "echo hi\n"));                   // just text
+//
+//   arrows  Home/End  PgUp/PgDn         move
+//   ctrl-←/→                            by word
+//   Enter  Tab  Backspace  Delete       edit (Tab inserts spaces)
+//   ctrl-k                              delete to the end of the line
+//   ctrl-s                              save (editors opened on a file)
+//   Esc                                 back to the command bar
+//
+// .live() hands back a LiveText that always holds the current text, for a
+// view that follows along (MarkdownView::live, for an edit/preview split).
+//
+// The title shows the file name, • when there are unsaved changes, and the
+// result of the last save. The cursor is a (line, column) pair in
+// characters; columns past the end of a shorter line are remembered so
+// moving up and down keeps its place.
+// =============================================================================
+
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Frame, layout::Rect, style::Style, widgets::Paragraph};
+
+use crate::{effects::Files, hints::Hints, pane::Pane, theme::Theme};
+
+const TAB: &str = "    ";
+
+pub struct EditorView {
+    title: String,
+    file: Option<(Arc<dyn Files>, PathBuf)>, // what it was opened on, and saves to
+    lines: Vec<String>,
+    row: usize,
+    col: usize,                   // in chars; may be past the end of the line
+    dirty: bool,                  // changed since opened or saved
+    status: Option<String>,       // the result of the last save
+    scroll: Cell<(usize, usize)>, // (first row, first column) on screen
+    live: Option<LiveText>,       // kept up to date for a preview
+}
+
+// The editor's text as it's typed, for a view that follows along (a
+// markdown preview). Clone it as often as needed.
+#[derive(Clone, Default)]
+pub struct LiveText(Arc<Mutex<(u64, String)>>); // (edits so far, text)
+
+impl LiveText {
+    // The text, and a number that changes whenever it does
+    pub fn get(&self) -> (u64, String) {
+        self.0
+            .lock()
+            .map_or_else(|_| (0, String::new()), |l| l.clone())
+    }
+}
+
+impl EditorView {
+    pub fn new(title: impl Into<String>, text: &str) -> Self {
+        let mut lines: Vec<String> = text.lines().map(String::from).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Self {
+            title: title.into(),
+            file: None,
+            lines,
+            row: 0,
+            col: 0,
+            dirty: false,
+            status: None,
+            scroll: Cell::new((0, 0)),
+            live: None,
+        }
+    }
+
+    // Edit a file; a file that doesn't exist yet starts empty and is
+    // created on the first save
+    pub fn open(files: Arc<dyn Files>, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let (text, status) = match files.read(path) {
+            Ok(text) => (text, None),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                (String::new(), Some("new file".to_string()))
+            }
+            Err(err) => (String::new(), Some(format!("can't read: {err}"))),
+        };
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        Self {
+            file: Some((files, path.to_path_buf())),
+            status,
+            ..Self::new(name, &text)
+        }
+    }
+
+    // Start with the cursor on a (1-based) line
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.row = line.saturating_sub(1).min(self.lines.len() - 1);
+        self
+    }
+
+    // Share the text as it's edited
+    pub fn live(mut self) -> (Self, LiveText) {
+        let live = LiveText::default();
+        self.live = Some(live.clone());
+        self.publish();
+        (self, live)
+    }
+
+    fn publish(&self) {
+        if let Some(LiveText(live)) = &self.live
+            && let Ok(mut live) = live.lock()
+        {
+            *live = (live.0 + 1, self.text());
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    fn col(&self) -> usize {
+        self.col.min(self.line_len(self.row))
+    }
+
+    // Byte offset of the cursor in its line
+    fn byte(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.col())
+            .map_or(line.len(), |(i, _)| i)
+    }
+
+    fn edited(&mut self) {
+        self.dirty = true;
+        self.status = None;
+        self.publish();
+    }
+
+    fn insert(&mut self, s: &str) {
+        let at = self.byte();
+        self.lines[self.row].insert_str(at, s);
+        self.col = self.col() + s.chars().count();
+        self.edited();
+    }
+
+    fn newline(&mut self) {
+        let at = self.byte();
+        let rest = self.lines[self.row].split_off(at);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+        self.edited();
+    }
+
+    fn backspace(&mut self) {
+        let col = self.col();
+        if col > 0 {
+            self.col = col - 1;
+            let at = self.byte();
+            self.lines[self.row].remove(at);
+        } else if self.row > 0 {
+            // Join onto the end of the line above
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        } else {
+            return;
+        }
+        self.edited();
+    }
+
+    fn delete(&mut self) {
+        if self.col() < self.line_len(self.row) {
+            let at = self.byte();
+            self.lines[self.row].remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        } else {
+            return;
+        }
+        self.edited();
+    }
+
+    fn delete_to_end(&mut self) {
+        if self.col() == self.line_len(self.row) {
+            return self.delete(); // at the end already: join the next line
+        }
+        let at = self.byte();
+        self.lines[self.row].truncate(at);
+        self.edited();
+    }
+
+    fn move_rows(&mut self, step: isize) {
+        let last = self.lines.len() - 1;
+        self.row = self.row.saturating_add_signed(step).min(last);
+    }
+
+    fn left(&mut self) {
+        if self.col() > 0 {
+            self.col = self.col() - 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+        }
+    }
+
+    fn right(&mut self) {
+        if self.col() < self.line_len(self.row) {
+            self.col = self.col() + 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    // Skip spaces, then a run of word characters (like most editors)
+    fn word(&mut self, forward: bool) {
+        let chars: Vec<char> = self.lines[self.row].chars().collect();
+        let mut col = self.col();
+        if forward {
+            if col == chars.len() {
+                return self.right();
+            }
+            while col < chars.len() && !chars[col].is_alphanumeric() {
+                col += 1;
+            }
+            while col < chars.len() && chars[col].is_alphanumeric() {
+                col += 1;
+            }
+        } else {
+            if col == 0 {
+                return self.left();
+            }
+            while col > 0 && !chars[col - 1].is_alphanumeric() {
+                col -= 1;
+            }
+            while col > 0 && chars[col - 1].is_alphanumeric() {
+                col -= 1;
+            }
+        }
+        self.col = col;
+    }
+
+    fn save(&mut self) {
+        let Some((files, path)) = &self.file else {
+            self.status = Some("nowhere to save".to_string());
+            return;
+        };
+        match files.write(path, &self.text()) {
+            Ok(()) => {
+                self.dirty = false;
+                self.status = Some("saved".to_string());
+            }
+            Err(err) => self.status = Some(format!("save failed: {err}")),
+        }
+    }
+}
+
+impl Pane for EditorView {
+    fn title(&self) -> String {
+        let dirty = if self.dirty { " •" } else { "" };
+        match &self.status {
+            Some(status) => format!("{}{dirty} — {status}", self.title),
+            None => format!("{}{dirty}", self.title),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        // Scroll just enough to keep the cursor on screen
+        let (height, width) = (area.height.max(1) as usize, area.width.max(1) as usize);
+        let (mut top, mut left) = self.scroll.get();
+        let col = self.col();
+        top = top.clamp(self.row.saturating_sub(height - 1), self.row);
+        left = left.clamp(col.saturating_sub(width - 1), col);
+        self.scroll.set((top, left));
+
+        let text = self.lines[top..]
+            .iter()
+            .take(height)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n");
+        let editor = Paragraph::new(text)
+            .style(Style::default().fg(theme.input_text))
+            .scroll((0, left as u16));
+        frame.render_widget(editor, area);
+
+        // App draws the input bar afterwards, and only puts the cursor
+        // there when this pane doesn't have focus
+        let (x, y) = ((col - left) as u16, (self.row - top) as u16);
+        frame.set_cursor_position((area.x + x, area.y + y));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('s') if ctrl => self.save(),
+            KeyCode::Char('k') if ctrl => self.delete_to_end(),
+            KeyCode::Left if ctrl => self.word(false),
+            KeyCode::Right if ctrl => self.word(true),
+            KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                self.insert(c.encode_utf8(&mut [0; 4]));
+            }
+            KeyCode::Tab => self.insert(TAB),
+            KeyCode::Enter => self.newline(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.left(),
+            KeyCode::Right => self.right(),
+            KeyCode::Up => self.move_rows(-1),
+            KeyCode::Down => self.move_rows(1),
+            KeyCode::PageUp => self.move_rows(-10),
+            KeyCode::PageDown => self.move_rows(10),
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = usize::MAX,
+            _ => return false,
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("ctrl-s", "save"),
+            ("ctrl-k", "delete to end"),
+            ("ctrl-←→", "by word"),
+        ]
+    }
+}
@@ -0,0 +1,307 @@
+// =============================================================================
+// File picker — choose a path without typing it
+//
+//   ↑/↓        move            Enter / →   open directory, or choose file
+//   type       filter          Backspace   delete filter char, or go up
+//   Tab        hidden files    ← / Esc     go up / cancel
+//
+// FilePicker::new(ctx.effects.files.clone(), dir) chooses a file;
+// .directories() chooses a directory instead (only directories are listed,
+// and "./" picks the current one). The answer is the chosen path, absolute.
+// =============================================================================
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    effects::Files,
+    hints::Hints,
+    input_line,
+    overlay::{self, Outcome, Overlay},
+    theme::Theme,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Here, // "./" — choose the current directory (directory mode only)
+    Parent,
+    Dir,
+    File,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    kind: Kind,
+}
+
+pub struct FilePicker {
+    files: Arc<dyn Files>,
+    dir: PathBuf,
+    directories_only: bool,
+    show_hidden: bool,
+    entries: Vec<Entry>, // everything in `dir`
+    visible: Vec<usize>, // indexes into `entries` that match the filter
+    filter: String,
+    selected: usize,       // index into `visible`
+    error: Option<String>, // why `dir` couldn't be listed
+}
+
+impl FilePicker {
+    // Start in `dir`, or the working directory if it doesn't exist
+    pub fn new(files: Arc<dyn Files>, dir: impl AsRef<Path>) -> Self {
+        let dir = (files.canonicalize(dir.as_ref()))
+            .or_else(|_| env::current_dir())
+            .unwrap_or_else(|_| PathBuf::from("/"));
+        let mut picker = Self {
+            files,
+            dir,
+            directories_only: false,
+            show_hidden: false,
+            entries: Vec::new(),
+            visible: Vec::new(),
+            filter: String::new(),
+            selected: 0,
+            error: None,
+        };
+        picker.refresh();
+        picker
+    }
+
+    pub fn directories(mut self) -> Self {
+        self.directories_only = true;
+        self.refresh();
+        self
+    }
+
+    // Re-read `dir` and reapply the filter
+    fn refresh(&mut self) {
+        self.entries.clear();
+        self.error = None;
+        if self.directories_only {
+            self.entries.push(Entry {
+                name: "./".into(),
+                kind: Kind::Here,
+            });
+        }
+        if self.dir.parent().is_some() {
+            self.entries.push(Entry {
+                name: "../".into(),
+                kind: Kind::Parent,
+            });
+        }
+
+        let mut listed = Vec::new();
+        match self.files.read_dir(&self.dir) {
+            Ok(read) => {
+                for path in read {
+                    let Some(name) = path.file_name() else {
+                        continue;
+                    };
+                    let name = name.to_string_lossy().into_owned();
+                    if name.starts_with('.') && !self.show_hidden {
+                        continue;
+                    }
+                    // Follow symlinks, so a link to a directory can be opened
+                    let is_dir = self.files.metadata(&path).is_ok_and(|meta| meta.is_dir);
+                    if is_dir || !self.directories_only {
+                        let kind = if is_dir { Kind::Dir } else { Kind::File };
+                        listed.push(Entry { name, kind });
+                    }
+                }
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        // Directories first, then by name regardless of case
+        listed.sort_by_key(|e| (e.kind != Kind::Dir, e.name.to_lowercase()));
+        self.entries.extend(listed);
+        self.apply_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.visible = (0..self.entries.len())
+            .filter(|&i| {
+                let entry = &self.entries[i];
+                match entry.kind {
+                    // Hidden while filtering, so Enter picks the first match
+                    Kind::Here | Kind::Parent => filter.is_empty(),
+                    Kind::Dir | Kind::File => entry.name.to_lowercase().contains(&filter),
+                }
+            })
+            .collect();
+        self.selected = 0;
+    }
+
+    fn enter(&mut self, dir: PathBuf) {
+        self.dir = dir;
+        self.filter.clear();
+        self.refresh();
+    }
+
+    fn go_up(&mut self) {
+        let Some(parent) = self.dir.parent() else {
+            return;
+        };
+        let child = self
+            .dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned());
+        self.enter(parent.to_path_buf());
+
+        // Land on the directory we just left
+        if let Some(child) = child
+            && let Some(pos) = self
+                .visible
+                .iter()
+                .position(|&i| self.entries[i].name == child)
+        {
+            self.selected = pos;
+        }
+    }
+
+    fn current(&self) -> Option<&Entry> {
+        self.visible.get(self.selected).map(|&i| &self.entries[i])
+    }
+
+    fn open_selected(&mut self) -> Outcome<PathBuf> {
+        let Some(entry) = self.current().cloned() else {
+            return Outcome::Open;
+        };
+        match entry.kind {
+            Kind::Here => Outcome::Done(self.dir.clone()),
+            Kind::Parent => {
+                self.go_up();
+                Outcome::Open
+            }
+            Kind::Dir => {
+                self.enter(self.dir.join(&entry.name));
+                Outcome::Open
+            }
+            Kind::File => Outcome::Done(self.dir.join(&entry.name)),
+        }
+    }
+
+    fn move_by(&mut self, step: isize) {
+        let last = self.visible.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(step).min(last);
+    }
+}
+
+impl Overlay for FilePicker {
+    type Answer = PathBuf;
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let title = if self.directories_only {
+            "Choose directory"
+        } else {
+            "Open file"
+        };
+        let area = overlay::popup(frame, 70, 22, title, theme);
+        let [path_row, filter_row, list_area, hint_row] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        frame.render_widget(
+            Paragraph::new(self.dir.display().to_string()).style(dim),
+            path_row,
+        );
+        let filter = format!("> {}", self.filter);
+        frame.render_widget(Paragraph::new(filter.as_str()), filter_row);
+        frame.set_cursor_position((filter_row.x + input_line::width(&filter), filter_row.y));
+
+        if let Some(err) = &self.error {
+            frame.render_widget(Paragraph::new(format!("  {err}")), list_area);
+        } else {
+            let items: Vec<ListItem> = self
+                .visible
+                .iter()
+                .map(|&i| {
+                    let entry = &self.entries[i];
+                    match entry.kind {
+                        Kind::Dir => ListItem::new(format!("{}/", entry.name))
+                            .style(Style::default().fg(theme.input_border)),
+                        Kind::Here | Kind::Parent => ListItem::new(entry.name.as_str()).style(dim),
+                        Kind::File => ListItem::new(entry.name.as_str()),
+                    }
+                })
+                .collect();
+            let list =
+                List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, list_area, &mut state);
+        }
+
+        let hidden = if self.show_hidden { "on" } else { "off" };
+        let hint = format!("⏎ open  ← up  tab hidden files ({hidden})  esc cancel");
+        frame.render_widget(Paragraph::new(Line::from(hint)).style(dim), hint_row);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<PathBuf> {
+        match key.code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Enter => return self.open_selected(),
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Down => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-10),
+            KeyCode::PageDown => self.move_by(10),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.move_by(isize::MAX),
+            KeyCode::Left => self.go_up(),
+            KeyCode::Right if self.current().is_some_and(|e| e.kind == Kind::Dir) => {
+                return self.open_selected();
+            }
+            KeyCode::Tab => {
+                self.show_hidden = !self.show_hidden;
+                self.refresh();
+            }
+            KeyCode::Backspace => {
+                if self.filter.pop().is_some() {
+                    self.apply_filter();
+                } else {
+                    self.go_up();
+                }
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.filter.push(c);
+                self.apply_filter();
+            }
+            _ => {}
+        }
+        Outcome::Open
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("Enter", "open / choose"),
+            ("type", "filter"),
+            ("←", "up"),
+            ("Tab", "hidden files"),
+            ("Esc", "cancel"),
+        ]
+    }
+}
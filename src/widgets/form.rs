@@ -0,0 +1,284 @@
+// =============================================================================
+// Form — several labeled fields in one dialog
+//
+//   let form = Form::new("New connection")
+//       .text("host", "Host", "localhost")
+//       .validate(|host| if host.is_empty() { Err("required".into()) } else { Ok(()) })
+//       .select("auth", "Auth", ["password", "key"])
+//       .checkbox("tls", "Use TLS", true);
+//   ctx.open(form, |ctx, values| { let host = &values["host"]; … });
+//
+//   Tab / ↓  Shift-Tab / ↑   next / previous field
+//   Space                    toggle a checkbox
+//   ←/→                      change a select (or move in a text field)
+//   Enter                    submit (or press the focused button)
+//   Esc                      cancel
+//
+// Text fields edit like Prompt. The answer maps each field's key to its
+// value: the text, the chosen option, or "true" / "false" for a checkbox.
+// .validate(check) applies to the field added just before it; a submit with
+// a field that fails its check moves to that field and shows why.
+// =============================================================================
+
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use super::prompt::LineEdit;
+use crate::{
+    hints::Hints,
+    overlay::{self, Outcome, Overlay},
+    theme::Theme,
+};
+
+type Check = Box<dyn Fn(&str) -> Result<(), String> + Send>;
+
+pub struct Form {
+    title: String,
+    fields: Vec<Field>,
+    focus: usize,                   // a field, or past them: Submit, then Cancel
+    error: Option<(usize, String)>, // the field that failed its check, and why
+}
+
+struct Field {
+    key: String,
+    label: String,
+    input: Input,
+    check: Option<Check>,
+}
+
+enum Input {
+    Text(LineEdit),
+    Checkbox(bool),
+    Select(Vec<String>, usize),
+}
+
+impl Field {
+    fn value(&self) -> String {
+        match &self.input {
+            Input::Text(line) => line.text.clone(),
+            Input::Checkbox(on) => on.to_string(),
+            Input::Select(options, chosen) => options[*chosen].clone(),
+        }
+    }
+}
+
+impl Form {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            fields: Vec::new(),
+            focus: 0,
+            error: None,
+        }
+    }
+
+    pub fn text(self, key: &str, label: &str, initial: impl Into<String>) -> Self {
+        self.field(key, label, Input::Text(LineEdit::new(initial)))
+    }
+
+    pub fn checkbox(self, key: &str, label: &str, on: bool) -> Self {
+        self.field(key, label, Input::Checkbox(on))
+    }
+
+    // The first option starts chosen; a select needs at least one
+    pub fn select<S: Into<String>>(
+        self,
+        key: &str,
+        label: &str,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let mut options: Vec<String> = options.into_iter().map(Into::into).collect();
+        if options.is_empty() {
+            options.push(String::new());
+        }
+        self.field(key, label, Input::Select(options, 0))
+    }
+
+    // Check the last field added before submitting
+    pub fn validate(mut self, check: impl Fn(&str) -> Result<(), String> + Send + 'static) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.check = Some(Box::new(check));
+        }
+        self
+    }
+
+    fn field(mut self, key: &str, label: &str, input: Input) -> Self {
+        self.fields.push(Field {
+            key: key.into(),
+            label: label.into(),
+            input,
+            check: None,
+        });
+        self
+    }
+
+    // Every value, or the first field that fails its check
+    fn submit(&mut self) -> Outcome<BTreeMap<String, String>> {
+        for (i, field) in self.fields.iter().enumerate() {
+            if let Some(check) = &field.check
+                && let Err(why) = check(&field.value())
+            {
+                self.focus = i;
+                self.error = Some((i, why));
+                return Outcome::Open;
+            }
+        }
+        let values = self.fields.iter().map(|f| (f.key.clone(), f.value()));
+        Outcome::Done(values.collect())
+    }
+
+    fn move_focus(&mut self, step: isize) {
+        let stops = self.fields.len() + 2; // and the two buttons
+        self.focus = (self.focus as isize + step).rem_euclid(stops as isize) as usize;
+    }
+}
+
+impl Overlay for Form {
+    type Answer = BTreeMap<String, String>;
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let label_width = self
+            .fields
+            .iter()
+            .map(|f| f.label.chars().count())
+            .max()
+            .unwrap_or(0) as u16
+            + 2;
+        let rows = self.fields.len() as u16;
+        let area = overlay::popup(
+            frame,
+            (label_width + 36).clamp(40, 70),
+            rows + 4,
+            &self.title,
+            theme,
+        );
+        let [fields_area, error_row, buttons_row] = Layout::vertical([
+            Constraint::Length(rows),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let text_style = Style::default().fg(theme.input_text);
+        let focused_label = Style::default()
+            .fg(theme.input_border)
+            .add_modifier(Modifier::BOLD);
+        let row_areas =
+            Layout::vertical(vec![Constraint::Length(1); self.fields.len()]).split(fields_area);
+        for (i, (field, row)) in self.fields.iter().zip(row_areas.iter()).enumerate() {
+            let focused = i == self.focus;
+            let [label_area, input_area] =
+                Layout::horizontal([Constraint::Length(label_width), Constraint::Fill(1)])
+                    .areas(*row);
+            let label_style = if focused {
+                focused_label
+            } else {
+                Style::default()
+            };
+            let label = Paragraph::new(format!("{}:", field.label)).style(label_style);
+            frame.render_widget(label, label_area);
+            match &field.input {
+                Input::Text(line) => line.render(frame, input_area, text_style, focused),
+                Input::Checkbox(on) => {
+                    let mark = if *on { "[x]" } else { "[ ]" };
+                    frame.render_widget(Paragraph::new(mark).style(text_style), input_area);
+                }
+                Input::Select(options, chosen) => {
+                    let arrows = Style::default().add_modifier(Modifier::DIM);
+                    let line = Line::from(vec![
+                        Span::styled("‹ ", arrows),
+                        Span::styled(options[*chosen].as_str(), text_style),
+                        Span::styled(" ›", arrows),
+                    ]);
+                    frame.render_widget(Paragraph::new(line), input_area);
+                }
+            }
+        }
+
+        if let Some((i, why)) = &self.error {
+            let style = Style::default().add_modifier(Modifier::BOLD);
+            let message = format!("{}: {why}", self.fields[*i].label);
+            frame.render_widget(Paragraph::new(message).style(style), error_row);
+        }
+
+        let button = |text: &'static str, stop: usize| {
+            let style = match self.focus == stop {
+                true => Style::default().add_modifier(Modifier::REVERSED),
+                false => Style::default(),
+            };
+            Span::styled(text, style)
+        };
+        let n = self.fields.len();
+        let buttons = Line::from(vec![
+            button("[ Submit ]", n),
+            Span::raw("  "),
+            button("[ Cancel ]", n + 1),
+        ]);
+        frame.render_widget(Paragraph::new(buttons).centered(), buttons_row);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<BTreeMap<String, String>> {
+        let n = self.fields.len();
+        match key.code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            KeyCode::Enter if self.focus == n + 1 => return Outcome::Cancel,
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Tab | KeyCode::Down => self.move_focus(1),
+            KeyCode::BackTab | KeyCode::Up => self.move_focus(-1),
+            _ => {
+                let Some(field) = self.fields.get_mut(self.focus) else {
+                    // On the buttons: ←/→ move between them
+                    match key.code {
+                        KeyCode::Left => self.focus = n,
+                        KeyCode::Right => self.focus = n + 1,
+                        _ => {}
+                    }
+                    return Outcome::Open;
+                };
+                let changed = match &mut field.input {
+                    Input::Text(line) => line.handle_key(key),
+                    Input::Checkbox(on) => match key.code {
+                        KeyCode::Char(' ') => {
+                            *on = !*on;
+                            true
+                        }
+                        _ => false,
+                    },
+                    Input::Select(options, chosen) => {
+                        let len = options.len();
+                        match key.code {
+                            KeyCode::Left => *chosen = (*chosen + len - 1) % len,
+                            KeyCode::Right | KeyCode::Char(' ') => *chosen = (*chosen + 1) % len,
+                            _ => return Outcome::Open,
+                        }
+                        true
+                    }
+                };
+                if changed && self.error.as_ref().is_some_and(|(i, _)| *i == self.focus) {
+                    self.error = None; // they're fixing it
+                }
+            }
+        }
+        Outcome::Open
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("Tab", "next field"),
+            ("Space", "toggle"),
+            ("Enter", "submit"),
+            ("Esc", "cancel"),
+        ]
+    }
+}
@@ -0,0 +1,222 @@
+// =============================================================================
+// Hex — a file's bytes as offset / hex / ASCII columns, shown as a pane
+//
+//   ctx.show(HexView::new("Hex: firmware.bin", bytes));
+//
+//   ←/→ ↑/↓                  move a byte / a row
+//   PgUp/PgDn Home/End       a page / the start or end
+//   g                        go to an offset (hex, 0x optional), then Enter
+//
+// Rows hold 16 bytes, or 8 when the pane is narrow. Bytes outside printable
+// ASCII show as "." on the right. The byte under the cursor is highlighted
+// in both columns and its offset and value are in the title.
+// =============================================================================
+
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{hints::Hints, pane::Pane, theme::Theme};
+
+pub struct HexView {
+    title: String,
+    bytes: Vec<u8>,
+    cursor: usize,         // offset of the selected byte
+    top: Cell<usize>,      // first row on screen; render() keeps the cursor in view
+    width: Cell<usize>,    // bytes per row, as of the last render
+    height: Cell<usize>,   // rows that fit, as of the last render
+    goto: Option<String>,  // the offset being typed after `g`
+    error: Option<String>, // why the last goto didn't go anywhere
+}
+
+impl HexView {
+    pub fn new(title: impl Into<String>, bytes: Vec<u8>) -> Self {
+        Self {
+            title: title.into(),
+            bytes,
+            cursor: 0,
+            top: Cell::new(0),
+            width: Cell::new(16),
+            height: Cell::new(1),
+            goto: None,
+            error: None,
+        }
+    }
+
+    fn move_by(&mut self, step: isize) {
+        let last = self.bytes.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(step).min(last);
+    }
+
+    fn page(&self) -> isize {
+        (self.width.get() * self.height.get().max(1)) as isize
+    }
+
+    fn jump(&mut self, typed: &str) {
+        let digits = typed.trim_start_matches("0x");
+        match usize::from_str_radix(digits, 16) {
+            Ok(offset) if offset < self.bytes.len() => self.cursor = offset,
+            Ok(_) => self.error = Some(format!("0x{digits} is past the end")),
+            Err(_) => self.error = Some(format!("'{typed}' isn't a hex offset")),
+        }
+    }
+
+    // The goto line swallows hex digits; Enter jumps, anything else cancels
+    fn handle_goto(&mut self, key: KeyEvent, typed: &mut String) -> bool {
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_hexdigit() || (c == 'x' && typed == "0") => {
+                typed.push(c);
+            }
+            KeyCode::Backspace if !typed.is_empty() => {
+                typed.pop();
+            }
+            KeyCode::Enter => {
+                if !typed.is_empty() {
+                    let typed = typed.clone();
+                    self.jump(&typed);
+                }
+                return true;
+            }
+            _ => return false,
+        }
+        self.goto = Some(typed.clone());
+        true
+    }
+}
+
+// The ASCII column's stand-in for a byte
+fn printable(byte: u8) -> char {
+    match byte {
+        0x20..=0x7e => byte as char,
+        _ => '.',
+    }
+}
+
+impl Pane for HexView {
+    fn title(&self) -> String {
+        match self.bytes.get(self.cursor) {
+            Some(byte) => format!(
+                "{} ({} bytes) @ 0x{:08x} = 0x{byte:02x} {byte}",
+                self.title,
+                self.bytes.len(),
+                self.cursor
+            ),
+            None => format!("{} (empty)", self.title),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        // "00000000  " + "xx " per byte + a gap per 8 + "|" ascii "|"
+        let width = if area.width >= 10 + 16 * 4 + 2 + 2 {
+            16
+        } else {
+            8
+        };
+        let footer = usize::from(self.goto.is_some() || self.error.is_some());
+        let height = (area.height as usize).saturating_sub(footer).max(1);
+        self.width.set(width);
+        self.height.set(height);
+
+        // Keep the cursor's row on screen, scrolling no more than needed
+        let row = self.cursor / width;
+        let mut top = self.top.get();
+        if row < top {
+            top = row;
+        } else if row >= top + height {
+            top = row + 1 - height;
+        }
+        self.top.set(top);
+
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let offset_style = Style::default().fg(theme.output_border);
+        let selected = Style::default().add_modifier(Modifier::REVERSED);
+        let mut lines: Vec<Line> = self
+            .bytes
+            .chunks(width)
+            .enumerate()
+            .skip(top)
+            .take(height)
+            .map(|(row, chunk)| {
+                let start = row * width;
+                let style_of = |i: usize, byte: u8| match (start + i == self.cursor, byte) {
+                    (true, _) => selected,
+                    (false, 0) => dim,
+                    _ => Style::default(),
+                };
+                let mut spans = vec![Span::styled(format!("{start:08x}  "), offset_style)];
+                for i in 0..width {
+                    if i > 0 && i % 8 == 0 {
+                        spans.push(Span::raw(" "));
+                    }
+                    match chunk.get(i) {
+                        Some(&byte) => {
+                            spans.push(Span::styled(format!("{byte:02x}"), style_of(i, byte)));
+                            spans.push(Span::raw(" "));
+                        }
+                        None => spans.push(Span::raw("   ")),
+                    }
+                }
+                spans.push(Span::styled(" |", dim));
+                for (i, &byte) in chunk.iter().enumerate() {
+                    spans.push(Span::styled(printable(byte).to_string(), style_of(i, byte)));
+                }
+                spans.push(Span::styled("|", dim));
+                Line::from(spans)
+            })
+            .collect();
+
+        if let Some(typed) = &self.goto {
+            lines.resize(height, Line::default());
+            lines.push(Line::from(vec![
+                Span::styled("Go to offset: 0x", Style::default().fg(theme.input_border)),
+                Span::styled(
+                    typed.trim_start_matches("0x"),
+                    Style::default().fg(theme.input_text),
+                ),
+                Span::styled("█", dim),
+            ]));
+        } else if let Some(error) = &self.error {
+            lines.resize(height, Line::default());
+            lines.push(Line::styled(error.as_str(), dim));
+        }
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.error = None;
+        if let Some(mut typed) = self.goto.take()
+            && self.handle_goto(key, &mut typed)
+        {
+            return true;
+        }
+        let width = self.width.get() as isize;
+        match key.code {
+            KeyCode::Left => self.move_by(-1),
+            KeyCode::Right => self.move_by(1),
+            KeyCode::Up => self.move_by(-width),
+            KeyCode::Down => self.move_by(width),
+            KeyCode::PageUp => self.move_by(-self.page()),
+            KeyCode::PageDown => self.move_by(self.page()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.move_by(isize::MAX),
+            KeyCode::Char('g') => self.goto = Some(String::new()),
+            _ => return false,
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("←→↑↓", "move"),
+            ("PgUp/PgDn", "page"),
+            ("g", "go to offset"),
+        ]
+    }
+}
@@ -0,0 +1,76 @@
+// =============================================================================
+// Image — a picture shown as a pane (needs the `images` feature)
+//
+//   ctx.show(ImageView::open(&*ctx.effects.files, "diagram.png")?);
+//
+// The terminal is asked once at startup (detect(), from run()) which
+// graphics protocol it speaks — kitty, iTerm2 or sixel — and how big its
+// font is. Terminals that answer none of them get an approximation in
+// colored half-block characters, which works anywhere with true color.
+// The image is scaled to fill the pane, keeping its shape.
+// =============================================================================
+
+use std::{cell::RefCell, io::Cursor, path::Path, sync::OnceLock};
+
+use color_eyre::{Result, eyre::WrapErr};
+use crossterm::event::KeyEvent;
+use ratatui::{Frame, layout::Rect};
+use ratatui_image::{Resize, StatefulImage, picker::Picker, protocol::StatefulProtocol};
+
+use crate::{effects::Files, pane::Pane, theme::Theme};
+
+static PICKER: OnceLock<Picker> = OnceLock::new();
+
+// Query the terminal; call with the TUI up, before reading any events
+pub fn detect() {
+    let picker = Picker::from_query_stdio().unwrap_or_else(|err| {
+        tracing::debug!("no terminal graphics ({err}), using half-blocks");
+        Picker::halfblocks()
+    });
+    tracing::info!(protocol = ?picker.protocol_type(), "image support");
+    let _ = PICKER.set(picker);
+}
+
+pub struct ImageView {
+    title: String,
+    image: RefCell<StatefulProtocol>, // re-encoded for the area it's drawn in
+}
+
+impl ImageView {
+    pub fn open(files: &dyn Files, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let decoded = (files.read_bytes(path))
+            .and_then(|bytes| image::ImageReader::new(Cursor::new(bytes)).with_guessed_format())
+            .wrap_err_with(|| format!("can't open {}", path.display()))?
+            .decode()
+            .wrap_err_with(|| format!("can't decode {}", path.display()))?;
+        let picker = PICKER.get_or_init(Picker::halfblocks);
+        let title = format!(
+            "{} ({}×{}, {:?})",
+            path.display(),
+            decoded.width(),
+            decoded.height(),
+            picker.protocol_type()
+        );
+        Ok(Self {
+            title,
+            image: RefCell::new(picker.new_resize_protocol(decoded)),
+        })
+    }
+}
+
+impl Pane for ImageView {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, _theme: &Theme) {
+        let mut image = self.image.borrow_mut();
+        let widget = StatefulImage::new().resize(Resize::Scale(None)); // small images grow too
+        frame.render_stateful_widget(widget, area, &mut *image);
+    }
+
+    fn handle_key(&mut self, _key: KeyEvent) -> bool {
+        false
+    }
+}
@@ -0,0 +1,370 @@
+// =============================================================================
+// Markdown — a rendered preview of a markdown document, shown as a pane
+//
+//   ctx.show(MarkdownView::file(ctx.effects.files.clone(), "README.md")); // re-rendered when saved
+//   ctx.show(MarkdownView::text("Help", HELP_MD));    // fixed text
+//
+//   let (editor, live) = EditorView::open(ctx.effects.files.clone(), "notes.md").live();
+//   ctx.show(Split::new(editor, MarkdownView::live("Preview", live)));
+//
+//   ↑/↓ PgUp/PgDn Home/End   scroll
+//
+// A file is checked for changes every tick; a LiveText follows an editor
+// keystroke by keystroke. Headings, emphasis, code, lists, block quotes,
+// links and rules are styled; anything else (tables, HTML) shows as text.
+// =============================================================================
+
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+use crate::{effects::Files, hints::Hints, pane::Pane, theme::Theme, widgets::editor::LiveText};
+
+pub struct MarkdownView {
+    title: String,
+    source: Source,
+    text: String,
+    top: usize,
+    height: Cell<usize>, // rows that fit, as of the last render
+}
+
+enum Source {
+    Fixed,
+    File {
+        files: Arc<dyn Files>,
+        path: PathBuf,
+        modified: Option<SystemTime>, // as of the last read
+    },
+    Live {
+        live: LiveText,
+        version: u64, // of the text we have
+    },
+}
+
+impl MarkdownView {
+    pub fn text(title: impl Into<String>, markdown: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            source: Source::Fixed,
+            text: markdown.into(),
+            top: 0,
+            height: Cell::new(1),
+        }
+    }
+
+    pub fn file(files: Arc<dyn Files>, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let mut view = Self::text(format!("Preview: {}", path.display()), "");
+        view.source = Source::File {
+            files,
+            path: path.to_path_buf(),
+            modified: None,
+        };
+        view.reload();
+        view
+    }
+
+    pub fn live(title: impl Into<String>, live: LiveText) -> Self {
+        let (version, text) = live.get();
+        Self {
+            source: Source::Live { live, version },
+            ..Self::text(title, text)
+        }
+    }
+
+    // Pick up a changed source; true if it did change
+    fn reload(&mut self) -> bool {
+        match &mut self.source {
+            Source::Fixed => false,
+            Source::File {
+                files,
+                path,
+                modified,
+            } => {
+                let now = files.metadata(path).ok().and_then(|meta| meta.modified);
+                if now.is_some() && now == *modified {
+                    return false;
+                }
+                *modified = now;
+                self.text = match files.read(path) {
+                    Ok(text) => text,
+                    Err(err) => format!("*Can't read {}: {err}*", path.display()),
+                };
+                true
+            }
+            Source::Live { live, version } => {
+                let (latest, text) = live.get();
+                if latest == *version {
+                    return false;
+                }
+                *version = latest;
+                self.text = text;
+                true
+            }
+        }
+    }
+
+    fn scroll_by(&mut self, step: isize) {
+        let last = self.text.lines().count().saturating_sub(self.height.get());
+        self.top = self.top.saturating_add_signed(step).min(last);
+    }
+}
+
+impl Pane for MarkdownView {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.height.set((area.height as usize).max(1));
+        let lines = render_markdown(&self.text, theme);
+        let preview = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((self.top as u16, 0));
+        frame.render_widget(preview, area);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let page = self.height.get() as isize;
+        match key.code {
+            KeyCode::Up => self.scroll_by(-1),
+            KeyCode::Down => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home => self.top = 0,
+            KeyCode::End => self.scroll_by(isize::MAX),
+            _ => return false,
+        }
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.reload()
+    }
+
+    fn hints(&self) -> Hints {
+        &[("↑↓", "scroll"), ("PgUp/PgDn", "page")]
+    }
+}
+
+// =============================================================================
+// Markdown → styled lines
+// =============================================================================
+fn render_markdown(text: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut out = Renderer {
+        accent: theme.input_border,
+        ..Renderer::default()
+    };
+    for event in Parser::new_ext(text, options) {
+        out.event(event);
+    }
+    out.flush();
+    out.lines
+}
+
+#[derive(Default)]
+struct Renderer {
+    accent: Color,
+    lines: Vec<Line<'static>>,
+    spans: Vec<Span<'static>>, // the line being built
+    styles: Vec<Style>,        // nested inline styles, innermost last
+    prefixes: Vec<String>,     // quote bars and list indents, outermost first
+    marker: Option<String>,    // a list item's bullet, for its first line
+    lists: Vec<Option<u64>>,   // each open list's next number (None = bullets)
+    links: Vec<String>,        // destinations of open links
+    in_code_block: bool,
+}
+
+impl Renderer {
+    fn style(&self) -> Style {
+        self.styles
+            .iter()
+            .fold(Style::default(), |style, s| style.patch(*s))
+    }
+
+    fn push(&mut self, text: impl Into<String>, style: Style) {
+        self.spans.push(Span::styled(text.into(), style));
+    }
+
+    // End the current line, if anything is on it
+    fn flush(&mut self) {
+        if self.spans.is_empty() {
+            return;
+        }
+        let mut prefix = self.prefixes.concat();
+        if let Some(marker) = self.marker.take() {
+            // The item's indent is the last prefix; its bullet goes there
+            let indent = self.prefixes.last().map_or(0, String::len);
+            prefix.truncate(prefix.len() - indent);
+            prefix.push_str(&marker);
+        }
+        let dim = Style::default().fg(self.accent);
+        let mut line = vec![Span::styled(prefix, dim)];
+        line.append(&mut self.spans);
+        self.lines.push(Line::from(line));
+    }
+
+    // A blank line between blocks, but never two in a row
+    fn blank(&mut self) {
+        self.flush();
+        if self.lines.last().is_some_and(|l| l.width() > 0) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        let code = Style::default().fg(Color::Yellow);
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.in_code_block => {
+                for line in text.lines() {
+                    self.push(format!("  {line}"), code);
+                    self.flush();
+                }
+            }
+            Event::Text(text) => self.push(text.to_string(), self.style()),
+            Event::Code(text) => self.push(text.to_string(), code),
+            Event::Html(text) | Event::InlineHtml(text) => {
+                let dim = Style::default().add_modifier(Modifier::DIM);
+                self.push(text.trim_end().to_string(), dim);
+            }
+            Event::SoftBreak => self.push(" ", self.style()),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.blank();
+                self.push("─".repeat(40), Style::default().fg(self.accent));
+                self.blank();
+            }
+            Event::TaskListMarker(done) => {
+                self.push(if done { "[x] " } else { "[ ] " }, self.style());
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.blank();
+                let mut style = Style::default()
+                    .fg(self.accent)
+                    .add_modifier(Modifier::BOLD);
+                if level == HeadingLevel::H1 {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                self.styles.push(style);
+            }
+            Tag::BlockQuote(_) => {
+                self.blank();
+                self.prefixes.push("│ ".into());
+                self.styles
+                    .push(Style::default().add_modifier(Modifier::ITALIC));
+            }
+            Tag::CodeBlock(kind) => {
+                self.blank();
+                if let CodeBlockKind::Fenced(lang) = kind
+                    && !lang.is_empty()
+                {
+                    let dim = Style::default().add_modifier(Modifier::DIM);
+                    self.push(format!("  {lang}"), dim);
+                    self.flush();
+                }
+                self.in_code_block = true;
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.blank();
+                }
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}. ", *n - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.prefixes.push(" ".repeat(marker.chars().count()));
+                self.marker = Some(marker);
+            }
+            Tag::Emphasis => self
+                .styles
+                .push(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self
+                .styles
+                .push(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self
+                .styles
+                .push(Style::default().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.styles
+                    .push(Style::default().add_modifier(Modifier::UNDERLINED));
+                self.links.push(dest_url.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.blank(),
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.blank();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.prefixes.pop();
+                self.styles.pop();
+                self.blank();
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.blank();
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank();
+                }
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.prefixes.pop();
+                self.marker = None;
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            TagEnd::Link | TagEnd::Image => {
+                self.styles.pop();
+                // Show where it goes, unless it's a link within the document
+                if let Some(url) = self.links.pop()
+                    && !url.starts_with('#')
+                {
+                    let dim = Style::default().add_modifier(Modifier::DIM);
+                    self.push(format!(" ({url})"), dim);
+                }
+            }
+            _ => {}
+        }
+    }
+}
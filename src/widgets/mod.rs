@@ -0,0 +1,58 @@
+// =============================================================================
+// Widgets — reusable popups and views for commands to build on
+//
+// Each file is one self-contained widget. Popups implement overlay::Overlay
+// and are opened with ctx.open(widget, callback); see overlay.rs. Views
+// implement pane::Pane and are shown with ctx.show(widget); see pane.rs.
+//
+//   file_picker.rs — browse the filesystem and choose a file or directory
+//   calendar.rs    — choose a date from a month view
+//   color.rs       — choose a palette color or type a hex one
+//   confirm.rs     — yes / no / cancel
+//   prompt.rs      — ask for a line of text
+//   select.rs      — choose one item from a list, fuzzy-filtered as you type
+//   form.rs        — labeled text fields, checkboxes and selects in one dialog
+//   table.rs       — sortable, scrollable rows and columns (a pane)
+//   chart.rs       — live time-series lines with a legend (a pane)
+//   diff.rs        — two texts side by side with changes highlighted (a pane)
+//   hex.rs         — a file's bytes as offset / hex / ASCII columns (a pane)
+//   editor.rs      — multi-line text editing, optionally of a file (a pane)
+//   markdown.rs    — rendered markdown that follows its file or editor (a pane)
+//   image_view.rs  — a picture via the terminal's graphics protocol (a pane;
+//                    needs the `images` feature)
+//   tree.rs        — collapsible hierarchy with lazily loaded children (a pane)
+// =============================================================================
+
+pub mod calendar;
+pub mod chart;
+pub mod color;
+pub mod confirm;
+pub mod diff;
+pub mod editor;
+pub mod file_picker;
+pub mod form;
+pub mod hex;
+#[cfg(feature = "images")]
+pub mod image_view;
+pub mod markdown;
+pub mod prompt;
+pub mod select;
+pub mod table;
+pub mod tree;
+
+pub use calendar::{Calendar, Date};
+pub use chart::ChartView;
+pub use color::ColorPicker;
+pub use confirm::{Choice, Confirm};
+pub use diff::DiffView;
+pub use editor::EditorView;
+pub use file_picker::FilePicker;
+pub use form::Form;
+pub use hex::HexView;
+#[cfg(feature = "images")]
+pub use image_view::ImageView;
+pub use markdown::MarkdownView;
+pub use prompt::Prompt;
+pub use select::Select;
+pub use table::TableView;
+pub use tree::{TreeNode, TreeView};
@@ -0,0 +1,230 @@
+// =============================================================================
+// Text prompt — ask one question and get a line of text back
+//
+//   ctx.open(Prompt::new("Enter filename:").initial("notes.txt"), |ctx, name| {
+//       …
+//   });
+//
+// The answer is edited in place:
+//
+//   ←/→  Home/End  ctrl-a/ctrl-e     move
+//   Backspace/Delete  ctrl-w          delete a character / the previous word
+//   ctrl-u                            delete everything before the cursor
+//   Enter / Esc                       answer / cancel
+//
+// .validate(check) refuses an answer that `check` rejects and shows why,
+// keeping the prompt open so it can be fixed.
+// =============================================================================
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::Paragraph,
+};
+
+use crate::{
+    input_line,
+    overlay::{self, Outcome, Overlay},
+    theme::Theme,
+};
+
+type Check = Box<dyn Fn(&str) -> Result<(), String> + Send>;
+
+pub struct Prompt {
+    question: String,
+    line: LineEdit,
+    check: Option<Check>,
+    error: Option<String>, // why the last Enter was refused
+}
+
+impl Prompt {
+    pub fn new(question: impl Into<String>) -> Self {
+        Self {
+            question: question.into(),
+            line: LineEdit::default(),
+            check: None,
+            error: None,
+        }
+    }
+
+    // Pre-fill the answer, with the cursor at the end
+    pub fn initial(mut self, text: impl Into<String>) -> Self {
+        self.line = LineEdit::new(text);
+        self
+    }
+
+    pub fn validate(mut self, check: impl Fn(&str) -> Result<(), String> + Send + 'static) -> Self {
+        self.check = Some(Box::new(check));
+        self
+    }
+}
+
+impl Overlay for Prompt {
+    type Answer = String;
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let width = (self.question.chars().count() as u16 + 4).clamp(40, 70);
+        let area = overlay::popup(frame, width, 6, "Input", theme);
+        let [question_row, input_row, error_row] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        frame.render_widget(Paragraph::new(self.question.as_str()), question_row);
+
+        self.line.render(
+            frame,
+            input_row,
+            Style::default().fg(theme.input_text),
+            true,
+        );
+
+        if let Some(error) = &self.error {
+            let style = Style::default().add_modifier(Modifier::BOLD);
+            frame.render_widget(Paragraph::new(error.as_str()).style(style), error_row);
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<String> {
+        match key.code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Enter => {
+                if let Some(check) = &self.check
+                    && let Err(why) = check(&self.line.text)
+                {
+                    self.error = Some(why);
+                    return Outcome::Open;
+                }
+                return Outcome::Done(std::mem::take(&mut self.line.text));
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            _ if !self.line.handle_key(key) => return Outcome::Open,
+            _ => {}
+        }
+        self.error = None; // they're fixing it
+        Outcome::Open
+    }
+}
+
+// =============================================================================
+// The mini editor — a String and a cursor (a byte offset, always on a
+// character boundary). Form's text fields use it too.
+// =============================================================================
+#[derive(Debug, Clone, Default)]
+pub(super) struct LineEdit {
+    pub(super) text: String,
+    cursor: usize,
+}
+
+impl LineEdit {
+    pub(super) fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let cursor = text.len();
+        Self { text, cursor }
+    }
+
+    // The editing keys listed at the top; false for anything else
+    pub(super) fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            _ if key.modifiers.contains(KeyModifiers::CONTROL) => match key.code {
+                KeyCode::Char('a') => self.home(),
+                KeyCode::Char('e') => self.end(),
+                KeyCode::Char('u') => self.delete_to_start(),
+                KeyCode::Char('w') => self.delete_word(),
+                _ => return false,
+            },
+            KeyCode::Left => self.left(),
+            KeyCode::Right => self.right(),
+            KeyCode::Home => self.home(),
+            KeyCode::End => self.end(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::ALT) => self.insert(c),
+            _ => return false,
+        }
+        true
+    }
+
+    // One row, scrolled sideways so the cursor stays visible in a long text
+    pub(super) fn render(&self, frame: &mut Frame, area: Rect, style: Style, focused: bool) {
+        let cursor = self.text[..self.cursor].chars().count();
+        let (shown, column) = match focused {
+            true => input_line::visible(&self.text, cursor, area.width),
+            false => (self.text.as_str(), 0),
+        };
+        frame.render_widget(Paragraph::new(shown).style(style), area);
+        if focused {
+            frame.set_cursor_position((area.x + column, area.y));
+        }
+    }
+
+    fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.text[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    fn left(&mut self) {
+        self.cursor = self.prev_boundary();
+    }
+
+    fn right(&mut self) {
+        self.cursor = self.next_boundary();
+    }
+
+    fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    fn end(&mut self) {
+        self.cursor = self.text.len();
+    }
+
+    fn backspace(&mut self) {
+        let start = self.prev_boundary();
+        self.text.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    fn delete(&mut self) {
+        let end = self.next_boundary();
+        self.text.drain(self.cursor..end);
+    }
+
+    fn delete_to_start(&mut self) {
+        self.text.drain(..self.cursor);
+        self.cursor = 0;
+    }
+
+    // Like a shell's ctrl-w: trailing spaces, then the word before them
+    fn delete_word(&mut self) {
+        let before = &self.text[..self.cursor];
+        let trimmed = before.trim_end();
+        let start = trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        self.text.drain(start..self.cursor);
+        self.cursor = start;
+    }
+}
@@ -0,0 +1,206 @@
+// =============================================================================
+// Fuzzy selector — pick one item from a list by typing part of it
+//
+//   ctx.open(Select::new("Theme", names), |ctx, name| { … });
+//
+// Typing filters the list to items containing the typed characters in
+// order ("drk" matches "dracula-dark"), best matches first: runs of
+// consecutive characters and matches at the start of a word rank higher.
+// ↑/↓ move, Enter chooses, Esc cancels. The answer is the item itself, so
+// any Display type works — names, paths, your own enums.
+// =============================================================================
+
+use std::fmt::Display;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    hints::Hints,
+    input_line,
+    overlay::{self, Outcome, Overlay},
+    theme::Theme,
+};
+
+pub struct Select<T> {
+    title: String,
+    items: Vec<T>,
+    labels: Vec<String>, // items[i].to_string(), computed once
+    filter: String,
+    matches: Vec<Match>, // best first
+    selected: usize,     // index into `matches`
+}
+
+struct Match {
+    item: usize,           // index into `items`
+    positions: Vec<usize>, // char indexes of the label that matched, for highlighting
+}
+
+impl<T: Display> Select<T> {
+    pub fn new(title: impl Into<String>, items: Vec<T>) -> Self {
+        let labels = items.iter().map(ToString::to_string).collect();
+        let mut select = Self {
+            title: title.into(),
+            items,
+            labels,
+            filter: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        select.refilter();
+        select
+    }
+
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i64, Match)> = self
+            .labels
+            .iter()
+            .enumerate()
+            .filter_map(|(item, label)| {
+                let (score, positions) = fuzzy_match(&self.filter, label)?;
+                Some((score, Match { item, positions }))
+            })
+            .collect();
+        // Stable, so equally good matches keep the caller's order
+        scored.sort_by_key(|(score, _)| -score);
+        self.matches = scored.into_iter().map(|(_, m)| m).collect();
+        self.selected = 0;
+    }
+
+    fn move_by(&mut self, step: isize) {
+        let last = self.matches.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(step).min(last);
+    }
+}
+
+impl<T: Display + Send + 'static> Overlay for Select<T> {
+    type Answer = T;
+
+    fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let widest = self.labels.iter().map(|l| l.chars().count()).max();
+        let width = (widest.unwrap_or(0) as u16 + 6).clamp(30, 70);
+        let height = (self.items.len() as u16 + 5).clamp(7, 20);
+        let area = overlay::popup(frame, width, height, &self.title, theme);
+        let [filter_row, list_area, count_row] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+
+        let filter = format!("> {}", self.filter);
+        frame.render_widget(Paragraph::new(filter.as_str()), filter_row);
+        frame.set_cursor_position((filter_row.x + input_line::width(&filter), filter_row.y));
+
+        let hit = Style::default()
+            .fg(theme.input_border)
+            .add_modifier(Modifier::BOLD);
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|m| {
+                let spans: Vec<Span> = self.labels[m.item]
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| match m.positions.contains(&i) {
+                        true => Span::styled(c.to_string(), hit),
+                        false => Span::raw(c.to_string()),
+                    })
+                    .collect();
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        let count = format!("{}/{}", self.matches.len(), self.items.len());
+        frame.render_widget(
+            Paragraph::new(count).style(Style::default().add_modifier(Modifier::DIM)),
+            count_row,
+        );
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Outcome<T> {
+        match key.code {
+            KeyCode::Esc => return Outcome::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Cancel;
+            }
+            KeyCode::Enter => {
+                let Some(m) = self.matches.get(self.selected) else {
+                    return Outcome::Open; // nothing matches; keep typing
+                };
+                let chosen = m.item;
+                return match std::mem::take(&mut self.items).into_iter().nth(chosen) {
+                    Some(item) => Outcome::Done(item),
+                    None => Outcome::Cancel,
+                };
+            }
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Down => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-10),
+            KeyCode::PageDown => self.move_by(10),
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.refilter();
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.filter.push(c);
+                self.refilter();
+            }
+            _ => {}
+        }
+        Outcome::Open
+    }
+
+    fn hints(&self) -> Hints {
+        &[("type", "filter"), ("Enter", "choose"), ("Esc", "cancel")]
+    }
+}
+
+// Does `label` contain the characters of `pattern` in order (ignoring
+// case)? If so, how well — higher is better — and where.
+//
+// Each matched character scores 1, plus a bonus for following the previous
+// match directly and for starting a word; characters skipped before the
+// first match cost a little, so earlier matches win ties.
+fn fuzzy_match(pattern: &str, label: &str) -> Option<(i64, Vec<usize>)> {
+    let mut positions = Vec::new();
+    let mut score = 0i64;
+    let mut chars = label.chars().enumerate();
+    let mut before: Option<char> = None; // the character before `c`
+
+    for p in pattern.chars().flat_map(char::to_lowercase) {
+        loop {
+            let (i, c) = chars.next()?;
+            let last_match = positions.last().copied();
+            let word_start = before.is_none_or(|b| !b.is_alphanumeric());
+            before = Some(c);
+            if c.to_lowercase().eq(std::iter::once(p)) {
+                score += 1;
+                if last_match.is_some_and(|last| last + 1 == i) {
+                    score += 4;
+                }
+                if word_start {
+                    score += 3;
+                }
+                positions.push(i);
+                break;
+            }
+        }
+    }
+    let first = positions.first().copied().unwrap_or(0) as i64;
+    Some((score * 10 - first, positions))
+}
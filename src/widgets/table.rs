@@ -0,0 +1,494 @@
+// =============================================================================
+// Table — rows and columns a command fills in, shown as a pane
+//
+//   let table = TableView::new("Processes", ["PID", "Name", "CPU %"])
+//       .rows(procs.iter().map(|p| vec![p.pid.to_string(), p.name.clone(), …]));
+//   ctx.show(table);
+//
+//   ↑/↓ PgUp/PgDn Home/End   select a row
+//   ←/→                      scroll columns sideways
+//   1–9                      sort by that column; again to reverse
+//   /                        filter rows as you type (clear it to show all):
+//                              ada        a cell in any column contains "ada"
+//                              name=ada   the name column contains "ada"
+//                              age>30     (or <) a number column's value
+//
+// Each column's kind (int, float, bool, text) is inferred from its cells:
+// number columns are right-aligned and sort by value, blanks last.
+// show_kinds() puts the kinds under the headers. Columns are as wide as
+// their widest cell (up to MAX_COLUMN_WIDTH).
+//
+// More rows can be pushed in after it's shown (push_rows), so a pane
+// reading a big file can fill it in as the user scrolls (see csv.rs).
+// =============================================================================
+
+use std::{cell::Cell, cmp::Ordering};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Paragraph, Row, Table, TableState},
+};
+
+use crate::{hints::Hints, pane::Pane, theme::Theme};
+
+const MAX_COLUMN_WIDTH: usize = 40;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Integer,
+    Float,
+    Bool,
+    Text,
+}
+
+impl Kind {
+    fn of(cell: &str) -> Option<Kind> {
+        let cell = cell.trim();
+        if cell.is_empty() {
+            None // blanks fit any kind
+        } else if cell.parse::<i64>().is_ok() {
+            Some(Kind::Integer)
+        } else if cell.parse::<f64>().is_ok() && cell.contains(|c: char| c.is_ascii_digit()) {
+            Some(Kind::Float) // but not "inf" or "NaN"
+        } else if cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false") {
+            Some(Kind::Bool)
+        } else {
+            Some(Kind::Text)
+        }
+    }
+
+    // The narrowest kind that fits cells of both
+    fn widen(kind: Option<Kind>, cell: Option<Kind>) -> Option<Kind> {
+        match (kind, cell) {
+            (kind, None) => kind,
+            (None, cell) => cell,
+            (Some(a), Some(b)) if a == b => Some(a),
+            (Some(Kind::Integer | Kind::Float), Some(Kind::Integer | Kind::Float)) => {
+                Some(Kind::Float)
+            }
+            _ => Some(Kind::Text),
+        }
+    }
+
+    fn name(kind: Option<Kind>) -> &'static str {
+        match kind {
+            Some(Kind::Integer) => "int",
+            Some(Kind::Float) => "float",
+            Some(Kind::Bool) => "bool",
+            Some(Kind::Text) => "text",
+            None => "empty",
+        }
+    }
+
+    fn is_number(kind: Option<Kind>) -> bool {
+        matches!(kind, Some(Kind::Integer | Kind::Float))
+    }
+}
+
+enum Filter {
+    Anywhere(String), // lowercased
+    Contains(usize, String),
+    Above(usize, f64),
+    Below(usize, f64),
+}
+
+pub struct TableView {
+    title: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    kinds: Vec<Option<Kind>>, // per column, widened as rows come in
+    widths: Vec<usize>,       // likewise
+    show_kinds: bool,
+    shown: Vec<usize>, // indexes into rows that pass the filter, in order
+    filter: Option<(String, Filter)>, // as typed, and parsed
+    typing: Option<String>, // a filter being typed after `/`
+    error: Option<String>, // why the last filter didn't apply
+    sort: Option<(usize, bool)>, // (column, descending)
+    selected: usize,   // index into shown
+    first_column: usize, // horizontal scroll
+    offset: Cell<usize>, // first visible row; render() keeps the selection in view
+}
+
+impl TableView {
+    pub fn new<S: Into<String>>(
+        title: impl Into<String>,
+        headers: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        Self {
+            title: title.into(),
+            kinds: vec![None; headers.len()],
+            widths: headers.iter().map(|h| h.chars().count() + 2).collect(), // room for ▲
+            headers,
+            rows: Vec::new(),
+            show_kinds: false,
+            shown: Vec::new(),
+            filter: None,
+            typing: None,
+            error: None,
+            sort: None,
+            selected: 0,
+            first_column: 0,
+            offset: Cell::new(0),
+        }
+    }
+
+    pub fn rows(mut self, rows: impl IntoIterator<Item = Vec<String>>) -> Self {
+        self.push_rows(rows);
+        self
+    }
+
+    pub fn sorted_by(mut self, column: usize) -> Self {
+        self.sort_by(column, false);
+        self
+    }
+
+    pub fn show_kinds(mut self) -> Self {
+        self.show_kinds = true;
+        self
+    }
+
+    // Add rows to a table that may already be on screen. They're kept in
+    // sort order and filtered like the rest.
+    pub fn push_rows(&mut self, rows: impl IntoIterator<Item = Vec<String>>) {
+        let start = self.rows.len();
+        self.rows.extend(rows);
+        if self.rows.len() == start {
+            return;
+        }
+        for row in &self.rows[start..] {
+            for (col, text) in row.iter().enumerate().take(self.headers.len()) {
+                self.kinds[col] = Kind::widen(self.kinds[col], Kind::of(text));
+                let width = text.chars().count().min(MAX_COLUMN_WIDTH);
+                self.widths[col] = self.widths[col].max(width);
+            }
+        }
+        if let Some((column, descending)) = self.sort {
+            self.sort_by(column, descending);
+        } else {
+            let filter = self.filter.as_ref().map(|(_, f)| f);
+            let passing = (start..self.rows.len()).filter(|&i| passes(filter, &self.rows[i]));
+            self.shown.extend(passing);
+        }
+    }
+
+    // Replace every row, say with a fresh reading of what the table shows.
+    // The sort, the filter and the selected row (found by its first cell)
+    // stay as they were. Only `ps`, `docker`, `k8s` and `feed` use it so
+    // far.
+    #[cfg_attr(
+        not(any(
+            feature = "sysinfo",
+            feature = "docker",
+            feature = "k8s",
+            feature = "feeds"
+        )),
+        allow(dead_code)
+    )]
+    pub fn set_rows(&mut self, rows: impl IntoIterator<Item = Vec<String>>) {
+        let key = self.selected_row().and_then(|row| row.first()).cloned();
+        self.rows.clear();
+        self.shown.clear();
+        self.kinds.fill(None);
+        self.push_rows(rows);
+        let found = key.and_then(|key| {
+            self.shown
+                .iter()
+                .position(|&i| cell(&self.rows[i], 0) == key)
+        });
+        self.selected = found.unwrap_or(self.selected);
+        self.move_by(0);
+    }
+
+    #[cfg_attr(
+        not(any(
+            feature = "sysinfo",
+            feature = "docker",
+            feature = "k8s",
+            feature = "feeds"
+        )),
+        allow(dead_code)
+    )]
+    pub fn selected_row(&self) -> Option<&[String]> {
+        let &i = self.shown.get(self.selected)?;
+        Some(&self.rows[i])
+    }
+
+    // How many rows are below the selected one; a pane loading rows lazily
+    // pushes more when this gets small
+    pub fn rows_below(&self) -> usize {
+        self.shown.len().saturating_sub(self.selected + 1)
+    }
+
+    // Is a filter being typed? (Keys go to it rather than the table)
+    pub fn typing(&self) -> bool {
+        self.typing.is_some()
+    }
+
+    pub fn sort_by(&mut self, column: usize, descending: bool) {
+        if column >= self.headers.len() {
+            return;
+        }
+        let kind = self.kinds[column];
+        self.rows.sort_by(|a, b| {
+            let order = compare_cells(kind, cell(a, column), cell(b, column));
+            if descending { order.reverse() } else { order }
+        });
+        self.sort = Some((column, descending));
+        self.refilter();
+    }
+
+    fn refilter(&mut self) {
+        let filter = self.filter.as_ref().map(|(_, f)| f);
+        self.shown = (0..self.rows.len())
+            .filter(|&i| passes(filter, &self.rows[i]))
+            .collect();
+        self.selected = 0;
+    }
+
+    fn apply_filter(&mut self, typed: &str) {
+        if typed.trim().is_empty() {
+            self.filter = None;
+        } else {
+            match self.parse_filter(typed) {
+                Ok(filter) => self.filter = Some((typed.trim().to_string(), filter)),
+                Err(why) => return self.error = Some(why),
+            }
+        }
+        self.refilter();
+    }
+
+    fn parse_filter(&self, typed: &str) -> Result<Filter, String> {
+        let typed = typed.trim();
+        let Some(at) = typed.find(['=', '<', '>']) else {
+            return Ok(Filter::Anywhere(typed.to_lowercase()));
+        };
+        let (name, value) = (typed[..at].trim(), typed[at + 1..].trim());
+        let Some(column) = self
+            .headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(name))
+        else {
+            return Err(format!("No column called '{name}'"));
+        };
+        if typed.as_bytes()[at] == b'=' {
+            return Ok(Filter::Contains(column, value.to_lowercase()));
+        }
+        let Ok(number) = value.parse::<f64>() else {
+            return Err(format!("'{value}' isn't a number"));
+        };
+        Ok(match typed.as_bytes()[at] {
+            b'>' => Filter::Above(column, number),
+            _ => Filter::Below(column, number),
+        })
+    }
+
+    // The filter line swallows text, filtering as it's typed; Enter, or
+    // any key that isn't typing (arrows and so on), puts it away
+    fn handle_typing(&mut self, key: KeyEvent, typed: &mut String) -> bool {
+        match key.code {
+            KeyCode::Char(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => return true,
+            _ => return false,
+        }
+        self.apply_filter(typed);
+        self.typing = Some(typed.clone());
+        true
+    }
+
+    fn move_by(&mut self, step: isize) {
+        let last = self.shown.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(step).min(last);
+    }
+}
+
+fn cell(row: &[String], column: usize) -> &str {
+    row.get(column).map_or("", String::as_str)
+}
+
+// Numbers by value with blanks last, anything else as text ignoring case
+fn compare_cells(kind: Option<Kind>, a: &str, b: &str) -> Ordering {
+    if Kind::is_number(kind) {
+        match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+            (Ok(x), Ok(y)) => return x.total_cmp(&y),
+            (Ok(_), Err(_)) => return Ordering::Less,
+            (Err(_), Ok(_)) => return Ordering::Greater,
+            _ => {}
+        }
+    }
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+fn passes(filter: Option<&Filter>, row: &[String]) -> bool {
+    let number = |col: usize| cell(row, col).trim().parse::<f64>().ok();
+    match filter {
+        None => true,
+        Some(Filter::Anywhere(text)) => row.iter().any(|c| c.to_lowercase().contains(text)),
+        Some(Filter::Contains(col, text)) => cell(row, *col).to_lowercase().contains(text),
+        Some(Filter::Above(col, limit)) => number(*col).is_some_and(|n| n > *limit),
+        Some(Filter::Below(col, limit)) => number(*col).is_some_and(|n| n < *limit),
+    }
+}
+
+impl Pane for TableView {
+    fn title(&self) -> String {
+        match &self.filter {
+            Some((typed, _)) => format!(
+                "{} ({} of {} rows match {typed})",
+                self.title,
+                self.shown.len(),
+                self.rows.len()
+            ),
+            None => format!("{} ({} rows)", self.title, self.rows.len()),
+        }
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let columns = self.first_column..self.headers.len();
+        let header_style = Style::default()
+            .fg(theme.input_border)
+            .add_modifier(Modifier::BOLD);
+        let dim = Style::default().add_modifier(Modifier::DIM);
+        let aligned = |col: usize, line: Line<'static>| {
+            if Kind::is_number(self.kinds[col]) {
+                line.right_aligned()
+            } else {
+                line
+            }
+        };
+
+        let header = Row::new(columns.clone().map(|col| {
+            let arrow = match self.sort {
+                Some((c, false)) if c == col => " ▲",
+                Some((c, true)) if c == col => " ▼",
+                _ => "",
+            };
+            let name = Line::styled(format!("{}{arrow}", self.headers[col]), header_style);
+            let mut text = Text::from(aligned(col, name));
+            if self.show_kinds {
+                text.push_line(aligned(col, Line::styled(Kind::name(self.kinds[col]), dim)));
+            }
+            text
+        }))
+        .height(if self.show_kinds { 2 } else { 1 });
+
+        let footer = u16::from(self.typing.is_some() || self.error.is_some());
+        let header_height = if self.show_kinds { 2 } else { 1 };
+        let table_area = Rect {
+            height: area.height.saturating_sub(footer),
+            ..area
+        };
+
+        // Keep the selected row on screen, scrolling no more than needed
+        let visible = table_area.height.saturating_sub(header_height).max(1) as usize;
+        let mut offset = self.offset.get();
+        if self.selected < offset {
+            offset = self.selected;
+        } else if self.selected >= offset + visible {
+            offset = self.selected + 1 - visible;
+        }
+        self.offset.set(offset);
+
+        // Only the rows on screen, so a million-row table draws as fast as a
+        // small one
+        let rows = self.shown.iter().skip(offset).take(visible).map(|&i| {
+            let row = &self.rows[i];
+            Row::new(columns.clone().map(|col| {
+                let text = cell(row, col);
+                let text = match text.char_indices().nth(MAX_COLUMN_WIDTH - 1) {
+                    Some((cut, _)) => format!("{}…", &text[..cut]),
+                    None => text.to_string(),
+                };
+                aligned(col, Line::from(text))
+            }))
+        });
+        let widths = columns.clone().map(|col| {
+            let kind = if self.show_kinds {
+                Kind::name(self.kinds[col]).len()
+            } else {
+                0
+            };
+            Constraint::Length(self.widths[col].max(kind) as u16)
+        });
+        let table = Table::new(rows, widths)
+            .header(header)
+            .column_spacing(2)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        let mut state = TableState::default()
+            .with_selected((!self.shown.is_empty()).then_some(self.selected - offset));
+        frame.render_stateful_widget(table, table_area, &mut state);
+
+        let footer_area = Rect {
+            y: area.y + table_area.height,
+            height: footer,
+            ..area
+        };
+        if let Some(typed) = &self.typing {
+            let line = Line::from(vec![
+                Span::styled("Filter: ", Style::default().fg(theme.input_border)),
+                Span::styled(typed.as_str(), Style::default().fg(theme.input_text)),
+                Span::styled("█", dim),
+                Span::styled(
+                    match &self.error {
+                        Some(error) => format!("   {error}"),
+                        None => "   text, column=text, column>number".to_string(),
+                    },
+                    dim,
+                ),
+            ]);
+            frame.render_widget(Paragraph::new(line), footer_area);
+        } else if let Some(error) = &self.error {
+            frame.render_widget(Line::styled(error.as_str(), dim), footer_area);
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.error = None;
+        if let Some(mut typed) = self.typing.take()
+            && self.handle_typing(key, &mut typed)
+        {
+            return true;
+        }
+        match key.code {
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Down => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-10),
+            KeyCode::PageDown => self.move_by(10),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.move_by(isize::MAX),
+            KeyCode::Left => self.first_column = self.first_column.saturating_sub(1),
+            KeyCode::Right => {
+                self.first_column =
+                    (self.first_column + 1).min(self.headers.len().saturating_sub(1));
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                let column = c as usize - '1' as usize;
+                let descending = self.sort == Some((column, false));
+                self.sort_by(column, descending);
+            }
+            KeyCode::Char('/') => {
+                let typed = self.filter.as_ref().map(|(typed, _)| typed.clone());
+                self.typing = Some(typed.unwrap_or_default());
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("↑↓", "select"),
+            ("←→", "scroll"),
+            ("1-9", "sort"),
+            ("/", "filter"),
+        ]
+    }
+}
@@ -0,0 +1,295 @@
+// =============================================================================
+// Tree — collapsible hierarchy shown as a pane (file trees, JSON, …)
+//
+//   let tree = TreeView::new("Files", vec![TreeNode::lazy("src", "/repo/src")])
+//       .loader(|key| read_children(key));
+//   ctx.show(tree);
+//
+//   ↑/↓ Home/End      move
+//   →  / ←            expand (then step into) / collapse (then step out)
+//   Enter / Space     expand or collapse
+//
+// Children can be given up front (TreeNode::branch) or fetched the first
+// time a node is expanded (TreeNode::lazy): the loader is called with the
+// node's key and returns its children. Loading happens on the UI thread, so
+// keep it quick — a directory listing is fine, a network call isn't.
+// =============================================================================
+
+use std::cell::Cell;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState},
+};
+
+use crate::{hints::Hints, pane::Pane, theme::Theme};
+
+#[derive(Debug, Clone)]
+pub struct TreeNode {
+    label: String,
+    key: String, // handed to the loader; defaults to the label
+    children: Children,
+    expanded: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Children {
+    None,
+    Loaded(Vec<TreeNode>),
+    Lazy, // ask the loader when expanded
+}
+
+impl TreeNode {
+    pub fn leaf(label: impl Into<String>) -> Self {
+        let label = label.into();
+        Self {
+            key: label.clone(),
+            label,
+            children: Children::None,
+            expanded: false,
+        }
+    }
+
+    pub fn branch(label: impl Into<String>, children: Vec<TreeNode>) -> Self {
+        Self {
+            children: Children::Loaded(children),
+            ..Self::leaf(label)
+        }
+    }
+
+    pub fn lazy(label: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            children: Children::Lazy,
+            ..Self::leaf(label)
+        }
+    }
+
+    pub fn expanded(mut self) -> Self {
+        self.expanded = true;
+        self
+    }
+
+    fn has_children(&self) -> bool {
+        match &self.children {
+            Children::None => false,
+            Children::Loaded(children) => !children.is_empty(),
+            Children::Lazy => true,
+        }
+    }
+}
+
+type Loader = Box<dyn FnMut(&str) -> Vec<TreeNode> + Send>;
+
+pub struct TreeView {
+    title: String,
+    roots: Vec<TreeNode>,
+    loader: Option<Loader>,
+    selected: usize,     // index into visible()
+    offset: Cell<usize>, // first visible row; render() keeps the selection in view
+}
+
+// A row on screen: where the node is in the tree, and how deep
+struct Visible {
+    path: Vec<usize>,
+    depth: usize,
+}
+
+impl TreeView {
+    pub fn new(title: impl Into<String>, roots: Vec<TreeNode>) -> Self {
+        Self {
+            title: title.into(),
+            roots,
+            loader: None,
+            selected: 0,
+            offset: Cell::new(0),
+        }
+    }
+
+    pub fn loader(mut self, loader: impl FnMut(&str) -> Vec<TreeNode> + Send + 'static) -> Self {
+        self.loader = Some(Box::new(loader));
+        self
+    }
+
+    // Nodes whose ancestors are all expanded, in display order
+    fn visible(&self) -> Vec<Visible> {
+        fn walk(nodes: &[TreeNode], path: &mut Vec<usize>, out: &mut Vec<Visible>) {
+            for (i, node) in nodes.iter().enumerate() {
+                path.push(i);
+                out.push(Visible {
+                    path: path.clone(),
+                    depth: path.len() - 1,
+                });
+                if node.expanded
+                    && let Children::Loaded(children) = &node.children
+                {
+                    walk(children, path, out);
+                }
+                path.pop();
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.roots, &mut Vec::new(), &mut out);
+        out
+    }
+
+    fn node(&self, path: &[usize]) -> Option<&TreeNode> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.get(*first)?;
+        for &i in rest {
+            let Children::Loaded(children) = &node.children else {
+                return None;
+            };
+            node = children.get(i)?;
+        }
+        Some(node)
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode> {
+        let (first, rest) = path.split_first()?;
+        let mut node = self.roots.get_mut(*first)?;
+        for &i in rest {
+            let Children::Loaded(children) = &mut node.children else {
+                return None;
+            };
+            node = children.get_mut(i)?;
+        }
+        Some(node)
+    }
+
+    fn selected_path(&self) -> Option<Vec<usize>> {
+        self.visible()
+            .into_iter()
+            .nth(self.selected)
+            .map(|v| v.path)
+    }
+
+    fn set_expanded(&mut self, path: &[usize], expanded: bool) {
+        // Take the loader out so it can be called while a node is borrowed
+        let mut loader = self.loader.take();
+        if let Some(node) = self.node_mut(path) {
+            if expanded && matches!(node.children, Children::Lazy) {
+                let children = loader.as_mut().map(|load| load(&node.key));
+                node.children = Children::Loaded(children.unwrap_or_default());
+            }
+            node.expanded = expanded && node.has_children();
+        }
+        self.loader = loader;
+    }
+
+    // → : expand a collapsed node, or step into an expanded one
+    fn expand_or_enter(&mut self) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        let Some(node) = self.node(&path) else {
+            return;
+        };
+        if node.expanded {
+            if node.has_children() {
+                self.selected += 1;
+            }
+        } else {
+            self.set_expanded(&path, true);
+        }
+    }
+
+    // ← : collapse an expanded node, or step out to the parent
+    fn collapse_or_leave(&mut self) {
+        let Some(path) = self.selected_path() else {
+            return;
+        };
+        if self.node(&path).is_some_and(|n| n.expanded) {
+            self.set_expanded(&path, false);
+        } else if path.len() > 1 {
+            let parent = &path[..path.len() - 1];
+            if let Some(pos) = self.visible().iter().position(|v| v.path == parent) {
+                self.selected = pos;
+            }
+        }
+    }
+
+    fn toggle(&mut self) {
+        if let Some(path) = self.selected_path() {
+            let expanded = self.node(&path).is_some_and(|n| n.expanded);
+            self.set_expanded(&path, !expanded);
+        }
+    }
+
+    fn move_by(&mut self, step: isize) {
+        let last = self.visible().len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(step).min(last);
+    }
+}
+
+impl Pane for TreeView {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let visible = self.visible();
+
+        let height = (area.height as usize).max(1);
+        let mut offset = self.offset.get();
+        if self.selected < offset {
+            offset = self.selected;
+        } else if self.selected >= offset + height {
+            offset = self.selected + 1 - height;
+        }
+        self.offset.set(offset);
+
+        let branch = Style::default().fg(theme.input_border);
+        let items: Vec<ListItem> = visible
+            .iter()
+            .filter_map(|v| {
+                let node = self.node(&v.path)?;
+                let indent = "  ".repeat(v.depth);
+                let (marker, style) = match (node.has_children(), node.expanded) {
+                    (false, _) => ("  ", Style::default()),
+                    (true, false) => ("▸ ", branch),
+                    (true, true) => ("▾ ", branch),
+                };
+                Some(ListItem::new(Line::from(vec![
+                    Span::raw(indent),
+                    Span::styled(marker, branch),
+                    Span::styled(node.label.as_str(), style),
+                ])))
+            })
+            .collect();
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default()
+            .with_offset(offset)
+            .with_selected((!visible.is_empty()).then_some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Up => self.move_by(-1),
+            KeyCode::Down => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-10),
+            KeyCode::PageDown => self.move_by(10),
+            KeyCode::Home => self.selected = 0,
+            KeyCode::End => self.move_by(isize::MAX),
+            KeyCode::Right => self.expand_or_enter(),
+            KeyCode::Left => self.collapse_or_leave(),
+            KeyCode::Enter | KeyCode::Char(' ') => self.toggle(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn hints(&self) -> Hints {
+        &[
+            ("↑↓", "move"),
+            ("→←", "expand / collapse"),
+            ("Enter", "toggle"),
+        ]
+    }
+}